@@ -183,12 +183,48 @@ impl ComponentObjects {
             .map(|(lazy_map_id, lazy_map)| (lazy_map_id, lazy_map.get(key).map(|v| v.to_vec())))
     }
 
+    pub fn remove_lazy_map_entry(
+        &mut self,
+        lazy_map_id: &LazyMapId,
+        key: &[u8],
+    ) -> Option<(LazyMapId, Option<Vec<u8>>)> {
+        if self.borrowed_vault.is_some() {
+            panic!("Should not be removing while value is being borrowed");
+        }
+
+        self.get_lazy_map_mut(lazy_map_id)
+            .map(|(lazy_map_id, lazy_map)| (lazy_map_id, lazy_map.remove(key)))
+    }
+
+    /// Lists up to `limit` entries whose key is greater than `after`, in ascending order of key bytes.
+    pub fn list_lazy_map_entries(
+        &mut self,
+        lazy_map_id: &LazyMapId,
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+        if self.borrowed_vault.is_some() {
+            panic!("Should not be listing while value is being borrowed");
+        }
+
+        self.get_lazy_map_mut(lazy_map_id).map(|(_, lazy_map)| {
+            let mut entries: Vec<(Vec<u8>, Vec<u8>)> = lazy_map
+                .iter()
+                .filter(|(key, _)| after.iter().all(|after| key.as_slice() > *after))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            entries.sort();
+            entries.truncate(limit);
+            entries
+        })
+    }
+
     fn get_lazy_map_mut(
         &mut self,
         lazy_map_id: &LazyMapId,
     ) -> Option<(LazyMapId, &mut HashMap<Vec<u8>, Vec<u8>>)> {
         if self.borrowed_vault.is_some() {
-            panic!("Should not be taking while value is being borrowed");
+            panic!("Should not be accessing a lazy map while value is being borrowed");
         }
 
         // TODO: Optimize to prevent iteration
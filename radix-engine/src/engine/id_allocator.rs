@@ -3,6 +3,7 @@ use scrypto::engine::types::*;
 use scrypto::rust::ops::Range;
 
 pub const ECDSA_TOKEN_BUCKET_ID: BucketId = 0;
+pub const ED25519_TOKEN_BUCKET_ID: BucketId = 1;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IdSpace {
@@ -0,0 +1,57 @@
+/// The default maximum depth of nested SNode invocations.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 8;
+
+/// The maximum size of a WASM instance's linear memory, in 64 KiB pages.
+///
+/// Published code is instrumented to declare at most this many pages, so that growing the
+/// memory beyond it fails within the instance.
+pub const MAX_WASM_MEMORY_PAGES: u32 = 512;
+
+/// The maximum number of cost units the ABI exports of a package can consume, all together,
+/// when the package is published.
+pub const MAX_ABI_COST_UNITS: u32 = 5_000_000;
+
+/// The maximum number of entries a blueprint can list from a lazy map in a single call.
+pub const MAX_LAZY_MAP_PAGE_SIZE: u32 = 1000;
+
+/// The default maximum declared size of a WASM instance's linear memory, in 64 KiB pages.
+pub const DEFAULT_MAX_WASM_MEMORY_PAGES: u32 = MAX_WASM_MEMORY_PAGES;
+
+/// The default maximum size of a substate written by a blueprint, in bytes.
+pub const DEFAULT_MAX_SUBSTATE_SIZE: usize = 1024 * 1024;
+
+/// The default maximum number of buckets a single call frame can hold.
+pub const DEFAULT_MAX_BUCKETS_PER_FRAME: usize = 512;
+
+/// The default maximum number of proofs a single call frame can hold.
+pub const DEFAULT_MAX_PROOFS_PER_FRAME: usize = 512;
+
+/// Bounds on the resources a transaction can use, other than cost units.
+///
+/// Exceeding any of them fails the transaction with a dedicated `RuntimeError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionLimits {
+    /// The maximum depth of nested SNode invocations, with the transaction itself at depth 0
+    pub max_call_depth: usize,
+    /// The maximum declared size of a WASM instance's linear memory, in 64 KiB pages; packages
+    /// declaring more can not be instantiated
+    pub max_wasm_memory_pages: u32,
+    /// The maximum size of a component state or lazy map entry, in bytes
+    pub max_substate_size: usize,
+    /// The maximum number of buckets a single call frame can hold
+    pub max_buckets_per_frame: usize,
+    /// The maximum number of proofs a single call frame can hold
+    pub max_proofs_per_frame: usize,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self {
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            max_wasm_memory_pages: DEFAULT_MAX_WASM_MEMORY_PAGES,
+            max_substate_size: DEFAULT_MAX_SUBSTATE_SIZE,
+            max_buckets_per_frame: DEFAULT_MAX_BUCKETS_PER_FRAME,
+            max_proofs_per_frame: DEFAULT_MAX_PROOFS_PER_FRAME,
+        }
+    }
+}
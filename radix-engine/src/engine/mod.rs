@@ -1,13 +1,33 @@
 mod component_objects;
 mod id_allocator;
 mod id_validator;
+mod limits;
 mod process;
 mod track;
+mod wasm_engine;
 mod wasm_env;
+#[cfg(feature = "wasmer")]
+mod wasmer_engine;
+mod wasmi_engine;
 
 pub use component_objects::*;
 pub use id_allocator::*;
 pub use id_validator::*;
+pub use limits::*;
 pub use process::{Process, SNodeState, SystemApi};
-pub use track::{CommitReceipt, Track};
-pub use wasm_env::{EnvModuleResolver, ENGINE_FUNCTION_INDEX, ENGINE_FUNCTION_NAME};
+pub use track::{BalanceChange, CommitReceipt, SubstateChange, SubstateKey, Track};
+pub use wasm_engine::{WasmEngine, WasmInstance, WasmRuntime, DEFAULT_MODULE_CACHE_SIZE};
+pub use wasm_env::{
+    EnvModuleResolver, COST_UNIT_FUNCTION_INDEX, COST_UNIT_FUNCTION_NAME, ENGINE_FUNCTION_INDEX,
+    ENGINE_FUNCTION_NAME, MAX_STACK_HEIGHT, STACK_HEIGHT_GLOBAL_NAME,
+};
+#[cfg(feature = "wasmer")]
+pub use wasmer_engine::{WasmerEngine, WasmerInstance};
+pub use wasmi_engine::{WasmiEngine, WasmiInstance};
+
+/// The WASM engine used by default.
+#[cfg(not(feature = "wasmer"))]
+pub type DefaultWasmEngine = WasmiEngine;
+/// The WASM engine used by default.
+#[cfg(feature = "wasmer")]
+pub type DefaultWasmEngine = WasmerEngine;
@@ -2,10 +2,12 @@ use colored::*;
 
 use sbor::*;
 use sbor::path::SborPath;
+use scrypto::abi::Mutability;
 use scrypto::buffer::*;
 use scrypto::core::{SNodeRef, ScryptoActor};
 use scrypto::engine::api::*;
 use scrypto::engine::types::*;
+use scrypto::resource::AccessRules;
 use scrypto::rust::borrow::ToOwned;
 use scrypto::rust::collections::*;
 use scrypto::rust::fmt;
@@ -15,7 +17,6 @@ use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
-use wasmi::*;
 
 use crate::engine::process::LazyMapState::{Committed, Uncommitted};
 use crate::engine::*;
@@ -96,14 +97,23 @@ pub trait SystemApi {
     fn create_resource(&mut self, resource_manager: ResourceManager) -> ResourceAddress;
 
     fn create_package(&mut self, package: Package) -> PackageAddress;
+
+    fn lock_fee(&mut self, vault_id: VaultId, fee: ResourceContainer) -> Result<(), RuntimeError>;
+
+    fn record_resource_movement(
+        &mut self,
+        movement: ResourceMovement,
+        resource_address: ResourceAddress,
+        resource: AmountOrIds,
+    );
 }
 
 pub enum SNodeState {
-    Transaction(TransactionProcess),
+    Transaction(Box<TransactionProcess>),
     PackageStatic,
     AuthZone(AuthZone),
     Worktop(Worktop),
-    Scrypto(ScryptoActorInfo, Option<Component>),
+    Scrypto(ScryptoActorInfo, Option<(Component, Mutability)>),
     ResourceStatic,
     ResourceRef(ResourceAddress, ResourceManager),
     BucketRef(BucketId, Bucket),
@@ -118,8 +128,6 @@ pub struct Interpreter {
     actor: ScryptoActorInfo,
     function: String,
     args: Vec<ScryptoValue>,
-    module: ModuleRef,
-    memory: MemoryRef,
 }
 
 /// Qualitative states for a WASM process
@@ -129,6 +137,7 @@ enum InterpreterState<'a> {
     Component {
         component_address: ComponentAddress,
         component: &'a mut Component,
+        mutability: Mutability,
         initial_loaded_object_refs: ComponentObjectRefs,
     },
 }
@@ -280,6 +289,8 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     .map_err(RuntimeError::WorktopError)
             }
             SNodeState::Scrypto(actor, component_state) => {
+                let instantiation_cost_per_byte =
+                    self.track.fee_table().wasm_instantiation_per_byte();
                 let package = self.track.get_package(actor.package_address()).ok_or(
                     RuntimeError::PackageNotFound(actor.package_address().clone()),
                 )?;
@@ -291,9 +302,12 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     ));
                 }
 
-                let (module, memory) = package.load_module().unwrap();
+                let instantiation_cost =
+                    (package.code().len() as u32).saturating_mul(instantiation_cost_per_byte);
+                let mut instance = self.track.instantiate_package(actor.package_address())?;
+                self.track.consume_cost_units(instantiation_cost)?;
 
-                let (interpreter_state, args) = if let Some(component) = component_state {
+                let (interpreter_state, args) = if let Some((component, mutability)) = component_state {
                     let component_address = actor.component_address().unwrap().clone();
                     let data = ScryptoValue::from_slice(component.state()).unwrap();
                     let initial_loaded_object_refs = ComponentObjectRefs {
@@ -303,6 +317,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     let istate = InterpreterState::Component {
                         component_address,
                         component,
+                        mutability: mutability.clone(),
                         initial_loaded_object_refs,
                     };
                     let mut args_with_self = vec![ScryptoValue::from_value(&component_address)];
@@ -320,30 +335,21 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                         function,
                         args,
                         actor: actor.clone(),
-                        module: module.clone(),
-                        memory,
                     },
                     interpreter_state,
                 });
 
                 // Execution
-                let result = module.invoke_export(actor.export_name(), &[], self);
+                let result = instance
+                    .invoke_export(actor.export_name(), self)
+                    .and_then(|rtn| {
+                        ScryptoValue::from_slice(&rtn)
+                            .map_err(RuntimeError::ParseScryptoValueError)
+                    });
 
                 // Return value
                 re_debug!(self, "Invoke result: {:?}", result);
-                let rtn = result
-                    .map_err(|e| {
-                        match e.into_host_error() {
-                            // Pass-through runtime errors
-                            Some(host_error) => *host_error.downcast::<RuntimeError>().unwrap(),
-                            None => RuntimeError::InvokeError,
-                        }
-                    })?
-                    .ok_or(RuntimeError::NoReturnData)?;
-                match rtn {
-                    RuntimeValue::I32(ptr) => self.read_return_value(ptr as u32),
-                    _ => Err(RuntimeError::InvalidReturnType),
-                }
+                result
             }
             SNodeState::ResourceStatic => {
                 ResourceManager::static_main(function.as_str(), args, self)
@@ -404,6 +410,42 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         function: String,
         args: Vec<ScryptoValue>,
     ) -> Result<ScryptoValue, RuntimeError> {
+        if !self.trace {
+            return self.invoke_snode_internal(snode_ref, function, args);
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        let now = std::time::Instant::now();
+        let cost_units_before = self.track.cost_unit_counter().consumed();
+        self.track
+            .begin_execution_trace(snode_ref.clone(), function.clone(), args.clone());
+
+        let result = self.invoke_snode_internal(snode_ref, function, args);
+
+        #[cfg(not(feature = "alloc"))]
+        let execution_time = Some(now.elapsed().as_micros());
+        #[cfg(feature = "alloc")]
+        let execution_time = None;
+        let cost_units = self.track.cost_unit_counter().consumed() - cost_units_before;
+        self.track
+            .end_execution_trace(result.clone(), cost_units, execution_time);
+        result
+    }
+
+    fn invoke_snode_internal(
+        &mut self,
+        snode_ref: SNodeRef,
+        function: String,
+        args: Vec<ScryptoValue>,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        let cost = self.track.fee_table().invoke_snode();
+        self.track.consume_cost_units(cost)?;
+
+        let max_call_depth = self.track.limits().max_call_depth;
+        if self.depth >= max_call_depth {
+            return Err(RuntimeError::MaxCallDepthExceeded(max_call_depth));
+        }
+
         // Authorization and state load
         let (mut snode, method_auths) = match &snode_ref {
             SNodeRef::PackageStatic => Ok((SNodeState::PackageStatic, vec![])),
@@ -438,9 +480,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                         ))
                     }
                     ScryptoActor::Component(component_address) => {
-                        let component = self
+                        let (component, mutability) = self
                             .track
-                            .borrow_global_mut_component(component_address.clone())?;
+                            .borrow_global_component(*component_address, &function)?;
                         let package_address = component.package_address();
                         let blueprint_name = component.blueprint_name().to_string();
                         let export_name = format!("{}_main", blueprint_name);
@@ -464,7 +506,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                                     export_name,
                                     component_address.clone(),
                                 ),
-                                Some(component),
+                                Some((component, mutability)),
                             ),
                             method_auths,
                         ))
@@ -524,7 +566,22 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     (None, vault)
                 } else if !self.snode_refs.vault_ids.contains(vault_id) {
                     return Err(RuntimeError::VaultNotFound(*vault_id));
-                } else if let Some(WasmProcess { interpreter_state: InterpreterState::Component { component_address, .. }, .. }) = &self.wasm_process_state {
+                } else if let Some(WasmProcess { interpreter_state: InterpreterState::Component { component_address, mutability, .. }, .. }) = &self.wasm_process_state {
+                    // Vaults of the component can only be read by an immutable method
+                    if let Mutability::Immutable = mutability {
+                        if !matches!(
+                            function.as_str(),
+                            "is_vault_frozen"
+                                | "get_vault_amount"
+                                | "get_vault_resource_address"
+                                | "get_non_fungible_ids_in_vault"
+                                | "create_vault_proof"
+                                | "create_vault_proof_by_amount"
+                                | "create_vault_proof_by_ids"
+                        ) {
+                            return Err(RuntimeError::ImmutableComponentWrite(*component_address));
+                        }
+                    }
                     let vault = self.track.borrow_vault_mut(component_address, vault_id);
                     (Some(*component_address), vault)
                 } else {
@@ -537,15 +594,47 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     .get_resource_manager(&resource_address)
                     .unwrap()
                     .get_auth(&function, &args);
+                let mut method_auths = vec![method_auth.clone()];
+                method_auths.extend(vault.get_auth(&function));
                 Ok((
                     SNodeState::VaultRef(vault_id.clone(), component, vault),
+                    method_auths,
+                ))
+            }
+            SNodeRef::ComponentVaultRef(resource_address, component_address, vault_id) => {
+                // Vaults of other components can only be recalled from or frozen
+                if !matches!(
+                    function.as_str(),
+                    "recall_from_vault"
+                        | "recall_non_fungibles_from_vault"
+                        | "freeze_vault"
+                        | "unfreeze_vault"
+                ) {
+                    return Err(RuntimeError::IllegalSystemCall);
+                }
+                if !self.track.has_vault(component_address, vault_id) {
+                    return Err(RuntimeError::VaultNotFound(*vault_id));
+                }
+                let vault = self.track.borrow_vault_mut(component_address, vault_id);
+                if vault.resource_address() != *resource_address {
+                    self.track.return_borrowed_vault(component_address, vault_id, vault);
+                    return Err(RuntimeError::VaultResourceMismatch(*vault_id, *resource_address));
+                }
+
+                let method_auth = self
+                    .track
+                    .get_resource_manager(resource_address)
+                    .unwrap()
+                    .get_auth(&function, &args);
+                Ok((
+                    SNodeState::VaultRef(*vault_id, Some(*component_address), vault),
                     vec![method_auth.clone()],
                 ))
             }
         }?;
 
         // Authorization check
-        if !method_auths.is_empty() {
+        if !method_auths.is_empty() && self.track.auth_check_enabled() {
             let mut auth_zones = Vec::new();
             if let Some(self_auth_zone) = &self.auth_zone {
                 auth_zones.push(self_auth_zone);
@@ -592,6 +681,12 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     moving_buckets.extend(self.send_buckets(&arg.bucket_ids)?);
                     moving_proofs.extend(self.send_proofs(&arg.proof_ids, MoveMethod::AsArgument)?);
                 }
+                if self.trace {
+                    self.track.trace_resources_in(
+                        Self::summarize_buckets(&moving_buckets),
+                        Self::summarize_proofs(&moving_proofs),
+                    );
+                }
 
                 // start a new process
                 let process_auth_zone = if matches!(snode, SNodeState::Scrypto(_, _)) {
@@ -616,10 +711,17 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                 // invoke the main function
                 let (result, received_buckets, received_proofs) =
                     process.run(&mut snode, function, args)?;
+                if self.trace {
+                    self.track.trace_resources_out(
+                        Self::summarize_buckets(&received_buckets),
+                        Self::summarize_proofs(&received_proofs),
+                    );
+                }
 
                 // move buckets and proofs to this process.
                 self.buckets.extend(received_buckets);
                 self.proofs.extend(received_proofs);
+                self.check_frame_limits()?;
 
                 // Return borrowed snodes
                 match snode {
@@ -631,9 +733,10 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     }
                     SNodeState::Scrypto(actor, component_state) => {
                         if let Some(component_address) = actor.component_address() {
+                            let (component, _) = component_state.unwrap();
                             self.track.return_borrowed_global_component(
                                 component_address,
-                                component_state.unwrap(),
+                                component,
                             );
                         }
                     }
@@ -666,6 +769,32 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(result)
     }
 
+    fn summarize_buckets(
+        buckets: &HashMap<BucketId, Bucket>,
+    ) -> Vec<(BucketId, ResourceAddress, AmountOrIds)> {
+        let mut summary: Vec<(BucketId, ResourceAddress, AmountOrIds)> = buckets
+            .iter()
+            .map(|(bucket_id, bucket)| {
+                (*bucket_id, bucket.resource_address(), bucket.total_amount_or_ids())
+            })
+            .collect();
+        summary.sort_by_key(|(bucket_id, _, _)| *bucket_id);
+        summary
+    }
+
+    fn summarize_proofs(
+        proofs: &HashMap<ProofId, Proof>,
+    ) -> Vec<(ProofId, ResourceAddress, AmountOrIds)> {
+        let mut summary: Vec<(ProofId, ResourceAddress, AmountOrIds)> = proofs
+            .iter()
+            .map(|(proof_id, proof)| {
+                (*proof_id, proof.resource_address(), proof.total_amount_or_ids())
+            })
+            .collect();
+        summary.sort_by_key(|(proof_id, _, _)| *proof_id);
+        summary
+    }
+
     /// Calls the ABI generator of a blueprint.
     // TODO: Remove
     pub fn call_abi(
@@ -761,6 +890,43 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(())
     }
 
+    /// Checks the size of a component state or lazy map entry before it is written
+    fn check_substate_size(&self, data: &[u8]) -> Result<(), RuntimeError> {
+        let max_substate_size = self.track.limits().max_substate_size;
+        if data.len() > max_substate_size {
+            return Err(RuntimeError::MaxSubstateSizeExceeded(max_substate_size));
+        }
+        Ok(())
+    }
+
+    /// Checks the number of buckets and proofs held by this call frame.
+    ///
+    /// Buckets and proofs enter a frame by being returned from a call, so this is checked
+    /// on return rather than on creation, where native errors would mask the failure.
+    fn check_frame_limits(&self) -> Result<(), RuntimeError> {
+        let limits = self.track.limits();
+        if self.buckets.len() > limits.max_buckets_per_frame {
+            return Err(RuntimeError::MaxBucketsPerFrameExceeded(
+                limits.max_buckets_per_frame,
+            ));
+        }
+        if self.proofs.len() > limits.max_proofs_per_frame {
+            return Err(RuntimeError::MaxProofsPerFrameExceeded(
+                limits.max_proofs_per_frame,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that a removed lazy map entry holds no vaults or lazy maps, which would be lost
+    fn check_entry_removable(value: &Option<Vec<u8>>) -> Result<(), RuntimeError> {
+        if let Some(value) = value {
+            let entry_object_refs = Self::process_entry_data(value)?;
+            ComponentObjectRefs::new().remove(&entry_object_refs)?;
+        }
+        Ok(())
+    }
+
     /// Process and parse entry data from any component object (components and maps)
     fn process_entry_data(data: &[u8]) -> Result<ComponentObjectRefs, RuntimeError> {
         let validated =
@@ -840,76 +1006,35 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(proofs)
     }
 
-    /// Send a byte array to wasm instance.
-    fn send_bytes(&mut self, bytes: &[u8]) -> Result<i32, RuntimeError> {
-        let wasm_process = self.wasm_process_state.as_ref().unwrap();
-        let result = wasm_process.vm.module.invoke_export(
-            "scrypto_alloc",
-            &[RuntimeValue::I32((bytes.len()) as i32)],
-            &mut NopExternals,
-        );
-
-        if let Ok(Some(RuntimeValue::I32(ptr))) = result {
-            if wasm_process.vm.memory.set((ptr + 4) as u32, bytes).is_ok() {
-                return Ok(ptr);
-            }
-        }
-
-        Err(RuntimeError::MemoryAllocError)
-    }
-
-    fn read_return_value(&mut self, ptr: u32) -> Result<ScryptoValue, RuntimeError> {
-        let wasm_process = self.wasm_process_state.as_ref().unwrap();
-        // read length
-        let len: u32 = wasm_process
-            .vm
-            .memory
-            .get_value(ptr)
-            .map_err(|_| RuntimeError::MemoryAccessError)?;
-
-        let start = ptr.checked_add(4).ok_or(RuntimeError::MemoryAccessError)?;
-        let end = start
-            .checked_add(len)
-            .ok_or(RuntimeError::MemoryAccessError)?;
-        let range = start as usize..end as usize;
-        let direct = wasm_process.vm.memory.direct_access();
-        let buffer = direct.as_ref();
-
-        if end > buffer.len().try_into().unwrap() {
-            return Err(RuntimeError::MemoryAccessError);
-        }
-
-        ScryptoValue::from_slice(&buffer[range]).map_err(RuntimeError::ParseScryptoValueError)
-    }
-
     /// Handles a system call.
     fn handle<I: Decode + fmt::Debug, O: Encode + fmt::Debug>(
         &mut self,
-        args: RuntimeArgs,
+        op: u32,
+        input_bytes: &[u8],
         handler: fn(&mut Self, input: I) -> Result<O, RuntimeError>,
-    ) -> Result<Option<RuntimeValue>, Trap> {
-        let wasm_process = self.wasm_process_state.as_mut().unwrap();
-        let op: u32 = args.nth_checked(0)?;
-        let input_ptr: u32 = args.nth_checked(1)?;
-        let input_len: u32 = args.nth_checked(2)?;
-        // SECURITY: bill before allocating memory
-        let mut input_bytes = vec![0u8; input_len as usize];
-        wasm_process
-            .vm
-            .memory
-            .get_into(input_ptr, &mut input_bytes)
-            .map_err(|_| Trap::from(RuntimeError::MemoryAccessError))?;
-        let input: I = scrypto_decode(&input_bytes)
-            .map_err(|e| Trap::from(RuntimeError::InvalidRequestData(e)))?;
-        if input_len <= 1024 {
+    ) -> Result<Vec<u8>, RuntimeError> {
+        let fee_table = self.track.fee_table();
+        let cost = fee_table.engine_call().saturating_add(
+            (input_bytes.len() as u32).saturating_mul(fee_table.engine_call_per_byte()),
+        );
+        self.track.consume_cost_units(cost)?;
+        let input: I = scrypto_decode(input_bytes).map_err(RuntimeError::InvalidRequestData)?;
+        if input_bytes.len() <= 1024 {
             re_trace!(self, "{:?}", input);
         } else {
-            re_trace!(self, "Large request: op = {:02x}, len = {}", op, input_len);
+            re_trace!(
+                self,
+                "Large request: op = {:02x}, len = {}",
+                op,
+                input_bytes.len()
+            );
         }
 
-        let output: O = handler(self, input).map_err(Trap::from)?;
+        let output: O = handler(self, input)?;
         let output_bytes = scrypto_encode(&output);
-        let output_ptr = self.send_bytes(&output_bytes).map_err(Trap::from)?;
+        let cost = (output_bytes.len() as u32)
+            .saturating_mul(self.track.fee_table().engine_call_per_byte());
+        self.track.consume_cost_units(cost)?;
         if output_bytes.len() <= 1024 {
             re_trace!(self, "{:?}", output);
         } else {
@@ -921,7 +1046,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             );
         }
 
-        Ok(Some(RuntimeValue::I32(output_ptr)))
+        Ok(output_bytes)
     }
 
     //============================
@@ -932,6 +1057,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         &mut self,
         input: CreateComponentInput,
     ) -> Result<CreateComponentOutput, RuntimeError> {
+        self.check_substate_size(&input.state)?;
         let data = Self::process_entry_data(&input.state)?;
         let new_objects = self.owned_snodes.take(data)?;
 
@@ -991,6 +1117,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         &mut self,
         input: PutComponentStateInput,
     ) -> Result<PutComponentStateOutput, RuntimeError> {
+        self.check_substate_size(&input.state)?;
         let wasm_process = self
             .wasm_process_state
             .as_mut()
@@ -999,13 +1126,18 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             InterpreterState::Component {
                 ref mut component,
                 component_address,
+                mutability: Mutability::Mutable,
                 initial_loaded_object_refs,
-                ..
             } => {
                 let mut new_set = Self::process_entry_data(&input.state)?;
                 new_set.remove(&initial_loaded_object_refs)?;
                 Ok((component, new_set, component_address))
             }
+            InterpreterState::Component {
+                component_address,
+                mutability: Mutability::Immutable,
+                ..
+            } => Err(RuntimeError::ImmutableComponentWrite(*component_address)),
             _ => Err(RuntimeError::IllegalSystemCall),
         }?;
 
@@ -1067,6 +1199,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         &mut self,
         input: PutLazyMapEntryInput,
     ) -> Result<PutLazyMapEntryOutput, RuntimeError> {
+        self.check_substate_size(&input.value)?;
         let wasm_process = self
             .wasm_process_state
             .as_mut()
@@ -1078,6 +1211,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             None => match &wasm_process.interpreter_state {
                 InterpreterState::Component {
                     component_address,
+                    mutability,
                     ..
                 } => {
                     if !self.snode_refs
@@ -1086,6 +1220,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     {
                         return Err(RuntimeError::LazyMapNotFound(input.lazy_map_id));
                     }
+                    if let Mutability::Immutable = mutability {
+                        return Err(RuntimeError::ImmutableComponentWrite(*component_address));
+                    }
                     let old_value = self.track.get_lazy_map_entry(
                         *component_address,
                         &input.lazy_map_id,
@@ -1146,21 +1283,158 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(PutLazyMapEntryOutput {})
     }
 
+    fn handle_remove_lazy_map_entry(
+        &mut self,
+        input: RemoveLazyMapEntryInput,
+    ) -> Result<RemoveLazyMapEntryOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_mut()
+            .ok_or(RuntimeError::IllegalSystemCall)?;
+        if let Some((_, value)) = self
+            .owned_snodes
+            .get_lazy_map_entry(&input.lazy_map_id, &input.key) {
+            Self::check_entry_removable(&value)?;
+            self.owned_snodes.remove_lazy_map_entry(&input.lazy_map_id, &input.key);
+            return Ok(RemoveLazyMapEntryOutput { value });
+        }
+
+        match &wasm_process.interpreter_state {
+            InterpreterState::Component {
+                component_address,
+                mutability,
+                ..
+            } => {
+                if !self.snode_refs
+                        .lazy_map_ids
+                        .contains(&input.lazy_map_id)
+                {
+                    return Err(RuntimeError::LazyMapNotFound(input.lazy_map_id));
+                }
+                if let Mutability::Immutable = mutability {
+                    return Err(RuntimeError::ImmutableComponentWrite(*component_address));
+                }
+                let value = self.track.get_lazy_map_entry(
+                    *component_address,
+                    &input.lazy_map_id,
+                    &input.key,
+                );
+                Self::check_entry_removable(&value)?;
+                self.track.remove_lazy_map_entry(
+                    *component_address,
+                    &input.lazy_map_id,
+                    &input.key,
+                );
+                Ok(RemoveLazyMapEntryOutput { value })
+            }
+            _ => Err(RuntimeError::LazyMapNotFound(input.lazy_map_id)),
+        }
+    }
+
+    fn handle_contains_lazy_map_entry(
+        &mut self,
+        input: ContainsLazyMapEntryInput,
+    ) -> Result<ContainsLazyMapEntryOutput, RuntimeError> {
+        if let Some((_, value)) = self
+            .owned_snodes
+            .get_lazy_map_entry(&input.lazy_map_id, &input.key) {
+            return Ok(ContainsLazyMapEntryOutput { contains: value.is_some() });
+        }
+
+        if !self.snode_refs.lazy_map_ids.contains(&input.lazy_map_id) {
+            return Err(RuntimeError::LazyMapNotFound(input.lazy_map_id));
+        }
+
+        if let Some(WasmProcess { interpreter_state: InterpreterState::Component { component_address, .. }, .. }) = &self.wasm_process_state {
+            let value = self.track.get_lazy_map_entry(
+                *component_address,
+                &input.lazy_map_id,
+                &input.key,
+            );
+            return Ok(ContainsLazyMapEntryOutput { contains: value.is_some() });
+        }
+
+        Err(RuntimeError::LazyMapNotFound(input.lazy_map_id))
+    }
+
+    fn handle_list_lazy_map_entries(
+        &mut self,
+        input: ListLazyMapEntriesInput,
+    ) -> Result<ListLazyMapEntriesOutput, RuntimeError> {
+        if input.limit > MAX_LAZY_MAP_PAGE_SIZE {
+            return Err(RuntimeError::MaxLazyMapPageSizeExceeded(MAX_LAZY_MAP_PAGE_SIZE));
+        }
+        let limit = input.limit as usize;
+        if let Some(entries) = self
+            .owned_snodes
+            .list_lazy_map_entries(&input.lazy_map_id, input.after.as_deref(), limit) {
+            return Ok(ListLazyMapEntriesOutput { entries });
+        }
+
+        if !self.snode_refs.lazy_map_ids.contains(&input.lazy_map_id) {
+            return Err(RuntimeError::LazyMapNotFound(input.lazy_map_id));
+        }
+
+        if let Some(WasmProcess { interpreter_state: InterpreterState::Component { component_address, .. }, .. }) = &self.wasm_process_state {
+            let entries = self.track.list_lazy_map_entries(
+                *component_address,
+                &input.lazy_map_id,
+                input.after.as_deref(),
+                limit,
+            );
+            for (_, value) in &entries {
+                let map_entry_objects = Self::process_entry_data(value)?;
+                self.snode_refs.extend(map_entry_objects);
+            }
+
+            return Ok(ListLazyMapEntriesOutput { entries });
+        }
+
+        Err(RuntimeError::LazyMapNotFound(input.lazy_map_id))
+    }
+
     fn handle_create_vault(
         &mut self,
         input: CreateEmptyVaultInput,
+    ) -> Result<CreateEmptyVaultOutput, RuntimeError> {
+        self.create_vault(input.resource_address, None)
+    }
+
+    fn handle_create_vault_with_access_rules(
+        &mut self,
+        input: CreateEmptyVaultWithAccessRulesInput,
+    ) -> Result<CreateEmptyVaultOutput, RuntimeError> {
+        // There is no state to read a schema path from
+        if let Some((method_name, _)) = input
+            .access_rules
+            .iter()
+            .find(|(_, method_auth)| has_schema_path(method_auth))
+        {
+            return Err(RuntimeError::SchemaPathInVaultAccessRules(method_name.clone()));
+        }
+        self.create_vault(input.resource_address, Some(input.access_rules))
+    }
+
+    fn create_vault(
+        &mut self,
+        resource_address: ResourceAddress,
+        access_rules: Option<AccessRules>,
     ) -> Result<CreateEmptyVaultOutput, RuntimeError> {
         let definition = self
             .track
-            .get_resource_manager(&input.resource_address)
+            .get_resource_manager(&resource_address)
             .ok_or(RuntimeError::ResourceManagerNotFound(
-                input.resource_address,
+                resource_address,
             ))?;
 
-        let new_vault = Vault::new(ResourceContainer::new_empty(
-            input.resource_address,
+        let container = ResourceContainer::new_empty(
+            resource_address,
             definition.resource_type(),
-        ));
+        );
+        let new_vault = match access_rules {
+            Some(access_rules) => Vault::with_access_rules(container, access_rules),
+            None => Vault::new(container),
+        };
         let vault_id = self.track.new_vault_id();
         self
             .owned_snodes
@@ -1191,6 +1465,40 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(EmitLogOutput {})
     }
 
+    fn handle_emit_event(
+        &mut self,
+        input: EmitEventInput,
+    ) -> Result<EmitEventOutput, RuntimeError> {
+        let actor = self
+            .wasm_process_state
+            .as_ref()
+            .ok_or(RuntimeError::InterpreterNotStarted)?
+            .vm
+            .actor
+            .clone();
+        let package = self
+            .track
+            .get_package(actor.package_address())
+            .ok_or(RuntimeError::PackageNotFound(*actor.package_address()))?;
+        let schema = match package.event_schema(actor.blueprint_name(), &input.name) {
+            Some(schema) => schema,
+            None => return Err(RuntimeError::EventNotDeclared(input.name)),
+        };
+        let data =
+            ScryptoValue::from_slice(&input.data).map_err(RuntimeError::ParseScryptoValueError)?;
+        if !matches_schema(&data.dom, schema) {
+            return Err(RuntimeError::EventSchemaMismatch(input.name));
+        }
+
+        self.track.add_event(Event {
+            actor,
+            name: input.name,
+            data: input.data,
+        });
+
+        Ok(EmitEventOutput {})
+    }
+
     fn handle_get_call_data(
         &mut self,
         _input: GetCallDataInput,
@@ -1330,42 +1638,59 @@ impl<'r, 'l, L: SubstateStore> SystemApi for Process<'r, 'l, L> {
     fn create_package(&mut self, package: Package) -> PackageAddress {
         self.track.create_package(package)
     }
-}
 
-impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
-    fn invoke_index(
+    fn lock_fee(&mut self, vault_id: VaultId, fee: ResourceContainer) -> Result<(), RuntimeError> {
+        self.track.lock_fee(vault_id, fee)
+    }
+
+    fn record_resource_movement(
         &mut self,
-        index: usize,
-        args: RuntimeArgs,
-    ) -> Result<Option<RuntimeValue>, Trap> {
-        match index {
-            ENGINE_FUNCTION_INDEX => {
-                let operation: u32 = args.nth_checked(0)?;
-                match operation {
-                    CREATE_COMPONENT => self.handle(args, Self::handle_create_component),
-                    GET_COMPONENT_INFO => self.handle(args, Self::handle_get_component_info),
-                    GET_COMPONENT_STATE => self.handle(args, Self::handle_get_component_state),
-                    PUT_COMPONENT_STATE => self.handle(args, Self::handle_put_component_state),
-
-                    CREATE_LAZY_MAP => self.handle(args, Self::handle_create_lazy_map),
-                    GET_LAZY_MAP_ENTRY => self.handle(args, Self::handle_get_lazy_map_entry),
-                    PUT_LAZY_MAP_ENTRY => self.handle(args, Self::handle_put_lazy_map_entry),
-
-                    CREATE_EMPTY_VAULT => self.handle(args, Self::handle_create_vault),
-
-                    INVOKE_SNODE => self.handle(args, Self::handle_invoke_snode),
-
-                    EMIT_LOG => self.handle(args, Self::handle_emit_log),
-                    GET_CALL_DATA => self.handle(args, Self::handle_get_call_data),
-                    GET_TRANSACTION_HASH => self.handle(args, Self::handle_get_transaction_hash),
-                    GET_CURRENT_EPOCH => self.handle(args, Self::handle_get_current_epoch),
-                    GENERATE_UUID => self.handle(args, Self::handle_generate_uuid),
-                    GET_ACTOR => self.handle(args, Self::handle_get_actor),
-
-                    _ => Err(RuntimeError::InvalidRequestCode(operation).into()),
-                }
+        movement: ResourceMovement,
+        resource_address: ResourceAddress,
+        resource: AmountOrIds,
+    ) {
+        self.track
+            .add_resource_movement(movement, resource_address, resource);
+    }
+}
+
+impl<'r, 'l, L: SubstateStore> WasmRuntime for Process<'r, 'l, L> {
+    fn main(&mut self, op: u32, input: &[u8]) -> Result<Vec<u8>, RuntimeError> {
+        match op {
+            CREATE_COMPONENT => self.handle(op, input, Self::handle_create_component),
+            GET_COMPONENT_INFO => self.handle(op, input, Self::handle_get_component_info),
+            GET_COMPONENT_STATE => self.handle(op, input, Self::handle_get_component_state),
+            PUT_COMPONENT_STATE => self.handle(op, input, Self::handle_put_component_state),
+
+            CREATE_LAZY_MAP => self.handle(op, input, Self::handle_create_lazy_map),
+            GET_LAZY_MAP_ENTRY => self.handle(op, input, Self::handle_get_lazy_map_entry),
+            PUT_LAZY_MAP_ENTRY => self.handle(op, input, Self::handle_put_lazy_map_entry),
+            REMOVE_LAZY_MAP_ENTRY => self.handle(op, input, Self::handle_remove_lazy_map_entry),
+            CONTAINS_LAZY_MAP_ENTRY => {
+                self.handle(op, input, Self::handle_contains_lazy_map_entry)
+            }
+            LIST_LAZY_MAP_ENTRIES => self.handle(op, input, Self::handle_list_lazy_map_entries),
+
+            CREATE_EMPTY_VAULT => self.handle(op, input, Self::handle_create_vault),
+            CREATE_EMPTY_VAULT_WITH_ACCESS_RULES => {
+                self.handle(op, input, Self::handle_create_vault_with_access_rules)
             }
-            _ => Err(RuntimeError::HostFunctionNotFound(index).into()),
+
+            INVOKE_SNODE => self.handle(op, input, Self::handle_invoke_snode),
+
+            EMIT_LOG => self.handle(op, input, Self::handle_emit_log),
+            EMIT_EVENT => self.handle(op, input, Self::handle_emit_event),
+            GET_CALL_DATA => self.handle(op, input, Self::handle_get_call_data),
+            GET_TRANSACTION_HASH => self.handle(op, input, Self::handle_get_transaction_hash),
+            GET_CURRENT_EPOCH => self.handle(op, input, Self::handle_get_current_epoch),
+            GENERATE_UUID => self.handle(op, input, Self::handle_generate_uuid),
+            GET_ACTOR => self.handle(op, input, Self::handle_get_actor),
+
+            _ => Err(RuntimeError::InvalidRequestCode(op)),
         }
     }
+
+    fn consume_cost_units(&mut self, n: u32) -> Result<(), RuntimeError> {
+        self.track.consume_cost_units(n)
+    }
 }
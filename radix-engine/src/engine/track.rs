@@ -1,18 +1,99 @@
 use indexmap::IndexMap;
+use sbor::*;
+use scrypto::abi::Mutability;
+use scrypto::buffer::*;
 use scrypto::constants::*;
+use scrypto::core::SNodeRef;
+use scrypto::crypto::PublicKey;
 use scrypto::engine::types::*;
+use scrypto::rust::boxed::Box;
+use scrypto::rust::cmp;
 use scrypto::rust::collections::*;
 use scrypto::rust::string::String;
 use scrypto::rust::vec::Vec;
+use scrypto::values::ScryptoValue;
 
 use crate::engine::*;
 use crate::errors::RuntimeError;
+use crate::fee::*;
 use crate::ledger::*;
 use crate::model::*;
 
 pub struct CommitReceipt {
     pub down_substates: HashSet<(Hash, u32)>,
     pub up_substates: Vec<(Hash, u32)>,
+    pub substate_changes: Vec<SubstateChange>,
+    pub balance_changes: BTreeMap<ComponentAddress, BTreeMap<ResourceAddress, BalanceChange>>,
+}
+
+/// The logical key of a substate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SubstateKey {
+    Package(PackageAddress),
+    Component(ComponentAddress),
+    ResourceManager(ResourceAddress),
+    Vault(ComponentAddress, VaultId),
+    LazyMapEntry(ComponentAddress, LazyMapId, Vec<u8>),
+    NonFungible(NonFungibleAddress),
+}
+
+impl SubstateKey {
+    /// Returns the ID of the substate in the substate store, which is its encoded address,
+    /// followed by its key within the parent for child substates.
+    pub fn to_id(&self) -> Vec<u8> {
+        let (mut id, child_key) = match self {
+            SubstateKey::Package(package_address) => (scrypto_encode(package_address), Vec::new()),
+            SubstateKey::Component(component_address) => {
+                (scrypto_encode(component_address), Vec::new())
+            }
+            SubstateKey::ResourceManager(resource_address) => {
+                (scrypto_encode(resource_address), Vec::new())
+            }
+            SubstateKey::Vault(component_address, vault_id) => {
+                (scrypto_encode(component_address), scrypto_encode(vault_id))
+            }
+            SubstateKey::LazyMapEntry(component_address, lazy_map_id, key) => {
+                let mut child_key = scrypto_encode(lazy_map_id);
+                child_key.extend(key);
+                (scrypto_encode(component_address), child_key)
+            }
+            SubstateKey::NonFungible(non_fungible_address) => (
+                scrypto_encode(&non_fungible_address.resource_address()),
+                scrypto_encode(&non_fungible_address.non_fungible_id()),
+            ),
+        };
+        id.extend(child_key);
+        id
+    }
+}
+
+/// A substate written by a transaction, with its SBOR-encoded values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubstateChange {
+    pub key: SubstateKey,
+    /// The value before the transaction, or `None` if the substate is new.
+    pub old_value: Option<Vec<u8>>,
+    /// The value after the transaction, or `None` if the substate has been removed.
+    pub new_value: Option<Vec<u8>>,
+}
+
+/// The net change of a resource balance held by a component, across all its vaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BalanceChange {
+    Fungible(Decimal),
+    NonFungible {
+        added: BTreeSet<NonFungibleId>,
+        removed: BTreeSet<NonFungibleId>,
+    },
+}
+
+impl BalanceChange {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            BalanceChange::Fungible(delta) => delta.is_zero(),
+            BalanceChange::NonFungible { added, removed } => added.is_empty() && removed.is_empty(),
+        }
+    }
 }
 
 impl CommitReceipt {
@@ -20,9 +101,76 @@ impl CommitReceipt {
         CommitReceipt {
             down_substates: HashSet::new(),
             up_substates: Vec::new(),
+            substate_changes: Vec::new(),
+            balance_changes: BTreeMap::new(),
+        }
+    }
+
+    /// Records the difference between the old and new content of a vault.
+    fn vault_update(
+        &mut self,
+        component_address: ComponentAddress,
+        resource_address: ResourceAddress,
+        old: Option<AmountOrIds>,
+        new: AmountOrIds,
+    ) {
+        let change = self
+            .balance_changes
+            .entry(component_address)
+            .or_default()
+            .entry(resource_address)
+            .or_insert(match new {
+                AmountOrIds::Amount(_) => BalanceChange::Fungible(Decimal::zero()),
+                AmountOrIds::Ids(_) => BalanceChange::NonFungible {
+                    added: BTreeSet::new(),
+                    removed: BTreeSet::new(),
+                },
+            });
+
+        match (change, new) {
+            (BalanceChange::Fungible(delta), AmountOrIds::Amount(new_amount)) => {
+                let old_amount = match old {
+                    Some(AmountOrIds::Amount(amount)) => amount,
+                    _ => Decimal::zero(),
+                };
+                *delta = *delta + new_amount - old_amount;
+            }
+            (BalanceChange::NonFungible { added, removed }, AmountOrIds::Ids(new_ids)) => {
+                let old_ids = match old {
+                    Some(AmountOrIds::Ids(ids)) => ids,
+                    _ => BTreeSet::new(),
+                };
+                for id in new_ids.difference(&old_ids) {
+                    if !removed.remove(id) {
+                        added.insert(id.clone());
+                    }
+                }
+                for id in old_ids.difference(&new_ids) {
+                    if !added.remove(id) {
+                        removed.insert(id.clone());
+                    }
+                }
+            }
+            _ => panic!("Resource type should not change"),
         }
     }
 
+    /// Returns the new value of each substate written, by substate ID.
+    pub fn substate_updates(&self) -> Vec<(Vec<u8>, Option<Vec<u8>>)> {
+        self.substate_changes
+            .iter()
+            .map(|change| (change.key.to_id(), change.new_value.clone()))
+            .collect()
+    }
+
+    fn change(&mut self, key: SubstateKey, old_value: Option<Vec<u8>>, new_value: Option<Vec<u8>>) {
+        self.substate_changes.push(SubstateChange {
+            key,
+            old_value,
+            new_value,
+        });
+    }
+
     fn down(&mut self, id: (Hash, u32)) {
         self.down_substates.insert(id);
     }
@@ -37,6 +185,9 @@ struct SubstateUpdate<T> {
     value: T,
 }
 
+/// A lazy map entry update, where `None` is the tombstone of a removed entry.
+type LazyMapEntryUpdate = SubstateUpdate<Option<Vec<u8>>>;
+
 /// An abstraction of transaction execution state.
 ///
 /// It acts as the facade of ledger state and keeps track of all temporary state updates,
@@ -44,61 +195,101 @@ struct SubstateUpdate<T> {
 ///
 /// Typically, a track is shared by all the processes created within a transaction.
 ///
+/// A track also meters the cost units consumed by the transaction. Substate reads are
+/// billed as they happen, but since most accessors are infallible, the limit is only
+/// enforced at the next call to `consume_cost_units()`.
+///
 pub struct Track<'s, S: SubstateStore> {
     substate_store: &'s mut S,
+    wasm_engine: &'s mut dyn WasmEngine,
     transaction_hash: Hash,
-    transaction_signers: Vec<EcdsaPublicKey>,
+    transaction_signers: Vec<PublicKey>,
     id_allocator: IdAllocator,
     logs: Vec<(Level, String)>,
+    events: Vec<Event>,
+    execution_trace: Vec<ExecutionTrace>,
+    /// Invocations in progress, innermost last
+    execution_trace_stack: Vec<ExecutionTrace>,
+    resource_movements: Vec<(ResourceMovement, ResourceAddress, AmountOrIds)>,
+    /// Owners of the vaults which paid fees, as those may not have been loaded
+    fee_vault_owners: HashMap<VaultId, ComponentAddress>,
+    auth_check_enabled: bool,
+    limits: ExecutionLimits,
+
+    fee_table: FeeTable,
+    cost_unit_counter: CostUnitCounter,
+    fee_locks: Vec<(ComponentAddress, VaultId, ResourceContainer)>,
 
     packages: IndexMap<PackageAddress, SubstateUpdate<Package>>,
 
     components: IndexMap<ComponentAddress, SubstateUpdate<Component>>,
     borrowed_components: HashMap<ComponentAddress, Option<(Hash, u32)>>,
+    /// Number of outstanding immutable borrows of each component
+    shared_borrowed_components: HashMap<ComponentAddress, usize>,
 
     resource_managers: IndexMap<ResourceAddress, SubstateUpdate<ResourceManager>>,
     borrowed_resource_managers: HashMap<ResourceAddress, Option<(Hash, u32)>>,
 
-    vaults: HashMap<(ComponentAddress, VaultId), SubstateUpdate<Vault>>,
+    vaults: IndexMap<(ComponentAddress, VaultId), SubstateUpdate<Vault>>,
     borrowed_vaults: HashMap<(ComponentAddress, VaultId), Option<(Hash, u32)>>,
 
-    non_fungibles: HashMap<NonFungibleAddress, SubstateUpdate<Option<NonFungible>>>,
+    non_fungibles: IndexMap<NonFungibleAddress, SubstateUpdate<Option<NonFungible>>>,
 
-    lazy_map_entries: HashMap<(ComponentAddress, LazyMapId, Vec<u8>), SubstateUpdate<Vec<u8>>>,
+    lazy_map_entries: IndexMap<(ComponentAddress, LazyMapId, Vec<u8>), LazyMapEntryUpdate>,
 }
 
 impl<'s, S: SubstateStore> Track<'s, S> {
     pub fn new(
         substate_store: &'s mut S,
         transaction_hash: Hash,
-        transaction_signers: Vec<EcdsaPublicKey>,
+        transaction_signers: Vec<PublicKey>,
+        cost_unit_limit: u32,
+        wasm_engine: &'s mut dyn WasmEngine,
     ) -> Self {
         Self {
             substate_store,
+            wasm_engine,
             transaction_hash,
             transaction_signers,
             id_allocator: IdAllocator::new(IdSpace::Application),
             logs: Vec::new(),
+            events: Vec::new(),
+            execution_trace: Vec::new(),
+            execution_trace_stack: Vec::new(),
+            resource_movements: Vec::new(),
+            fee_vault_owners: HashMap::new(),
+            auth_check_enabled: true,
+            limits: ExecutionLimits::default(),
+            fee_table: FeeTable::new(),
+            cost_unit_counter: CostUnitCounter::new(cost_unit_limit),
+            fee_locks: Vec::new(),
             packages: IndexMap::new(),
             components: IndexMap::new(),
             borrowed_components: HashMap::new(),
+            shared_borrowed_components: HashMap::new(),
             resource_managers: IndexMap::new(),
             borrowed_resource_managers: HashMap::new(),
-            lazy_map_entries: HashMap::new(),
-            vaults: HashMap::new(),
+            lazy_map_entries: IndexMap::new(),
+            vaults: IndexMap::new(),
             borrowed_vaults: HashMap::new(),
-            non_fungibles: HashMap::new(),
+            non_fungibles: IndexMap::new(),
         }
     }
 
     /// Start a process.
     pub fn start_process<'r>(&'r mut self, verbose: bool) -> Process<'r, 's, S> {
-        let signers: BTreeSet<NonFungibleId> = self
-            .transaction_signers
-            .clone()
-            .into_iter()
-            .map(|public_key| NonFungibleId::from_bytes(public_key.to_vec()))
-            .collect();
+        let mut ecdsa_signers = BTreeSet::new();
+        let mut ed25519_signers = BTreeSet::new();
+        for public_key in &self.transaction_signers {
+            match public_key {
+                PublicKey::Ecdsa(public_key) => {
+                    ecdsa_signers.insert(NonFungibleId::from_bytes(public_key.to_vec()));
+                }
+                PublicKey::Ed25519(public_key) => {
+                    ed25519_signers.insert(NonFungibleId::from_bytes(public_key.to_vec()));
+                }
+            }
+        }
 
         // With the latest change, proof amount can't be zero, thus a virtual proof is created
         // only if there are signers.
@@ -108,13 +299,25 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         //
         // TODO: possible to update static check to reject them early?
         let mut initial_auth_zone_proofs = Vec::new();
-        if !signers.is_empty() {
+        if !ecdsa_signers.is_empty() {
             // Proofs can't be zero amount
-            let mut ecdsa_bucket =
-                Bucket::new(ResourceContainer::new_non_fungible(ECDSA_TOKEN, signers));
+            let mut ecdsa_bucket = Bucket::new(ResourceContainer::new_non_fungible(
+                ECDSA_TOKEN,
+                ecdsa_signers,
+            ));
             let ecdsa_proof = ecdsa_bucket.create_proof(ECDSA_TOKEN_BUCKET_ID).unwrap();
             initial_auth_zone_proofs.push(ecdsa_proof);
         }
+        if !ed25519_signers.is_empty() {
+            let mut ed25519_bucket = Bucket::new(ResourceContainer::new_non_fungible(
+                ED25519_TOKEN,
+                ed25519_signers,
+            ));
+            let ed25519_proof = ed25519_bucket
+                .create_proof(ED25519_TOKEN_BUCKET_ID)
+                .unwrap();
+            initial_auth_zone_proofs.push(ed25519_proof);
+        }
 
         Process::new(
             0,
@@ -142,6 +345,56 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &self.logs
     }
 
+    /// Returns the events emitted so far.
+    pub fn events(&self) -> &Vec<Event> {
+        &self.events
+    }
+
+    /// Returns the resource events of the movements recorded so far.
+    ///
+    /// Vault owners are resolved at this point, as new vaults are only stored in a component
+    /// after receiving resource. Movements of vaults without an owner are still reported, with
+    /// no component address.
+    pub fn resource_events(&self) -> Vec<ResourceEvent> {
+        let owners: HashMap<VaultId, ComponentAddress> = self
+            .vaults
+            .keys()
+            .chain(self.borrowed_vaults.keys())
+            .map(|(component_address, vault_id)| (*vault_id, *component_address))
+            .chain(self.fee_vault_owners.clone())
+            .collect();
+
+        self.resource_movements
+            .iter()
+            .map(|(movement, resource_address, resource)| {
+                let resource_address = *resource_address;
+                let resource = resource.clone();
+                match movement {
+                    ResourceMovement::VaultDeposit(vault_id) => ResourceEvent::VaultDeposit {
+                        vault_id: *vault_id,
+                        component_address: owners.get(vault_id).copied(),
+                        resource_address,
+                        resource,
+                    },
+                    ResourceMovement::VaultWithdrawal(vault_id) => ResourceEvent::VaultWithdrawal {
+                        vault_id: *vault_id,
+                        component_address: owners.get(vault_id).copied(),
+                        resource_address,
+                        resource,
+                    },
+                    ResourceMovement::Mint => ResourceEvent::Mint {
+                        resource_address,
+                        resource,
+                    },
+                    ResourceMovement::Burn => ResourceEvent::Burn {
+                        resource_address,
+                        resource,
+                    },
+                }
+            })
+            .collect()
+    }
+
     /// Returns new packages created so far.
     pub fn new_package_addresses(&self) -> Vec<PackageAddress> {
         let mut package_addresses = Vec::new();
@@ -180,14 +433,317 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         self.logs.push((level, message));
     }
 
+    /// Adds an event.
+    pub fn add_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Returns the call tree of the transaction, recorded when tracing is on.
+    pub fn execution_trace(&self) -> &Vec<ExecutionTrace> {
+        &self.execution_trace
+    }
+
+    /// Starts recording an invocation, nested under the one in progress.
+    pub fn begin_execution_trace(
+        &mut self,
+        snode_ref: SNodeRef,
+        function: String,
+        args: Vec<ScryptoValue>,
+    ) {
+        self.execution_trace_stack.push(ExecutionTrace {
+            snode_ref,
+            function,
+            args,
+            buckets_in: Vec::new(),
+            proofs_in: Vec::new(),
+            buckets_out: Vec::new(),
+            proofs_out: Vec::new(),
+            output: Ok(ScryptoValue::from_value(&())),
+            cost_units: 0,
+            execution_time: None,
+            children: Vec::new(),
+        });
+    }
+
+    /// Records the resources passed to the invocation in progress.
+    pub fn trace_resources_in(
+        &mut self,
+        buckets: Vec<(BucketId, ResourceAddress, AmountOrIds)>,
+        proofs: Vec<(ProofId, ResourceAddress, AmountOrIds)>,
+    ) {
+        if let Some(trace) = self.execution_trace_stack.last_mut() {
+            trace.buckets_in = buckets;
+            trace.proofs_in = proofs;
+        }
+    }
+
+    /// Records the resources returned by the invocation in progress.
+    pub fn trace_resources_out(
+        &mut self,
+        buckets: Vec<(BucketId, ResourceAddress, AmountOrIds)>,
+        proofs: Vec<(ProofId, ResourceAddress, AmountOrIds)>,
+    ) {
+        if let Some(trace) = self.execution_trace_stack.last_mut() {
+            trace.buckets_out = buckets;
+            trace.proofs_out = proofs;
+        }
+    }
+
+    /// Completes the invocation in progress and attaches it to its caller.
+    pub fn end_execution_trace(
+        &mut self,
+        output: Result<ScryptoValue, RuntimeError>,
+        cost_units: u32,
+        execution_time: Option<u128>,
+    ) {
+        let mut trace = self
+            .execution_trace_stack
+            .pop()
+            .expect("Execution trace should have been started");
+        trace.output = output;
+        trace.cost_units = cost_units;
+        trace.execution_time = execution_time;
+        match self.execution_trace_stack.last_mut() {
+            Some(parent) => parent.children.push(trace),
+            None => self.execution_trace.push(trace),
+        }
+    }
+
+    /// Records a resource movement.
+    pub fn add_resource_movement(
+        &mut self,
+        movement: ResourceMovement,
+        resource_address: ResourceAddress,
+        resource: AmountOrIds,
+    ) {
+        self.resource_movements
+            .push((movement, resource_address, resource));
+    }
+
+    /// Returns whether method and resource access rules are enforced.
+    pub fn auth_check_enabled(&self) -> bool {
+        self.auth_check_enabled
+    }
+
+    /// Disables all authorization checks, which is only meant for transaction previews.
+    pub fn disable_auth_check(&mut self) {
+        self.auth_check_enabled = false;
+    }
+
+    /// Returns the execution limits enforced on this transaction.
+    pub fn limits(&self) -> &ExecutionLimits {
+        &self.limits
+    }
+
+    /// Sets the execution limits enforced on this transaction.
+    pub fn set_limits(&mut self, limits: ExecutionLimits) {
+        self.limits = limits;
+    }
+
+    /// Returns the fee table used for metering.
+    pub fn fee_table(&self) -> &FeeTable {
+        &self.fee_table
+    }
+
+    /// Returns the cost unit counter.
+    pub fn cost_unit_counter(&self) -> &CostUnitCounter {
+        &self.cost_unit_counter
+    }
+
+    /// Consumes cost units, failing if the cost unit limit is exceeded.
+    pub fn consume_cost_units(&mut self, n: u32) -> Result<(), RuntimeError> {
+        self.cost_unit_counter
+            .consume(n)
+            .map_err(RuntimeError::CostingError)
+    }
+
+    fn meter_substate_read(&mut self, len: usize) {
+        let per_byte = self.fee_table.substate_read_per_byte();
+        self.cost_unit_counter
+            .add((len as u32).saturating_mul(per_byte));
+    }
+
+    fn read_substate<A: Encode, T: Decode>(&mut self, address: &A) -> Option<(T, (Hash, u32))> {
+        let substate = self.substate_store.get_substate(address)?;
+        self.meter_substate_read(substate.value.len());
+        Some((scrypto_decode(&substate.value).unwrap(), substate.phys_id))
+    }
+
+    fn read_child_substate<A: Encode, K: Encode, T: Decode>(
+        &mut self,
+        address: &A,
+        key: &K,
+    ) -> Option<(T, (Hash, u32))> {
+        let substate = self
+            .substate_store
+            .get_child_substate(address, &scrypto_encode(key))?;
+        self.meter_substate_read(substate.value.len());
+        Some((scrypto_decode(&substate.value).unwrap(), substate.phys_id))
+    }
+
+    fn read_grand_child_substate<A: Encode, C: Encode>(
+        &mut self,
+        address: &A,
+        child_key: &C,
+        grand_child_key: &[u8],
+    ) -> Option<(Vec<u8>, (Hash, u32))> {
+        let value =
+            self.substate_store
+                .get_decoded_grand_child_substate(address, child_key, grand_child_key)?;
+        self.meter_substate_read(value.0.len());
+        Some(value)
+    }
+
+    /// Consumes cost units for writing all updated substates into the substate store.
+    ///
+    /// This should be called once the transaction has been executed and before `commit()`.
+    pub fn consume_substate_writes(&mut self) -> Result<(), RuntimeError> {
+        let mut len = 0usize;
+        for update in self.packages.values() {
+            // Packages are immutable, only new ones are written
+            if update.prev_id.is_none() {
+                len += scrypto_encode(&update.value).len();
+            }
+        }
+        for update in self.components.values() {
+            len += scrypto_encode(&update.value).len();
+        }
+        for update in self.resource_managers.values() {
+            len += scrypto_encode(&update.value).len();
+        }
+        for update in self.lazy_map_entries.values() {
+            len += update.value.as_ref().map_or(0, Vec::len);
+        }
+        for update in self.vaults.values() {
+            len += scrypto_encode(&update.value).len();
+        }
+        for update in self.non_fungibles.values() {
+            len += scrypto_encode(&update.value).len();
+        }
+
+        let per_byte = self.fee_table.substate_write_per_byte();
+        self.consume_cost_units((len as u32).saturating_mul(per_byte))
+    }
+
+    /// Locks XRD taken from a vault to pay the transaction fee.
+    ///
+    /// Only vaults owned by a component can lock fees, as any unused fee is refunded to them.
+    pub fn lock_fee(&mut self, vault_id: VaultId, fee: ResourceContainer) -> Result<(), RuntimeError> {
+        if fee.resource_address() != RADIX_TOKEN {
+            return Err(RuntimeError::LockFeeNotAllowed(vault_id));
+        }
+
+        let component_address = self
+            .borrowed_vaults
+            .keys()
+            .find(|(_, borrowed_vault_id)| *borrowed_vault_id == vault_id)
+            .map(|(component_address, _)| *component_address)
+            .ok_or(RuntimeError::LockFeeNotAllowed(vault_id))?;
+        self.fee_locks.push((component_address, vault_id, fee));
+        Ok(())
+    }
+
+    /// Returns the total amount of XRD locked for fees.
+    pub fn locked_fee(&self) -> Decimal {
+        let mut total = Decimal::zero();
+        for (_, _, fee) in &self.fee_locks {
+            total += fee.total_amount();
+        }
+        total
+    }
+
+    /// Returns the sources and amounts of all fees locked so far.
+    pub fn fee_locks(&self) -> Vec<(ComponentAddress, VaultId, Decimal)> {
+        self.fee_locks
+            .iter()
+            .map(|(component_address, vault_id, fee)| {
+                (*component_address, *vault_id, fee.total_amount())
+            })
+            .collect()
+    }
+
+    /// Locks fees again from the committed state of a vault, typically after a transaction
+    /// has failed and its own state updates have been discarded.
+    ///
+    /// Nothing is locked if the vault does not exist in the substate store.
+    pub fn relock_fee(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        amount: Decimal,
+    ) {
+        let canonical_id = (component_address, vault_id);
+        if !self.vaults.contains_key(&canonical_id)
+            && self
+                .substate_store
+                .get_child_substate(&component_address, &scrypto_encode(&vault_id))
+                .is_none()
+        {
+            return;
+        }
+
+        let mut vault = self.borrow_vault_mut(&component_address, &vault_id);
+        let amount = cmp::min(amount, vault.total_amount());
+        let fee = vault
+            .take(amount)
+            .expect("Amount should not exceed vault balance");
+        self.return_borrowed_vault(&component_address, &vault_id, vault);
+        self.fee_locks.push((component_address, vault_id, fee));
+    }
+
+    /// Pays the fee for the given cost units out of the locked fees, and refunds the rest
+    /// to the vaults they were locked from.
+    ///
+    /// Returns the amount paid, which is less than the fee if not enough has been locked.
+    pub fn pay_fees(&mut self, cost_units: u32, cost_unit_price: Decimal) -> Decimal {
+        let mut remaining = cost_unit_price * cost_units;
+        let mut paid = Decimal::zero();
+
+        let fee_locks: Vec<(ComponentAddress, VaultId, ResourceContainer)> =
+            self.fee_locks.drain(..).collect();
+        for (component_address, vault_id, mut fee) in fee_locks {
+            let amount = cmp::min(remaining, fee.total_amount());
+            if amount.is_positive() {
+                let payment = fee
+                    .take_by_amount(amount)
+                    .expect("Amount should not exceed locked fee");
+                let mut system_vault = self.borrow_vault_mut(&SYSTEM_COMPONENT, &XRD_VAULT_ID);
+                system_vault
+                    .put(Bucket::new(payment))
+                    .expect("Failed to collect fee");
+                self.return_borrowed_vault(&SYSTEM_COMPONENT, &XRD_VAULT_ID, system_vault);
+                self.fee_vault_owners.insert(vault_id, component_address);
+                self.add_resource_movement(
+                    ResourceMovement::VaultWithdrawal(vault_id),
+                    RADIX_TOKEN,
+                    AmountOrIds::Amount(amount),
+                );
+                self.add_resource_movement(
+                    ResourceMovement::VaultDeposit(XRD_VAULT_ID),
+                    RADIX_TOKEN,
+                    AmountOrIds::Amount(amount),
+                );
+                remaining -= amount;
+                paid += amount;
+            }
+
+            if !fee.is_empty() {
+                let mut vault = self.borrow_vault_mut(&component_address, &vault_id);
+                vault.put(Bucket::new(fee)).expect("Failed to refund fee");
+                self.return_borrowed_vault(&component_address, &vault_id, vault);
+            }
+        }
+
+        paid
+    }
+
     /// Returns an immutable reference to a package, if exists.
     pub fn get_package(&mut self, package_address: &PackageAddress) -> Option<&Package> {
         if self.packages.contains_key(package_address) {
             return self.packages.get(package_address).map(|p| &p.value);
         }
 
-        if let Some((package, phys_id)) = self.substate_store.get_decoded_substate(package_address)
-        {
+        if let Some((package, phys_id)) = self.read_substate(package_address) {
             self.packages.insert(
                 package_address.clone(),
                 SubstateUpdate {
@@ -201,6 +757,24 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
     }
 
+    /// Instantiates the code of a package using the WASM engine.
+    pub fn instantiate_package(
+        &mut self,
+        package_address: &PackageAddress,
+    ) -> Result<Box<dyn WasmInstance>, RuntimeError> {
+        self.get_package(package_address)
+            .ok_or(RuntimeError::PackageNotFound(*package_address))?;
+        let package = &self.packages.get(package_address).unwrap().value;
+        if package.max_memory_pages() > self.limits.max_wasm_memory_pages {
+            return Err(RuntimeError::MaxWasmMemoryPagesExceeded(
+                self.limits.max_wasm_memory_pages,
+            ));
+        }
+        self.wasm_engine
+            .instantiate(*package_address, package.code_hash(), package.code())
+            .map_err(|e| RuntimeError::PackageError(PackageError::WasmValidationError(e)))
+    }
+
     /// Inserts a new package.
     pub fn create_package(&mut self, package: Package) -> PackageAddress {
         let package_address = self.new_package_address();
@@ -214,25 +788,54 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         package_address
     }
 
-    pub fn borrow_global_mut_component(
+    /// Borrows a component for a method call, as declared mutable or immutable by the blueprint
+    /// ABI. Methods not declared in the ABI are assumed to be mutable.
+    ///
+    /// Immutable methods get a read-only copy, which is never written back. Any number of
+    /// immutable borrows may co-exist, but not alongside a mutable borrow.
+    pub fn borrow_global_component(
         &mut self,
         component_address: ComponentAddress,
-    ) -> Result<Component, RuntimeError> {
-        let maybe_component = self.components.remove(&component_address);
-        if let Some(SubstateUpdate { value, prev_id }) = maybe_component {
-            self.borrowed_components.insert(component_address, prev_id);
-            Ok(value)
-        } else if self.borrowed_components.contains_key(&component_address) {
-            Err(RuntimeError::ComponentReentrancy(component_address))
-        } else if let Some((component, phys_id)) =
-            self.substate_store.get_decoded_substate(&component_address)
-        {
-            self.borrowed_components
-                .insert(component_address, Some(phys_id));
-            Ok(component)
-        } else {
-            Err(RuntimeError::ComponentNotFound(component_address))
+        method_name: &str,
+    ) -> Result<(Component, Mutability), RuntimeError> {
+        if self.borrowed_components.contains_key(&component_address) {
+            return Err(RuntimeError::ComponentReentrancy(component_address));
+        }
+        let (component, prev_id) = match self.components.get(&component_address) {
+            Some(update) => (update.value.clone(), update.prev_id),
+            None => self
+                .read_substate(&component_address)
+                .map(|(component, phys_id)| (component, Some(phys_id)))
+                .ok_or(RuntimeError::ComponentNotFound(component_address))?,
+        };
+        let package_address = component.package_address();
+        let package = self
+            .get_package(&package_address)
+            .ok_or(RuntimeError::PackageNotFound(package_address))?;
+        let mutability = package
+            .method_mutability(component.blueprint_name(), method_name)
+            .cloned()
+            .unwrap_or(Mutability::Mutable);
+
+        match mutability {
+            Mutability::Mutable => {
+                if self
+                    .shared_borrowed_components
+                    .contains_key(&component_address)
+                {
+                    return Err(RuntimeError::ComponentReentrancy(component_address));
+                }
+                self.components.remove(&component_address);
+                self.borrowed_components.insert(component_address, prev_id);
+            }
+            Mutability::Immutable => {
+                *self
+                    .shared_borrowed_components
+                    .entry(component_address)
+                    .or_default() += 1;
+            }
         }
+        Ok((component, mutability))
     }
 
     pub fn return_borrowed_global_component(
@@ -248,6 +851,12 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                     value: component,
                 },
             );
+        } else if let Some(count) = self.shared_borrowed_components.get_mut(&component_address) {
+            // Immutable borrows are read-only copies, nothing to write back
+            *count -= 1;
+            if *count == 0 {
+                self.shared_borrowed_components.remove(&component_address);
+            }
         } else {
             panic!("Component was never borrowed");
         }
@@ -259,9 +868,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             return self.components.get(&component_address).map(|c| &c.value);
         }
 
-        if let Some((component, phys_id)) =
-            self.substate_store.get_decoded_substate(&component_address)
-        {
+        if let Some((component, phys_id)) = self.read_substate(&component_address) {
             self.components.insert(
                 component_address,
                 SubstateUpdate {
@@ -301,7 +908,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 .unwrap_or(Option::None);
         }
 
-        if let Some((non_fungible, phys_id)) = self.substate_store.get_decoded_child_substate(
+        if let Some((non_fungible, phys_id)) = self.read_child_substate(
             &non_fungible_address.resource_address(),
             &non_fungible_address.non_fungible_id(),
         ) {
@@ -327,11 +934,10 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         non_fungible_address: NonFungibleAddress,
         non_fungible: Option<NonFungible>,
     ) {
-        let cur: Option<(Option<NonFungible>, (Hash, u32))> =
-            self.substate_store.get_decoded_child_substate(
-                &non_fungible_address.resource_address(),
-                &non_fungible_address.non_fungible_id(),
-            );
+        let cur: Option<(Option<NonFungible>, (Hash, u32))> = self.read_child_substate(
+            &non_fungible_address.resource_address(),
+            &non_fungible_address.non_fungible_id(),
+        );
         let prev_id = cur.map(|(_, cur_id)| cur_id);
 
         self.non_fungibles.insert(
@@ -351,17 +957,12 @@ impl<'s, S: SubstateStore> Track<'s, S> {
     ) -> Option<Vec<u8>> {
         let canonical_id = (component_address.clone(), lazy_map_id.clone(), key.to_vec());
 
-        if self.lazy_map_entries.contains_key(&canonical_id) {
-            return Some(
-                self.lazy_map_entries
-                    .get(&canonical_id)
-                    .map(|r| r.value.clone())
-                    .unwrap(),
-            );
+        if let Some(entry) = self.lazy_map_entries.get(&canonical_id) {
+            return entry.value.clone();
         }
 
         let grand_child_key = key.to_vec();
-        let value = self.substate_store.get_decoded_grand_child_substate(
+        let value = self.read_grand_child_substate(
             &component_address,
             lazy_map_id,
             &grand_child_key,
@@ -371,7 +972,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 canonical_id,
                 SubstateUpdate {
                     prev_id: Some(phys_id),
-                    value: entry_bytes.clone(),
+                    value: Some(entry_bytes.clone()),
                 },
             );
         }
@@ -388,7 +989,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         let canonical_id = (component_address.clone(), lazy_map_id.clone(), key.clone());
 
         if !self.lazy_map_entries.contains_key(&canonical_id) {
-            let entry = self.substate_store.get_decoded_grand_child_substate(
+            let entry = self.read_grand_child_substate(
                 &component_address,
                 &lazy_map_id,
                 &key,
@@ -398,7 +999,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                     canonical_id,
                     SubstateUpdate {
                         prev_id: Some(phys_id),
-                        value,
+                        value: Some(value),
                     },
                 );
                 return;
@@ -406,19 +1007,92 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(entry) = self.lazy_map_entries.get_mut(&canonical_id) {
-            entry.value = value;
+            entry.value = Some(value);
         } else {
             // TODO: Virtual Down
             self.lazy_map_entries.insert(
                 canonical_id,
                 SubstateUpdate {
                     prev_id: None,
-                    value,
+                    value: Some(value),
                 },
             );
         }
     }
 
+    /// Removes a lazy map entry, leaving a tombstone until the transaction is committed.
+    ///
+    /// Returns the removed value, if the entry exists.
+    pub fn remove_lazy_map_entry(
+        &mut self,
+        component_address: ComponentAddress,
+        lazy_map_id: &LazyMapId,
+        key: &[u8],
+    ) -> Option<Vec<u8>> {
+        // Loads the entry, so that the committed one is taken down on commit
+        let value = self.get_lazy_map_entry(component_address, lazy_map_id, key);
+        let canonical_id = (component_address, *lazy_map_id, key.to_vec());
+        if let Some(entry) = self.lazy_map_entries.get_mut(&canonical_id) {
+            entry.value = None;
+        }
+        value
+    }
+
+    /// Lists up to `limit` entries of a lazy map whose key is greater than `after`, in
+    /// ascending order of key bytes.
+    ///
+    /// Committed entries are merged with the updates and tombstones of this transaction.
+    pub fn list_lazy_map_entries(
+        &mut self,
+        component_address: ComponentAddress,
+        lazy_map_id: &LazyMapId,
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, Vec<u8>)> {
+        if limit == 0 {
+            return Vec::new();
+        }
+
+        let mut entries = BTreeMap::new();
+        let prefix = scrypto_encode(lazy_map_id);
+        let mut cursor = after.map(|key| key.to_vec());
+        loop {
+            let page = self.substate_store.get_child_substates(
+                &component_address,
+                &prefix,
+                cursor.as_deref(),
+                limit,
+            );
+            let exhausted = page.len() < limit;
+            for (key, substate) in page {
+                // Shadowed entries are billed too, as reading them costs the same
+                self.meter_substate_read(substate.value.len());
+                let canonical_id = (component_address, *lazy_map_id, key.clone());
+                if !self.lazy_map_entries.contains_key(&canonical_id) {
+                    entries.insert(key.clone(), substate.value);
+                }
+                cursor = Some(key);
+            }
+            // Any committed entry not seen yet sorts after the first `limit` entries
+            if exhausted || entries.len() >= limit {
+                break;
+            }
+        }
+
+        for ((address, id, key), update) in &self.lazy_map_entries {
+            if *address == component_address
+                && id == lazy_map_id
+                && after.iter().all(|after| key.as_slice() > *after)
+            {
+                if let Some(value) = &update.value {
+                    entries.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        entries.into_iter().take(limit).collect()
+    }
+
     /// Returns an immutable reference to a resource manager, if exists.
     pub fn get_resource_manager(
         &mut self,
@@ -431,9 +1105,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 .map(|r| &r.value);
         }
 
-        if let Some((resource_manager, phys_id)) =
-            self.substate_store.get_decoded_substate(resource_address)
-        {
+        if let Some((resource_manager, phys_id)) = self.read_substate(resource_address) {
             self.resource_managers.insert(
                 resource_address.clone(),
                 SubstateUpdate {
@@ -463,9 +1135,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             self.borrowed_resource_managers
                 .insert(resource_address, prev_id);
             Ok(value)
-        } else if let Some((resource_manager, phys_id)) =
-            self.substate_store.get_decoded_substate(&resource_address)
-        {
+        } else if let Some((resource_manager, phys_id)) = self.read_substate(&resource_address) {
             self.borrowed_resource_managers
                 .insert(resource_address, Some(phys_id));
             Ok(resource_manager)
@@ -519,7 +1189,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             return value;
         }
 
-        if let Some((vault, phys_id)) = self.substate_store.get_decoded_child_substate(component_address, vid) {
+        if let Some((vault, phys_id)) = self.read_child_substate(component_address, vid) {
             self.borrowed_vaults
                 .insert(canonical_id, Some(phys_id));
             return vault;
@@ -528,6 +1198,19 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         panic!("Should not get here");
     }
 
+    /// Returns whether a component holds a vault which is not currently borrowed.
+    pub fn has_vault(&self, component_address: &ComponentAddress, vid: &VaultId) -> bool {
+        let canonical_id = (*component_address, *vid);
+        if self.vaults.contains_key(&canonical_id) {
+            return true;
+        }
+        !self.borrowed_vaults.contains_key(&canonical_id)
+            && self
+                .substate_store
+                .get_child_substate(component_address, &scrypto_encode(vid))
+                .is_some()
+    }
+
     pub fn return_borrowed_vault(
         &mut self,
         component_address: &ComponentAddress,
@@ -625,8 +1308,48 @@ impl<'s, S: SubstateStore> Track<'s, S> {
     /// Commits changes to the underlying ledger.
     /// Currently none of these objects are deleted so all commits are puts
     pub fn commit(&mut self) -> CommitReceipt {
+        self.finalize(true)
+    }
+
+    /// Produces the same receipt as `commit()`, without writing anything to the ledger.
+    pub fn preview_commit(&mut self) -> CommitReceipt {
+        self.finalize(false)
+    }
+
+    /// Reads the raw value of a substate from the underlying ledger.
+    fn committed_value(&self, key: &SubstateKey) -> Option<Vec<u8>> {
+        let substate = match key {
+            SubstateKey::Package(package_address) => {
+                self.substate_store.get_substate(package_address)
+            }
+            SubstateKey::Component(component_address) => {
+                self.substate_store.get_substate(component_address)
+            }
+            SubstateKey::ResourceManager(resource_address) => {
+                self.substate_store.get_substate(resource_address)
+            }
+            SubstateKey::Vault(component_address, vault_id) => self
+                .substate_store
+                .get_child_substate(component_address, &scrypto_encode(vault_id)),
+            SubstateKey::LazyMapEntry(component_address, lazy_map_id, key) => {
+                let mut child_key = scrypto_encode(lazy_map_id);
+                child_key.extend(key);
+                self.substate_store
+                    .get_child_substate(component_address, &child_key)
+            }
+            SubstateKey::NonFungible(non_fungible_address) => {
+                self.substate_store.get_child_substate(
+                    &non_fungible_address.resource_address(),
+                    &scrypto_encode(&non_fungible_address.non_fungible_id()),
+                )
+            }
+        };
+        substate.map(|substate| substate.value)
+    }
+
+    fn finalize(&mut self, write: bool) -> CommitReceipt {
         // Sanity check
-        if !self.borrowed_components.is_empty() {
+        if !self.borrowed_components.is_empty() || !self.shared_borrowed_components.is_empty() {
             panic!("Borrowed components should be empty by end of transaction.");
         }
         if !self.borrowed_resource_managers.is_empty() {
@@ -649,8 +1372,13 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             let phys_id = id_gen.next();
             receipt.up(phys_id);
 
-            self.substate_store
-                .put_encoded_substate(&package_address, &package.value, phys_id);
+            let key = SubstateKey::Package(package_address);
+            let old_value = package.prev_id.and_then(|_| self.committed_value(&key));
+            receipt.change(key, old_value, Some(scrypto_encode(&package.value)));
+            if write {
+                self.substate_store
+                    .put_encoded_substate(&package_address, &package.value, phys_id);
+            }
         }
 
         let component_addresses: Vec<ComponentAddress> = self.components.keys().cloned().collect();
@@ -663,8 +1391,16 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             let phys_id = id_gen.next();
             receipt.up(phys_id);
 
-            self.substate_store
-                .put_encoded_substate(&component_address, &component.value, phys_id);
+            let key = SubstateKey::Component(component_address);
+            let old_value = component.prev_id.and_then(|_| self.committed_value(&key));
+            receipt.change(key, old_value, Some(scrypto_encode(&component.value)));
+            if write {
+                self.substate_store.put_encoded_substate(
+                    &component_address,
+                    &component.value,
+                    phys_id,
+                );
+            }
         }
 
         let resource_addresses: Vec<ResourceAddress> =
@@ -678,31 +1414,64 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             let phys_id = id_gen.next();
             receipt.up(phys_id);
 
-            self.substate_store.put_encoded_substate(
-                &resource_address,
-                &resource_manager.value,
-                phys_id,
+            let key = SubstateKey::ResourceManager(resource_address);
+            let old_value = resource_manager
+                .prev_id
+                .and_then(|_| self.committed_value(&key));
+            receipt.change(
+                key,
+                old_value,
+                Some(scrypto_encode(&resource_manager.value)),
             );
+            if write {
+                self.substate_store.put_encoded_substate(
+                    &resource_address,
+                    &resource_manager.value,
+                    phys_id,
+                );
+            }
         }
 
         let entry_ids: Vec<(ComponentAddress, LazyMapId, Vec<u8>)> =
             self.lazy_map_entries.keys().cloned().collect();
         for entry_id in entry_ids {
             let entry = self.lazy_map_entries.remove(&entry_id).unwrap();
+            if entry.prev_id.is_none() && entry.value.is_none() {
+                // Inserted and removed within this transaction
+                continue;
+            }
             if let Some(prev_id) = entry.prev_id {
                 receipt.down(prev_id);
             }
             let phys_id = id_gen.next();
-            receipt.up(phys_id);
+            if entry.value.is_some() {
+                receipt.up(phys_id);
+            }
 
             let (component_address, lazy_map_id, key) = entry_id;
-            self.substate_store.put_encoded_grand_child_substate(
-                &component_address,
-                &lazy_map_id,
-                &key,
-                &entry.value,
-                phys_id,
-            );
+            let substate_key =
+                SubstateKey::LazyMapEntry(component_address, lazy_map_id, key.clone());
+            let old_value = entry
+                .prev_id
+                .and_then(|_| self.committed_value(&substate_key));
+            receipt.change(substate_key, old_value, entry.value.clone());
+            if write {
+                match &entry.value {
+                    Some(value) => self.substate_store.put_encoded_grand_child_substate(
+                        &component_address,
+                        &lazy_map_id,
+                        &key,
+                        value,
+                        phys_id,
+                    ),
+                    None => {
+                        let mut child_key = scrypto_encode(&lazy_map_id);
+                        child_key.extend(key);
+                        self.substate_store
+                            .delete_child_substate(&component_address, &child_key);
+                    }
+                }
+            }
         }
 
         let vault_ids: Vec<(ComponentAddress, VaultId)> = self.vaults.keys().cloned().collect();
@@ -715,12 +1484,26 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             receipt.up(phys_id);
 
             let (component_address, vault_id) = vault_id;
-            self.substate_store.put_encoded_child_substate(
-                &component_address,
-                &vault_id,
-                &vault.value,
-                phys_id,
+            let key = SubstateKey::Vault(component_address, vault_id);
+            let old_value = vault.prev_id.and_then(|_| self.committed_value(&key));
+            receipt.vault_update(
+                component_address,
+                vault.value.resource_address(),
+                old_value.as_ref().map(|value| {
+                    let old_vault: Vault = scrypto_decode(value).unwrap();
+                    old_vault.total_amount_or_ids()
+                }),
+                vault.value.total_amount_or_ids(),
             );
+            receipt.change(key, old_value, Some(scrypto_encode(&vault.value)));
+            if write {
+                self.substate_store.put_encoded_child_substate(
+                    &component_address,
+                    &vault_id,
+                    &vault.value,
+                    phys_id,
+                );
+            }
         }
 
         let non_fungible_addresses: Vec<NonFungibleAddress> =
@@ -733,14 +1516,29 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             let phys_id = id_gen.next();
             receipt.up(phys_id);
 
-            self.substate_store.put_encoded_child_substate(
-                &non_fungible_address.resource_address(),
-                &non_fungible_address.non_fungible_id(),
-                &non_fungible.value,
-                phys_id,
-            );
+            let key = SubstateKey::NonFungible(non_fungible_address.clone());
+            let old_value = non_fungible
+                .prev_id
+                .and_then(|_| self.committed_value(&key));
+            receipt.change(key, old_value, Some(scrypto_encode(&non_fungible.value)));
+            if write {
+                self.substate_store.put_encoded_child_substate(
+                    &non_fungible_address.resource_address(),
+                    &non_fungible_address.non_fungible_id(),
+                    &non_fungible.value,
+                    phys_id,
+                );
+            }
         }
 
+        receipt
+            .balance_changes
+            .values_mut()
+            .for_each(|changes| changes.retain(|_, change| !change.is_empty()));
+        receipt
+            .balance_changes
+            .retain(|_, changes| !changes.is_empty());
+
         receipt
     }
 }
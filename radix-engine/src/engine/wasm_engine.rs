@@ -0,0 +1,50 @@
+use scrypto::crypto::Hash;
+use scrypto::engine::types::*;
+use scrypto::rust::boxed::Box;
+use scrypto::rust::vec::Vec;
+
+use crate::errors::*;
+
+/// Represents the host side of a running WASM instance, i.e. the Radix Engine.
+pub trait WasmRuntime {
+    /// Handles a system call, given the operation code and the encoded input.
+    fn main(&mut self, op: u32, input: &[u8]) -> Result<Vec<u8>, RuntimeError>;
+
+    /// Consumes cost units, as instructed by the metering instrumentation.
+    fn consume_cost_units(&mut self, n: u32) -> Result<(), RuntimeError>;
+}
+
+/// Represents an instantiated WASM module.
+pub trait WasmInstance {
+    /// Invokes an export which takes no arguments and returns a pointer to the encoded
+    /// return value, with system calls handled by the given runtime.
+    ///
+    /// Traps raised by the runtime are passed through, traps raised by the stack limiter
+    /// result in `RuntimeError::MaxStackHeightExceeded` and other traps in
+    /// `RuntimeError::InvokeError`.
+    fn invoke_export(
+        &mut self,
+        name: &str,
+        runtime: &mut dyn WasmRuntime,
+    ) -> Result<Vec<u8>, RuntimeError>;
+}
+
+/// The default number of compiled modules an engine keeps in its cache.
+pub const DEFAULT_MODULE_CACHE_SIZE: usize = 128;
+
+/// A WASM engine compiles and instantiates WASM modules.
+///
+/// All engines must behave identically, as the code they run is metered by instrumentation.
+///
+/// Compiled modules are cached by package address and code hash, so that subsequent calls and
+/// transactions only pay for instantiation. Every invocation still gets a fresh instance, as
+/// no memory state may leak from one call into another.
+pub trait WasmEngine {
+    /// Instantiates the given, already validated and instrumented, code of a package.
+    fn instantiate(
+        &mut self,
+        package_address: PackageAddress,
+        code_hash: Hash,
+        code: &[u8],
+    ) -> Result<Box<dyn WasmInstance>, WasmValidationError>;
+}
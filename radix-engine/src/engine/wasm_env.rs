@@ -5,6 +5,14 @@ use wasmi::*;
 pub const ENGINE_FUNCTION_INDEX: usize = 0;
 /// Radix Engine entrance function name.
 pub const ENGINE_FUNCTION_NAME: &str = "radix_engine";
+/// Cost unit metering function index.
+pub const COST_UNIT_FUNCTION_INDEX: usize = 1;
+/// Cost unit metering function name, injected by instrumentation.
+pub const COST_UNIT_FUNCTION_NAME: &str = "gas";
+/// Stack height counter name, exported after the stack limiter has been injected.
+pub const STACK_HEIGHT_GLOBAL_NAME: &str = "scrypto_stack_height";
+/// The maximum stack height of a WASM function call chain, as counted by the stack limiter.
+pub const MAX_STACK_HEIGHT: u32 = 64 * 1024;
 
 /// An `env` module resolver defines how symbols in `env` are resolved.
 pub struct EnvModuleResolver;
@@ -25,6 +33,17 @@ impl ModuleImportResolver for EnvModuleResolver {
                     ENGINE_FUNCTION_INDEX,
                 ))
             }
+            COST_UNIT_FUNCTION_NAME => {
+                if signature.params() != [ValueType::I32] || signature.return_type().is_some() {
+                    return Err(Error::Instantiation(
+                        "Function signature does not match".into(),
+                    ));
+                }
+                Ok(FuncInstance::alloc_host(
+                    signature.clone(),
+                    COST_UNIT_FUNCTION_INDEX,
+                ))
+            }
             _ => Err(Error::Instantiation(format!(
                 "Export {} not found",
                 field_name
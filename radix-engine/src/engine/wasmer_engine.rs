@@ -0,0 +1,241 @@
+use std::sync::{Arc, Mutex};
+
+use lru::LruCache;
+use scrypto::crypto::Hash;
+use scrypto::engine::types::*;
+use wasmer::{
+    Exports, Function, ImportObject, Instance, LazyInit, Memory, Module, NativeFunc, Store, Val,
+    WasmerEnv,
+};
+
+use crate::engine::*;
+use crate::errors::*;
+
+/// A WASM engine based on the `wasmer` JIT compiler.
+pub struct WasmerEngine {
+    store: Store,
+    /// Compiled modules, keyed by package address and code hash
+    modules: LruCache<(PackageAddress, Hash), Module>,
+}
+
+/// An instance of a module, compiled by `wasmer`.
+pub struct WasmerInstance {
+    instance: Instance,
+    /// Points to the runtime of the ongoing invocation, see `WasmerInstanceEnv`
+    runtime_ptr: Arc<Mutex<usize>>,
+}
+
+/// The environment of host functions.
+///
+/// Host functions must be `Send + Sync`, so the runtime of the ongoing invocation is passed
+/// as a raw pointer, which is only valid during `WasmerInstance::invoke_export`.
+#[derive(WasmerEnv, Clone)]
+struct WasmerInstanceEnv {
+    #[wasmer(export)]
+    memory: LazyInit<Memory>,
+    #[wasmer(export(optional = true, name = "scrypto_alloc"))]
+    scrypto_alloc: LazyInit<NativeFunc<i32, i32>>,
+    runtime_ptr: Arc<Mutex<usize>>,
+}
+
+impl WasmerEngine {
+    pub fn new() -> Self {
+        Self::with_cache_size(DEFAULT_MODULE_CACHE_SIZE)
+    }
+
+    /// Creates an engine which keeps up to `cache_size` compiled modules.
+    pub fn with_cache_size(cache_size: usize) -> Self {
+        assert!(cache_size > 0, "Module cache size must be non-zero");
+        Self {
+            store: Store::default(),
+            modules: LruCache::new(cache_size),
+        }
+    }
+}
+
+impl Default for WasmerEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WasmEngine for WasmerEngine {
+    fn instantiate(
+        &mut self,
+        package_address: PackageAddress,
+        code_hash: Hash,
+        code: &[u8],
+    ) -> Result<Box<dyn WasmInstance>, WasmValidationError> {
+        let key = (package_address, code_hash);
+        if !self.modules.contains(&key) {
+            let module =
+                Module::new(&self.store, code).map_err(|_| WasmValidationError::InvalidModule)?;
+            self.modules.put(key, module);
+        }
+        let module = self.modules.get(&key).unwrap();
+
+        let runtime_ptr = Arc::new(Mutex::new(0));
+        let env = WasmerInstanceEnv {
+            memory: LazyInit::new(),
+            scrypto_alloc: LazyInit::new(),
+            runtime_ptr: runtime_ptr.clone(),
+        };
+        let mut exports = Exports::new();
+        exports.insert(
+            ENGINE_FUNCTION_NAME,
+            Function::new_native_with_env(&self.store, env.clone(), radix_engine),
+        );
+        exports.insert(
+            COST_UNIT_FUNCTION_NAME,
+            Function::new_native_with_env(&self.store, env, consume_cost_units),
+        );
+        let mut import_object = ImportObject::new();
+        import_object.register("env", exports);
+
+        let instance = Instance::new(module, &import_object)
+            .map_err(|_| WasmValidationError::InvalidModule)?;
+        if instance.exports.get_memory("memory").is_err() {
+            return Err(WasmValidationError::NoValidMemoryExport);
+        }
+
+        Ok(Box::new(WasmerInstance {
+            instance,
+            runtime_ptr,
+        }))
+    }
+}
+
+impl WasmerInstanceEnv {
+    fn runtime(&self) -> &mut dyn WasmRuntime {
+        let ptr = *self.runtime_ptr.lock().unwrap();
+        assert_ne!(ptr, 0, "Host function called outside of an invocation");
+        // SAFETY: a non-zero pointer has been set by `WasmerInstance::invoke_export` and points
+        // to a `&mut dyn WasmRuntime` on its stack frame. Host functions are only called by the
+        // module while that frame is executing `Function::call`, and the pointer is reset before
+        // `invoke_export` returns, so it never outlives the runtime it points to. Host functions
+        // run on the calling thread and one at a time, so no other reference to the runtime is
+        // alive while the returned one is in use.
+        unsafe { &mut **(ptr as *mut &mut dyn WasmRuntime) }
+    }
+
+    fn memory(&self) -> &Memory {
+        self.memory_ref().expect("Memory should be exported")
+    }
+}
+
+fn read_memory(memory: &Memory, ptr: u32, len: u32) -> Result<Vec<u8>, RuntimeError> {
+    let end = ptr.checked_add(len).ok_or(RuntimeError::MemoryAccessError)?;
+    let view = memory.view::<u8>();
+    // SECURITY: check bounds before allocating memory
+    if end as usize > view.len() {
+        return Err(RuntimeError::MemoryAccessError);
+    }
+
+    Ok(view[ptr as usize..end as usize]
+        .iter()
+        .map(|cell| cell.get())
+        .collect())
+}
+
+fn read_value(memory: &Memory, ptr: u32) -> Result<Vec<u8>, RuntimeError> {
+    let len = read_memory(memory, ptr, 4)?;
+    let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]);
+    let start = ptr.checked_add(4).ok_or(RuntimeError::MemoryAccessError)?;
+    read_memory(memory, start, len)
+}
+
+fn send_value(env: &WasmerInstanceEnv, bytes: &[u8]) -> Result<i32, RuntimeError> {
+    let scrypto_alloc = env
+        .scrypto_alloc_ref()
+        .ok_or(RuntimeError::MemoryAllocError)?;
+    let ptr = scrypto_alloc
+        .call(bytes.len() as i32)
+        .map_err(into_runtime_error)?;
+
+    let view = env.memory().view::<u8>();
+    let start = (ptr as u32)
+        .checked_add(4)
+        .ok_or(RuntimeError::MemoryAllocError)? as usize;
+    if start + bytes.len() > view.len() {
+        return Err(RuntimeError::MemoryAllocError);
+    }
+    for (cell, byte) in view[start..start + bytes.len()].iter().zip(bytes) {
+        cell.set(*byte);
+    }
+    Ok(ptr)
+}
+
+fn into_runtime_error(e: wasmer::RuntimeError) -> RuntimeError {
+    match e.downcast::<RuntimeError>() {
+        // Pass-through runtime errors
+        Ok(e) => e,
+        Err(_) => RuntimeError::InvokeError,
+    }
+}
+
+fn radix_engine(
+    env: &WasmerInstanceEnv,
+    op: i32,
+    input_ptr: i32,
+    input_len: i32,
+) -> Result<i32, RuntimeError> {
+    let input = read_memory(env.memory(), input_ptr as u32, input_len as u32)?;
+    let output = env.runtime().main(op as u32, &input)?;
+    send_value(env, &output)
+}
+
+fn consume_cost_units(env: &WasmerInstanceEnv, cost_units: i32) -> Result<(), RuntimeError> {
+    env.runtime().consume_cost_units(cost_units as u32)
+}
+
+impl WasmerInstance {
+    /// Returns whether the stack limiter has trapped, i.e. the stack height counter is left
+    /// above the maximum.
+    fn stack_height_exceeded(&self) -> bool {
+        match self.instance.exports.get_global(STACK_HEIGHT_GLOBAL_NAME) {
+            Ok(global) => {
+                matches!(global.get(), Val::I32(height) if height as u32 > MAX_STACK_HEIGHT)
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+impl WasmInstance for WasmerInstance {
+    fn invoke_export(
+        &mut self,
+        name: &str,
+        runtime: &mut dyn WasmRuntime,
+    ) -> Result<Vec<u8>, RuntimeError> {
+        let function = self
+            .instance
+            .exports
+            .get_function(name)
+            .map_err(|_| RuntimeError::InvokeError)?;
+
+        let mut runtime = runtime;
+        // The pointer is only dereferenced by host functions during `function.call` below, and
+        // is reset right after it, see `WasmerInstanceEnv::runtime`
+        *self.runtime_ptr.lock().unwrap() = &mut runtime as *mut _ as usize;
+        let rtn = function.call(&[]).map_err(|e| match into_runtime_error(e) {
+            RuntimeError::InvokeError if self.stack_height_exceeded() => {
+                RuntimeError::MaxStackHeightExceeded
+            }
+            e => e,
+        });
+        *self.runtime_ptr.lock().unwrap() = 0;
+
+        match rtn?.first() {
+            Some(Val::I32(ptr)) => {
+                let memory = self
+                    .instance
+                    .exports
+                    .get_memory("memory")
+                    .map_err(|_| RuntimeError::MemoryAccessError)?;
+                read_value(memory, *ptr as u32)
+            }
+            Some(_) => Err(RuntimeError::InvalidReturnType),
+            None => Err(RuntimeError::NoReturnData),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use lru::LruCache;
+use scrypto::crypto::Hash;
+use scrypto::engine::types::*;
+use scrypto::rust::boxed::Box;
+use scrypto::rust::vec;
+use scrypto::rust::vec::Vec;
+use wasmi::*;
+
+use crate::engine::*;
+use crate::errors::*;
+
+/// A WASM engine based on the `wasmi` interpreter.
+pub struct WasmiEngine {
+    /// Parsed modules, keyed by package address and code hash
+    modules: LruCache<(PackageAddress, Hash), Module>,
+}
+
+/// An instance of a module, running on `wasmi`.
+pub struct WasmiInstance {
+    module: ModuleRef,
+    memory: MemoryRef,
+}
+
+/// Bridges system calls from `wasmi` to a `WasmRuntime`.
+struct WasmiExternals<'a, 'r> {
+    instance: &'a WasmiInstance,
+    runtime: &'r mut dyn WasmRuntime,
+}
+
+impl WasmiEngine {
+    pub fn new() -> Self {
+        Self::with_cache_size(DEFAULT_MODULE_CACHE_SIZE)
+    }
+
+    /// Creates an engine which keeps up to `cache_size` parsed modules.
+    pub fn with_cache_size(cache_size: usize) -> Self {
+        assert!(cache_size > 0, "Module cache size must be non-zero");
+        Self {
+            modules: LruCache::new(cache_size),
+        }
+    }
+}
+
+impl Default for WasmiEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WasmEngine for WasmiEngine {
+    fn instantiate(
+        &mut self,
+        package_address: PackageAddress,
+        code_hash: Hash,
+        code: &[u8],
+    ) -> Result<Box<dyn WasmInstance>, WasmValidationError> {
+        let key = (package_address, code_hash);
+        if !self.modules.contains(&key) {
+            let module =
+                Module::from_buffer(code).map_err(|_| WasmValidationError::InvalidModule)?;
+            self.modules.put(key, module);
+        }
+        let module = self.modules.get(&key).unwrap();
+
+        let module = ModuleInstance::new(
+            module,
+            &ImportsBuilder::new().with_resolver("env", &EnvModuleResolver),
+        )
+        .map_err(|_| WasmValidationError::InvalidModule)?
+        .assert_no_start();
+
+        match module.export_by_name("memory") {
+            Some(ExternVal::Memory(memory)) => Ok(Box::new(WasmiInstance { module, memory })),
+            _ => Err(WasmValidationError::NoValidMemoryExport),
+        }
+    }
+}
+
+impl WasmiInstance {
+    /// Returns whether the stack limiter has trapped, i.e. the stack height counter is left
+    /// above the maximum.
+    fn stack_height_exceeded(&self) -> bool {
+        match self.module.export_by_name(STACK_HEIGHT_GLOBAL_NAME) {
+            Some(ExternVal::Global(global)) => {
+                matches!(global.get(), RuntimeValue::I32(height) if height as u32 > MAX_STACK_HEIGHT)
+            }
+            _ => false,
+        }
+    }
+
+    fn read_memory(&self, ptr: u32, len: u32) -> Result<Vec<u8>, RuntimeError> {
+        let end = ptr.checked_add(len).ok_or(RuntimeError::MemoryAccessError)?;
+        // SECURITY: check bounds before allocating memory
+        if end as usize > self.memory.current_size().0 * 65536 {
+            return Err(RuntimeError::MemoryAccessError);
+        }
+
+        let mut bytes = vec![0u8; len as usize];
+        self.memory
+            .get_into(ptr, &mut bytes)
+            .map_err(|_| RuntimeError::MemoryAccessError)?;
+        Ok(bytes)
+    }
+
+    fn read_value(&self, ptr: u32) -> Result<Vec<u8>, RuntimeError> {
+        let len: u32 = self
+            .memory
+            .get_value(ptr)
+            .map_err(|_| RuntimeError::MemoryAccessError)?;
+        let start = ptr.checked_add(4).ok_or(RuntimeError::MemoryAccessError)?;
+        self.read_memory(start, len)
+    }
+
+    fn send_value(&self, bytes: &[u8], externals: &mut WasmiExternals) -> Result<i32, RuntimeError> {
+        let result = self.module.invoke_export(
+            "scrypto_alloc",
+            &[RuntimeValue::I32(bytes.len() as i32)],
+            externals,
+        );
+
+        if let Ok(Some(RuntimeValue::I32(ptr))) = result {
+            if self.memory.set((ptr + 4) as u32, bytes).is_ok() {
+                return Ok(ptr);
+            }
+        }
+
+        Err(RuntimeError::MemoryAllocError)
+    }
+}
+
+impl WasmInstance for WasmiInstance {
+    fn invoke_export(
+        &mut self,
+        name: &str,
+        runtime: &mut dyn WasmRuntime,
+    ) -> Result<Vec<u8>, RuntimeError> {
+        let mut externals = WasmiExternals {
+            instance: self,
+            runtime,
+        };
+
+        let rtn = self
+            .module
+            .invoke_export(name, &[], &mut externals)
+            .map_err(|e| match e.into_host_error() {
+                // Pass-through runtime errors
+                Some(host_error) => *host_error.downcast::<RuntimeError>().unwrap(),
+                None if self.stack_height_exceeded() => RuntimeError::MaxStackHeightExceeded,
+                None => RuntimeError::InvokeError,
+            })?
+            .ok_or(RuntimeError::NoReturnData)?;
+
+        match rtn {
+            RuntimeValue::I32(ptr) => self.read_value(ptr as u32),
+            _ => Err(RuntimeError::InvalidReturnType),
+        }
+    }
+}
+
+impl<'a, 'r> Externals for WasmiExternals<'a, 'r> {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        match index {
+            ENGINE_FUNCTION_INDEX => {
+                let op: u32 = args.nth_checked(0)?;
+                let input_ptr: u32 = args.nth_checked(1)?;
+                let input_len: u32 = args.nth_checked(2)?;
+
+                let instance = self.instance;
+                let input = instance.read_memory(input_ptr, input_len)?;
+                let output = self.runtime.main(op, &input)?;
+                let output_ptr = instance.send_value(&output, self)?;
+                Ok(Some(RuntimeValue::I32(output_ptr)))
+            }
+            COST_UNIT_FUNCTION_INDEX => {
+                let cost_units: u32 = args.nth_checked(0)?;
+                self.runtime.consume_cost_units(cost_units)?;
+                Ok(None)
+            }
+            _ => Err(RuntimeError::HostFunctionNotFound(index).into()),
+        }
+    }
+}
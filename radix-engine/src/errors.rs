@@ -6,6 +6,7 @@ use scrypto::values::*;
 use wasmi::*;
 
 use crate::engine::*;
+use crate::fee::*;
 use crate::model::*;
 
 /// Error coming from WASMI module which maps to wasmi:Error but is cloneable
@@ -63,6 +64,14 @@ pub enum WasmValidationError {
     NoPackageInitExport(WasmiError),
     /// package_init function is not the correct interface
     InvalidPackageInit,
+    /// The ABI exports consumed more than `MAX_ABI_COST_UNITS`.
+    AbiCostUnitLimitExceeded,
+    /// The wasm module imports a function reserved for instrumentation.
+    ReservedImportNotAllowed,
+    /// Failed to instrument the wasm module for metering.
+    FailedToInstrumentCode,
+    /// The wasm module requires more memory than allowed.
+    TooManyMemoryPages,
 }
 
 /// Represents an error when validating a transaction.
@@ -73,6 +82,21 @@ pub enum TransactionValidationError {
     VaultNotAllowed(VaultId),
     LazyMapNotAllowed(LazyMapId),
     InvalidSignature,
+    MissingNotarySignature,
+    InvalidEpochRange {
+        start_epoch_inclusive: u64,
+        end_epoch_exclusive: u64,
+    },
+    InvalidNetwork {
+        expected: u32,
+        actual: u32,
+    },
+    EpochOutOfRange {
+        current_epoch: u64,
+        start_epoch_inclusive: u64,
+        end_epoch_exclusive: u64,
+    },
+    IntentAlreadyCommitted(Hash),
 }
 
 /// Represents an error when executing a transaction.
@@ -130,6 +154,30 @@ pub enum RuntimeError {
 
     ComponentReentrancy(ComponentAddress),
 
+    /// Component state can not be written by an immutable method.
+    ImmutableComponentWrite(ComponentAddress),
+
+    /// The maximum depth of nested invocations has been exceeded.
+    MaxCallDepthExceeded(usize),
+
+    /// The maximum height of the WASM stack has been exceeded.
+    MaxStackHeightExceeded,
+
+    /// The declared maximum size of a WASM instance's linear memory, in pages, exceeds the limit.
+    MaxWasmMemoryPagesExceeded(u32),
+
+    /// The maximum size of a substate, in bytes, has been exceeded.
+    MaxSubstateSizeExceeded(usize),
+
+    /// The maximum number of buckets in a call frame has been exceeded.
+    MaxBucketsPerFrameExceeded(usize),
+
+    /// The maximum number of proofs in a call frame has been exceeded.
+    MaxProofsPerFrameExceeded(usize),
+
+    /// The maximum number of lazy map entries listed in a single call has been exceeded.
+    MaxLazyMapPageSizeExceeded(u32),
+
     /// Component does not exist.
     ComponentNotFound(ComponentAddress),
 
@@ -166,6 +214,12 @@ pub enum RuntimeError {
     /// Duplicate Vault added
     DuplicateVault(VaultId),
 
+    /// The vault does not hold the resource of the resource manager acting on it.
+    VaultResourceMismatch(VaultId, ResourceAddress),
+
+    /// Vault access rules can not refer to a schema path, as vaults have no state of their own.
+    SchemaPathInVaultAccessRules(String),
+
     /// Bucket does not exist.
     BucketNotFound(BucketId),
 
@@ -208,6 +262,12 @@ pub enum RuntimeError {
     /// Invalid log level.
     InvalidLevel,
 
+    /// The event is not declared by the blueprint.
+    EventNotDeclared(String),
+
+    /// The event data does not match the schema declared by the blueprint.
+    EventSchemaMismatch(String),
+
     /// Resource check failure.
     ResourceCheckFailure,
 
@@ -233,6 +293,17 @@ pub enum RuntimeError {
     /// Can't move restricted proof.
     CantMoveRestrictedProof(ProofId),
 
+    /// Cost unit metering error, e.g. the cost unit limit has been exceeded.
+    CostingError(CostUnitCounterError),
+
+    /// Fee can only be locked from XRD vaults owned by a component.
+    LockFeeNotAllowed(VaultId),
+
+    /// Not enough fee has been locked to pay for the transaction.
+    InsufficientFeeLocked {
+        required: Decimal,
+        locked: Decimal,
+    },
 }
 
 impl fmt::Display for RuntimeError {
@@ -242,3 +313,6 @@ impl fmt::Display for RuntimeError {
 }
 
 impl HostError for RuntimeError {}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for RuntimeError {}
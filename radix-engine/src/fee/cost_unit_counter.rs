@@ -0,0 +1,56 @@
+/// Represents an error when consuming cost units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CostUnitCounterError {
+    /// The transaction has consumed more cost units than its limit.
+    LimitExceeded { limit: u32, consumed: u32 },
+}
+
+/// Keeps track of the cost units consumed by a transaction.
+#[derive(Debug, Clone)]
+pub struct CostUnitCounter {
+    limit: u32,
+    consumed: u32,
+}
+
+impl CostUnitCounter {
+    pub fn new(limit: u32) -> Self {
+        Self { limit, consumed: 0 }
+    }
+
+    /// Consumes the given amount of cost units, failing if the limit is exceeded.
+    pub fn consume(&mut self, n: u32) -> Result<(), CostUnitCounterError> {
+        self.add(n);
+        self.check()
+    }
+
+    /// Adds the given amount of cost units without checking the limit.
+    ///
+    /// The limit is enforced by the next call to `consume()` or `check()`.
+    pub fn add(&mut self, n: u32) {
+        self.consumed = self.consumed.saturating_add(n);
+    }
+
+    /// Checks whether the consumed cost units are within the limit.
+    pub fn check(&self) -> Result<(), CostUnitCounterError> {
+        if self.consumed > self.limit {
+            Err(CostUnitCounterError::LimitExceeded {
+                limit: self.limit,
+                consumed: self.consumed,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn consumed(&self) -> u32 {
+        self.consumed
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.limit.saturating_sub(self.consumed)
+    }
+}
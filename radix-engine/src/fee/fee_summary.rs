@@ -0,0 +1,14 @@
+use scrypto::math::Decimal;
+
+/// Summarizes the cost units consumed by a transaction and the fee paid for them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeSummary {
+    /// The maximum number of cost units the transaction was allowed to consume.
+    pub cost_unit_limit: u32,
+    /// The number of cost units consumed.
+    pub cost_unit_consumed: u32,
+    /// The price of a cost unit, in XRD.
+    pub cost_unit_price: Decimal,
+    /// The XRD collected by the system component.
+    pub fee_paid: Decimal,
+}
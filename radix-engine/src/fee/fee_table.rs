@@ -0,0 +1,90 @@
+use scrypto::math::Decimal;
+
+/// The default maximum number of cost units a transaction can consume.
+pub const DEFAULT_COST_UNIT_LIMIT: u32 = 10_000_000;
+
+/// The default price of a cost unit, in XRD.
+///
+/// Fees are not charged by default, as not every blueprint is able to lock a fee yet.
+pub const DEFAULT_COST_UNIT_PRICE: Decimal = Decimal(0);
+
+/// Defines how many cost units each metered operation consumes.
+#[derive(Debug, Clone)]
+pub struct FeeTable {
+    tx_base_fee: u32,
+    wasm_instantiation_per_byte: u32,
+    wasm_instruction: u32,
+    wasm_memory_grow_per_page: u32,
+    invoke_snode: u32,
+    engine_call: u32,
+    engine_call_per_byte: u32,
+    substate_read_per_byte: u32,
+    substate_write_per_byte: u32,
+}
+
+impl FeeTable {
+    pub fn new() -> Self {
+        Self {
+            tx_base_fee: 10_000,
+            wasm_instantiation_per_byte: 1,
+            wasm_instruction: 1,
+            wasm_memory_grow_per_page: 1_000,
+            invoke_snode: 1_000,
+            engine_call: 100,
+            engine_call_per_byte: 1,
+            substate_read_per_byte: 1,
+            substate_write_per_byte: 10,
+        }
+    }
+
+    /// The fixed cost of executing a transaction.
+    pub fn tx_base_fee(&self) -> u32 {
+        self.tx_base_fee
+    }
+
+    /// The cost of instantiating a WASM module, per byte of code.
+    pub fn wasm_instantiation_per_byte(&self) -> u32 {
+        self.wasm_instantiation_per_byte
+    }
+
+    /// The cost of executing a single WASM instruction.
+    pub fn wasm_instruction(&self) -> u32 {
+        self.wasm_instruction
+    }
+
+    /// The cost of growing WASM linear memory, per page.
+    pub fn wasm_memory_grow_per_page(&self) -> u32 {
+        self.wasm_memory_grow_per_page
+    }
+
+    /// The cost of invoking an SNode, regardless of who the caller is.
+    pub fn invoke_snode(&self) -> u32 {
+        self.invoke_snode
+    }
+
+    /// The fixed cost of a call from WASM into the engine.
+    pub fn engine_call(&self) -> u32 {
+        self.engine_call
+    }
+
+    /// The cost of a call from WASM into the engine, per byte of input and output.
+    pub fn engine_call_per_byte(&self) -> u32 {
+        self.engine_call_per_byte
+    }
+
+    /// The cost of reading a substate from the substate store, per byte.
+    pub fn substate_read_per_byte(&self) -> u32 {
+        self.substate_read_per_byte
+    }
+
+    /// The cost of writing a substate into the substate store, per byte.
+    pub fn substate_write_per_byte(&self) -> u32 {
+        self.substate_write_per_byte
+    }
+}
+
+impl Default for FeeTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,7 @@
+mod cost_unit_counter;
+mod fee_summary;
+mod fee_table;
+
+pub use cost_unit_counter::{CostUnitCounter, CostUnitCounterError};
+pub use fee_summary::FeeSummary;
+pub use fee_table::{FeeTable, DEFAULT_COST_UNIT_LIMIT, DEFAULT_COST_UNIT_PRICE};
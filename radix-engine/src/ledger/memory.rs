@@ -1,6 +1,9 @@
 use sbor::Encode;
 use scrypto::buffer::scrypto_encode;
-use scrypto::rust::collections::HashMap;
+use scrypto::crypto::Hash;
+use scrypto::engine::types::*;
+use scrypto::rust::collections::{BTreeMap, HashMap, HashSet};
+use scrypto::rust::ops::Bound;
 use scrypto::rust::vec::Vec;
 
 use crate::ledger::traits::Substate;
@@ -10,7 +13,19 @@ use crate::ledger::*;
 #[derive(Debug, Clone)]
 pub struct InMemorySubstateStore {
     substates: HashMap<Vec<u8>, Substate>,
-    child_substates: HashMap<Vec<u8>, Substate>,
+    /// Ordered by key, so that children can be listed like in any key-value database
+    child_substates: BTreeMap<Vec<u8>, Substate>,
+    /// The values of substates as of each state version they were updated in
+    substate_history: BTreeMap<(Vec<u8>, u64), Option<Substate>>,
+    child_substate_history: BTreeMap<(Vec<u8>, u64), Option<Substate>>,
+    state_version: u64,
+    /// The state version of the imported snapshot, before which there is no history
+    base_state_version: u64,
+    /// The hashes of the state tree nodes as of each state version they were updated in
+    state_tree: BTreeMap<(Vec<u8>, u64), Hash>,
+    committed_intents: HashSet<Hash>,
+    transactions: Vec<CommittedTransaction>,
+    transaction_indices: HashMap<Hash, u64>,
     current_epoch: u64,
     nonce: u64,
 }
@@ -19,7 +34,15 @@ impl InMemorySubstateStore {
     pub fn new() -> Self {
         Self {
             substates: HashMap::new(),
-            child_substates: HashMap::new(),
+            child_substates: BTreeMap::new(),
+            substate_history: BTreeMap::new(),
+            child_substate_history: BTreeMap::new(),
+            state_version: 0,
+            base_state_version: 0,
+            state_tree: BTreeMap::new(),
+            committed_intents: HashSet::new(),
+            transactions: Vec::new(),
+            transaction_indices: HashMap::new(),
             current_epoch: 0,
             nonce: 0,
         }
@@ -30,6 +53,91 @@ impl InMemorySubstateStore {
         ledger.bootstrap();
         ledger
     }
+
+    /// Exports the substates, committed intents, epoch, nonce and state version of this ledger.
+    pub fn export_snapshot(&self) -> LedgerSnapshot {
+        let mut substates: Vec<(Vec<u8>, Substate)> = self
+            .substates
+            .iter()
+            .map(|(id, substate)| (id.clone(), substate.clone()))
+            .collect();
+        substates.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut committed_intents: Vec<Hash> = self.committed_intents.iter().cloned().collect();
+        committed_intents.sort_by_key(|intent_hash| intent_hash.0);
+
+        LedgerSnapshot {
+            substates,
+            child_substates: self
+                .child_substates
+                .iter()
+                .map(|(id, substate)| (id.clone(), substate.clone()))
+                .collect(),
+            committed_intents,
+            epoch: self.current_epoch,
+            nonce: self.nonce,
+            state_version: self.state_version,
+        }
+    }
+
+    /// Replaces the content of this ledger with a snapshot, which becomes the start of history.
+    pub fn import_snapshot(&mut self, snapshot: LedgerSnapshot) {
+        *self = Self::new();
+        let updates: Vec<(Vec<u8>, Option<Vec<u8>>)> = snapshot
+            .substates
+            .iter()
+            .chain(snapshot.child_substates.iter())
+            .map(|(id, substate)| (id.clone(), Some(substate.value.clone())))
+            .collect();
+        self.update_state_tree(snapshot.state_version, &updates);
+        for (id, substate) in snapshot.substates {
+            self.substate_history
+                .insert((id.clone(), snapshot.state_version), Some(substate.clone()));
+            self.substates.insert(id, substate);
+        }
+        for (id, substate) in snapshot.child_substates {
+            self.child_substate_history
+                .insert((id.clone(), snapshot.state_version), Some(substate.clone()));
+            self.child_substates.insert(id, substate);
+        }
+        self.committed_intents = snapshot.committed_intents.into_iter().collect();
+        self.current_epoch = snapshot.epoch;
+        self.nonce = snapshot.nonce;
+        self.state_version = snapshot.state_version;
+        self.base_state_version = snapshot.state_version;
+    }
+
+    fn value_at(
+        history: &BTreeMap<(Vec<u8>, u64), Option<Substate>>,
+        id: &[u8],
+        version: u64,
+    ) -> Option<Substate> {
+        history
+            .range((id.to_vec(), 0)..=(id.to_vec(), version))
+            .next_back()
+            .and_then(|(_, substate)| substate.clone())
+    }
+
+    /// Removes the history after the given version, and returns the value of each reverted
+    /// substate as of that version.
+    fn rollback_history(
+        history: &mut BTreeMap<(Vec<u8>, u64), Option<Substate>>,
+        version: u64,
+    ) -> Vec<(Vec<u8>, Option<Substate>)> {
+        let keys: Vec<(Vec<u8>, u64)> = history
+            .keys()
+            .filter(|(_, v)| *v > version)
+            .cloned()
+            .collect();
+        for key in &keys {
+            history.remove(key);
+        }
+        keys.into_iter()
+            .map(|(id, _)| {
+                let substate = Self::value_at(history, &id, version);
+                (id, substate)
+            })
+            .collect()
+    }
 }
 
 impl Default for InMemorySubstateStore {
@@ -38,13 +146,56 @@ impl Default for InMemorySubstateStore {
     }
 }
 
+impl TransactionStore for InMemorySubstateStore {
+    fn get_transaction_count(&self) -> u64 {
+        self.transactions.len() as u64
+    }
+
+    fn get_transaction_by_index(&self, index: u64) -> Option<CommittedTransaction> {
+        self.transactions.get(index as usize).cloned()
+    }
+
+    fn get_transaction_by_hash(&self, hash: &Hash) -> Option<CommittedTransaction> {
+        self.transaction_indices
+            .get(hash)
+            .and_then(|index| self.get_transaction_by_index(*index))
+    }
+
+    fn put_transaction(&mut self, transaction: CommittedTransaction) {
+        self.transaction_indices
+            .insert(transaction.hash, transaction.index);
+        self.transactions.push(transaction);
+    }
+}
+
+impl QueryableSubstateStore for InMemorySubstateStore {
+    fn get_lazy_map_entries(
+        &self,
+        component_address: ComponentAddress,
+        lazy_map_id: &LazyMapId,
+    ) -> HashMap<Vec<u8>, Vec<u8>> {
+        self.get_child_substates(
+            &component_address,
+            &scrypto_encode(lazy_map_id),
+            None,
+            usize::MAX,
+        )
+        .into_iter()
+        .map(|(key, substate)| (key, substate.value))
+        .collect()
+    }
+}
+
 impl SubstateStore for InMemorySubstateStore {
     fn get_substate<T: Encode>(&self, address: &T) -> Option<Substate> {
         self.substates.get(&scrypto_encode(address)).cloned()
     }
 
     fn put_substate<T: Encode>(&mut self, address: &T, substate: Substate) {
-        self.substates.insert(scrypto_encode(address), substate);
+        let id = scrypto_encode(address);
+        self.substate_history
+            .insert((id.clone(), self.state_version + 1), Some(substate.clone()));
+        self.substates.insert(id, substate);
     }
 
     fn get_child_substate<T: Encode>(&self, address: &T, key: &[u8]) -> Option<Substate> {
@@ -56,9 +207,128 @@ impl SubstateStore for InMemorySubstateStore {
     fn put_child_substate<T: Encode>(&mut self, address: &T, key: &[u8], substate: Substate) {
         let mut id = scrypto_encode(address);
         id.extend(key.to_vec());
+        self.child_substate_history
+            .insert((id.clone(), self.state_version + 1), Some(substate.clone()));
         self.child_substates.insert(id, substate);
     }
 
+    fn delete_child_substate<T: Encode>(&mut self, address: &T, key: &[u8]) {
+        let mut id = scrypto_encode(address);
+        id.extend(key.to_vec());
+        self.child_substate_history
+            .insert((id.clone(), self.state_version + 1), None);
+        self.child_substates.remove(&id);
+    }
+
+    fn get_child_substates<T: Encode>(
+        &self,
+        address: &T,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, Substate)> {
+        let mut id = scrypto_encode(address);
+        id.extend(prefix.to_vec());
+        let start = match after {
+            Some(after) => {
+                let mut start = id.clone();
+                start.extend(after.to_vec());
+                Bound::Excluded(start)
+            }
+            None => Bound::Included(id.clone()),
+        };
+
+        self.child_substates
+            .range((start, Bound::Unbounded))
+            .take_while(|(key, _)| key.starts_with(&id))
+            .take(limit)
+            .map(|(key, substate)| (key[id.len()..].to_vec(), substate.clone()))
+            .collect()
+    }
+
+    fn is_intent_committed(&self, intent_hash: &Hash) -> bool {
+        self.committed_intents.contains(intent_hash)
+    }
+
+    fn put_committed_intent(&mut self, intent_hash: Hash) {
+        self.committed_intents.insert(intent_hash);
+    }
+
+    fn get_state_version(&self) -> u64 {
+        self.state_version
+    }
+
+    fn increase_state_version(&mut self) {
+        self.state_version += 1;
+    }
+
+    fn get_substate_at<T: Encode>(&self, address: &T, version: u64) -> Option<Substate> {
+        Self::value_at(&self.substate_history, &scrypto_encode(address), version)
+    }
+
+    fn get_child_substate_at<T: Encode>(
+        &self,
+        address: &T,
+        key: &[u8],
+        version: u64,
+    ) -> Option<Substate> {
+        let mut id = scrypto_encode(address);
+        id.extend(key.to_vec());
+        Self::value_at(&self.child_substate_history, &id, version)
+    }
+
+    fn get_base_state_version(&self) -> u64 {
+        self.base_state_version
+    }
+
+    fn rollback_to(&mut self, version: u64) {
+        assert!(
+            version >= self.base_state_version,
+            "Can't roll back to before the base state version"
+        );
+        if version >= self.state_version {
+            return;
+        }
+
+        for (id, substate) in Self::rollback_history(&mut self.substate_history, version) {
+            match substate {
+                Some(substate) => self.substates.insert(id, substate),
+                None => self.substates.remove(&id),
+            };
+        }
+        for (id, substate) in Self::rollback_history(&mut self.child_substate_history, version) {
+            match substate {
+                Some(substate) => self.child_substates.insert(id, substate),
+                None => self.child_substates.remove(&id),
+            };
+        }
+
+        self.state_tree.retain(|(_, v), _| *v <= version);
+
+        while let Some(transaction) = self.transactions.last() {
+            if transaction.state_version <= version {
+                break;
+            }
+            self.committed_intents.remove(&transaction.hash);
+            self.transaction_indices.remove(&transaction.hash);
+            self.transactions.pop();
+        }
+        self.state_version = version;
+    }
+
+    fn get_state_tree_node(&self, key: &StateTreeNodeKey, version: u64) -> Option<Hash> {
+        let id = scrypto_encode(key);
+        self.state_tree
+            .range((id.clone(), 0)..=(id, version))
+            .next_back()
+            .map(|(_, node)| *node)
+    }
+
+    fn put_state_tree_node(&mut self, key: StateTreeNodeKey, version: u64, node: Hash) {
+        self.state_tree
+            .insert((scrypto_encode(&key), version), node);
+    }
+
     fn get_epoch(&self) -> u64 {
         self.current_epoch
     }
@@ -1,8 +1,16 @@
 mod memory;
+mod state_tree;
 mod traits;
 
 pub use memory::InMemorySubstateStore;
+pub use state_tree::StateTreeNodeKey;
+pub use state_tree::SubstateProof;
+pub use traits::CommittedTransaction;
+pub use traits::LedgerSnapshot;
+pub use traits::NonFungibleEntry;
 pub use traits::QueryableSubstateStore;
 pub use traits::Substate;
 pub use traits::SubstateIdGenerator;
 pub use traits::SubstateStore;
+pub use traits::TransactionStore;
+pub(crate) use traits::XRD_VAULT_ID;
@@ -0,0 +1,235 @@
+use sbor::*;
+use scrypto::crypto::{hash, Hash};
+use scrypto::rust::collections::HashMap;
+use scrypto::rust::vec;
+use scrypto::rust::vec::Vec;
+
+use crate::ledger::SubstateStore;
+
+/// The depth of the state tree, which has a leaf for every possible hash of a substate ID.
+pub const STATE_TREE_DEPTH: u16 = 256;
+
+/// The position of a node in the state tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, TypeId, Encode, Decode)]
+pub struct StateTreeNodeKey {
+    /// The number of edges between the node and the root
+    pub depth: u16,
+    /// The path from the root, as the leading `depth` bits of the hash, with all others zeroed
+    pub path: Hash,
+}
+
+impl StateTreeNodeKey {
+    pub fn new(depth: u16, path: &Hash) -> Self {
+        let mut masked = [0u8; 32];
+        for i in 0..depth as usize {
+            masked[i / 8] |= path.0[i / 8] & (0x80 >> (i % 8));
+        }
+        Self {
+            depth,
+            path: Hash(masked),
+        }
+    }
+
+    /// Returns the key of the other child of this node's parent.
+    pub fn sibling(&self) -> Self {
+        let i = self.depth as usize - 1;
+        let mut path = self.path;
+        path.0[i / 8] ^= 0x80 >> (i % 8);
+        Self {
+            depth: self.depth,
+            path,
+        }
+    }
+}
+
+/// A proof of the value of a substate, or of its absence, against a state root.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct SubstateProof {
+    /// The siblings of the nodes on the path from the leaf to the root, with their depth,
+    /// leaving out the roots of empty subtrees
+    pub siblings: Vec<(u16, Hash)>,
+}
+
+impl SubstateProof {
+    /// Checks that the substate with the given ID has the given SBOR-encoded value, or does
+    /// not exist if `None`, in the state with the given root.
+    pub fn verify(&self, state_root: &Hash, id: &[u8], value: Option<&[u8]>) -> bool {
+        let empty_hashes = empty_node_hashes();
+        let path = hash(id);
+        let mut node = leaf_hash(&path, value);
+        let mut siblings = self.siblings.iter().peekable();
+        for depth in (1..=STATE_TREE_DEPTH).rev() {
+            let sibling = match siblings.peek() {
+                Some((sibling_depth, sibling)) if *sibling_depth == depth => {
+                    siblings.next();
+                    *sibling
+                }
+                _ => empty_hashes[depth as usize],
+            };
+            node = parent_hash(&path, depth, node, sibling);
+        }
+        siblings.next().is_none() && node == *state_root
+    }
+}
+
+/// Returns the root hash of the state tree as of the given state version.
+pub fn get_state_root<S: SubstateStore + ?Sized>(store: &S, version: u64) -> Hash {
+    store
+        .get_state_tree_node(&StateTreeNodeKey::new(0, &Hash([0u8; 32])), version)
+        .unwrap_or_else(|| empty_node_hashes()[0])
+}
+
+/// Returns a proof of the value of a substate as of the given state version.
+pub fn prove_substate<S: SubstateStore + ?Sized>(
+    store: &S,
+    id: &[u8],
+    version: u64,
+) -> SubstateProof {
+    let empty_hashes = empty_node_hashes();
+    let path = hash(id);
+    let mut siblings = Vec::new();
+    for depth in (1..=STATE_TREE_DEPTH).rev() {
+        let key = StateTreeNodeKey::new(depth, &path).sibling();
+        match store.get_state_tree_node(&key, version) {
+            Some(sibling) if sibling != empty_hashes[depth as usize] => {
+                siblings.push((depth, sibling))
+            }
+            _ => {}
+        }
+    }
+    SubstateProof { siblings }
+}
+
+/// Updates the state tree with the new value of each substate, by ID, as of the given state
+/// version.
+///
+/// The new nodes are only written once all of them have been computed, so that the store is
+/// never asked to read back its own writes.
+///
+/// This is a prototype: paths through empty subtrees are not compressed, so each updated
+/// substate costs a node at every depth of the tree, i.e. `STATE_TREE_DEPTH + 1` writes.
+pub fn update_state_tree<S: SubstateStore + ?Sized>(
+    store: &mut S,
+    version: u64,
+    updates: &[(Vec<u8>, Option<Vec<u8>>)],
+) {
+    // TODO: compress paths, keeping only the nodes where the paths of substates diverge
+    let empty_hashes = empty_node_hashes();
+    let mut nodes = HashMap::new();
+    for (id, value) in updates {
+        let path = hash(id);
+        let mut node = leaf_hash(&path, value.as_deref());
+        nodes.insert(StateTreeNodeKey::new(STATE_TREE_DEPTH, &path), node);
+        for depth in (1..=STATE_TREE_DEPTH).rev() {
+            let key = StateTreeNodeKey::new(depth, &path).sibling();
+            let sibling = nodes
+                .get(&key)
+                .copied()
+                .or_else(|| store.get_state_tree_node(&key, version))
+                .unwrap_or(empty_hashes[depth as usize]);
+            node = parent_hash(&path, depth, node, sibling);
+            nodes.insert(StateTreeNodeKey::new(depth - 1, &path), node);
+        }
+    }
+    for (key, node) in nodes {
+        store.put_state_tree_node(key, version, node);
+    }
+}
+
+/// Returns the hash of the parent of the node at the given depth on a path, given the node and
+/// its sibling.
+fn parent_hash(path: &Hash, depth: u16, node: Hash, sibling: Hash) -> Hash {
+    let i = depth as usize - 1;
+    let (left, right) = if path.0[i / 8] & (0x80 >> (i % 8)) == 0 {
+        (node, sibling)
+    } else {
+        (sibling, node)
+    };
+    let mut data = vec![1u8];
+    data.extend(left.0);
+    data.extend(right.0);
+    hash(data)
+}
+
+fn leaf_hash(path: &Hash, value: Option<&[u8]>) -> Hash {
+    match value {
+        Some(value) => {
+            let mut data = vec![0u8];
+            data.extend(path.0);
+            data.extend(canonical_value_hash(value).0);
+            hash(data)
+        }
+        None => Hash([0u8; 32]),
+    }
+}
+
+/// Returns the hash of the root of an empty subtree, by depth of the root.
+fn empty_node_hashes() -> Vec<Hash> {
+    let mut hashes = vec![Hash([0u8; 32]); STATE_TREE_DEPTH as usize + 1];
+    for depth in (0..STATE_TREE_DEPTH as usize).rev() {
+        let mut data = vec![1u8];
+        data.extend(hashes[depth + 1].0);
+        data.extend(hashes[depth + 1].0);
+        hashes[depth] = hash(data);
+    }
+    hashes
+}
+
+/// Hashes an SBOR-encoded value, with the entries of hash sets and maps sorted, so that equal
+/// values have the same hash regardless of the iteration order they were encoded in.
+fn canonical_value_hash(value: &[u8]) -> Hash {
+    match decode_any(value) {
+        Ok(mut value) => {
+            canonicalize(&mut value);
+            hash(encode_value(&value))
+        }
+        Err(_) => hash(value),
+    }
+}
+
+fn canonicalize(value: &mut Value) {
+    match value {
+        Value::Struct { fields } | Value::Enum { fields, .. } => {
+            fields.iter_mut().for_each(canonicalize);
+        }
+        Value::Option { value } => {
+            if let Some(value) = &mut **value {
+                canonicalize(value);
+            }
+        }
+        Value::Result { value } => match &mut **value {
+            Ok(value) | Err(value) => canonicalize(value),
+        },
+        Value::Array { elements, .. }
+        | Value::Tuple { elements }
+        | Value::Vec { elements, .. }
+        | Value::TreeSet { elements, .. }
+        | Value::TreeMap { elements, .. } => {
+            elements.iter_mut().for_each(canonicalize);
+        }
+        Value::HashSet { elements, .. } => {
+            elements.iter_mut().for_each(canonicalize);
+            elements.sort_by_cached_key(encode_value);
+        }
+        Value::HashMap { elements, .. } => {
+            elements.iter_mut().for_each(canonicalize);
+            let mut entries: Vec<(Vec<u8>, Value, Value)> = elements
+                .chunks(2)
+                .map(|pair| (encode_value(&pair[0]), pair[0].clone(), pair[1].clone()))
+                .collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            *elements = entries
+                .into_iter()
+                .flat_map(|(_, key, value)| [key, value])
+                .collect();
+        }
+        _ => {}
+    }
+}
+
+fn encode_value(value: &Value) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut enc = Encoder::with_type(&mut bytes);
+    encode_any(None, value, &mut enc);
+    bytes
+}
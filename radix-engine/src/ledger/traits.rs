@@ -5,12 +5,16 @@ use scrypto::constants::*;
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
 use scrypto::prelude::LOCKED;
+use scrypto::resource::NonFungibleData;
 use scrypto::resource::ResourceMethod::Withdraw;
 use scrypto::rust::borrow::ToOwned;
 use scrypto::rust::collections::*;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
+use scrypto::types::ScryptoType;
+use scrypto::values::ScryptoValue;
 
+use crate::ledger::state_tree::{self, StateTreeNodeKey, SubstateProof};
 use crate::model::*;
 
 const XRD_SYMBOL: &str = "XRD";
@@ -18,7 +22,7 @@ const XRD_NAME: &str = "Radix";
 const XRD_DESCRIPTION: &str = "The Radix Public Network's native token, used to pay the network's required transaction fees and to secure the network through staking to its validator nodes.";
 const XRD_URL: &str = "https://tokens.radixdlt.com";
 const XRD_MAX_SUPPLY: i128 = 24_000_000_000i128;
-const XRD_VAULT_ID: VaultId = (Hash([0u8; 32]), 0);
+pub(crate) const XRD_VAULT_ID: VaultId = (Hash([0u8; 32]), 0);
 const XRD_VAULT: scrypto::resource::Vault = scrypto::resource::Vault(XRD_VAULT_ID);
 
 const SYSTEM_COMPONENT_NAME: &str = "System";
@@ -34,6 +38,106 @@ pub trait QueryableSubstateStore {
         component_address: ComponentAddress,
         lazy_map_id: &LazyMapId,
     ) -> HashMap<Vec<u8>, Vec<u8>>;
+
+    /// Returns up to `limit` non-fungibles of a resource, in ascending order of their encoded
+    /// ids, skipping all ids up to and including `after`.
+    ///
+    /// Burnt non-fungibles are left out.
+    fn get_non_fungibles(
+        &self,
+        resource_address: ResourceAddress,
+        after: Option<&NonFungibleId>,
+        limit: usize,
+    ) -> Vec<NonFungibleEntry>
+    where
+        Self: SubstateStore + Sized,
+    {
+        // Child substate keys are encoded ids, which all start with the same type id
+        let prefix = [ScryptoType::NonFungibleId.id()];
+        let mut cursor = after.map(|id| scrypto_encode(id)[prefix.len()..].to_vec());
+        let mut entries = Vec::new();
+        while entries.len() < limit {
+            let remaining = limit - entries.len();
+            let page =
+                self.get_child_substates(&resource_address, &prefix, cursor.as_deref(), remaining);
+            let exhausted = page.len() < remaining;
+            for (key, substate) in page {
+                let non_fungible: Option<NonFungible> = scrypto_decode(&substate.value).unwrap();
+                if let Some(non_fungible) = non_fungible {
+                    let mut encoded_id = prefix.to_vec();
+                    encoded_id.extend(&key);
+                    entries.push(NonFungibleEntry {
+                        id: scrypto_decode(&encoded_id).unwrap(),
+                        immutable_data: ScryptoValue::from_slice(&non_fungible.immutable_data())
+                            .unwrap(),
+                        mutable_data: ScryptoValue::from_slice(&non_fungible.mutable_data())
+                            .unwrap(),
+                    });
+                }
+                cursor = Some(key);
+            }
+            if exhausted {
+                break;
+            }
+        }
+        entries
+    }
+}
+
+/// A non-fungible listed by `QueryableSubstateStore::get_non_fungibles`, with its data decoded.
+#[derive(Debug, Clone)]
+pub struct NonFungibleEntry {
+    pub id: NonFungibleId,
+    pub immutable_data: ScryptoValue,
+    pub mutable_data: ScryptoValue,
+}
+
+impl NonFungibleEntry {
+    /// Decodes the data as the `NonFungibleData` type of the blueprint which minted it.
+    pub fn data<T: NonFungibleData>(&self) -> Result<T, DecodeError> {
+        T::decode(&self.immutable_data.raw, &self.mutable_data.raw)
+    }
+}
+
+/// A transaction committed to the ledger, with a summary of its receipt.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CommittedTransaction {
+    /// The intent hash of the transaction
+    pub hash: Hash,
+    /// The position of the transaction in the ledger history
+    pub index: u64,
+    /// The state version after committing the transaction
+    pub state_version: u64,
+    /// The SBOR-encoded signed transaction
+    pub transaction: Vec<u8>,
+    pub receipt: ReceiptSummary,
+}
+
+/// A store of the transactions committed to a ledger.
+pub trait TransactionStore {
+    /// Returns the number of committed transactions.
+    fn get_transaction_count(&self) -> u64;
+
+    fn get_transaction_by_index(&self, index: u64) -> Option<CommittedTransaction>;
+
+    fn get_transaction_by_hash(&self, hash: &Hash) -> Option<CommittedTransaction>;
+
+    /// Appends a transaction to the history, at the index it carries.
+    fn put_transaction(&mut self, transaction: CommittedTransaction);
+}
+
+/// A snapshot of the state of a ledger, which can be imported into any ledger.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct LedgerSnapshot {
+    /// The substates, keyed by encoded address
+    pub substates: Vec<(Vec<u8>, Substate)>,
+    /// The child substates, keyed by encoded parent address followed by child key
+    pub child_substates: Vec<(Vec<u8>, Substate)>,
+    /// The intent hashes of the committed transactions, so that they can't be replayed
+    pub committed_intents: Vec<Hash>,
+    pub epoch: u64,
+    pub nonce: u64,
+    pub state_version: u64,
 }
 
 #[derive(Clone, Debug, Encode, Decode, TypeId)]
@@ -61,12 +165,25 @@ impl SubstateIdGenerator {
 }
 
 /// A ledger stores all transactions and substates.
-pub trait SubstateStore {
+pub trait SubstateStore: TransactionStore {
     fn get_substate<T: Encode>(&self, address: &T) -> Option<Substate>;
     fn put_substate<T: Encode>(&mut self, address: &T, substate: Substate);
 
     fn get_child_substate<T: Encode>(&self, address: &T, key: &[u8]) -> Option<Substate>;
     fn put_child_substate<T: Encode>(&mut self, address: &T, key: &[u8], substate: Substate);
+    fn delete_child_substate<T: Encode>(&mut self, address: &T, key: &[u8]);
+
+    /// Returns up to `limit` child substates whose key starts with `prefix`, in ascending
+    /// order of key bytes, skipping all keys up to and including `prefix` + `after`.
+    ///
+    /// Keys are returned without the prefix.
+    fn get_child_substates<T: Encode>(
+        &self,
+        address: &T,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, Substate)>;
 
     // Temporary Encoded/Decoded interface
     fn get_decoded_substate<A: Encode, T: Decode>(&self, address: &A) -> Option<(T, (Hash, u32))> {
@@ -87,6 +204,14 @@ pub trait SubstateStore {
             },
         );
     }
+    fn get_decoded_substate_at<A: Encode, T: Decode>(
+        &self,
+        address: &A,
+        version: u64,
+    ) -> Option<(T, (Hash, u32))> {
+        self.get_substate_at(address, version)
+            .map(|s| (scrypto_decode(&s.value).unwrap(), s.phys_id))
+    }
     fn get_decoded_child_substate<A: Encode, K: Encode, T: Decode>(
         &self,
         address: &A,
@@ -151,16 +276,25 @@ pub trait SubstateStore {
         if package.is_none() {
             let tx_hash = hash(self.get_and_increase_nonce().to_le_bytes());
             let mut id_gen = SubstateIdGenerator::new(tx_hash);
+            let mut updates = Vec::new();
 
             // System package
             let system_package =
                 Package::new(include_bytes!("../../../assets/system.wasm").to_vec()).unwrap();
             self.put_encoded_substate(&SYSTEM_PACKAGE, &system_package, id_gen.next());
+            updates.push((
+                scrypto_encode(&SYSTEM_PACKAGE),
+                Some(scrypto_encode(&system_package)),
+            ));
 
             // Account package
             let account_package =
                 Package::new(include_bytes!("../../../assets/account.wasm").to_vec()).unwrap();
             self.put_encoded_substate(&ACCOUNT_PACKAGE, &account_package, id_gen.next());
+            updates.push((
+                scrypto_encode(&ACCOUNT_PACKAGE),
+                Some(scrypto_encode(&account_package)),
+            ));
 
             // Radix token resource address
             let mut metadata = HashMap::new();
@@ -176,9 +310,12 @@ pub trait SubstateStore {
                 ResourceType::Fungible { divisibility: 18 },
                 metadata,
                 resource_auth,
+                None,
+                None,
             )
             .unwrap();
             self.put_encoded_substate(&RADIX_TOKEN, &xrd, id_gen.next());
+            updates.push((scrypto_encode(&RADIX_TOKEN), Some(scrypto_encode(&xrd))));
             let minted_xrd = xrd
                 .mint_fungible(XRD_MAX_SUPPLY.into(), RADIX_TOKEN.clone())
                 .unwrap();
@@ -189,9 +326,28 @@ pub trait SubstateStore {
                 ResourceType::NonFungible,
                 HashMap::new(),
                 ecdsa_resource_auth,
+                None,
+                None,
             )
             .unwrap();
             self.put_encoded_substate(&ECDSA_TOKEN, &ecdsa_token, id_gen.next());
+            updates.push((scrypto_encode(&ECDSA_TOKEN), Some(scrypto_encode(&ecdsa_token))));
+
+            let mut ed25519_resource_auth = HashMap::new();
+            ed25519_resource_auth.insert(Withdraw, (rule!(allow_all), LOCKED));
+            let ed25519_token = ResourceManager::new(
+                ResourceType::NonFungible,
+                HashMap::new(),
+                ed25519_resource_auth,
+                None,
+                None,
+            )
+            .unwrap();
+            self.put_encoded_substate(&ED25519_TOKEN, &ed25519_token, id_gen.next());
+            updates.push((
+                scrypto_encode(&ED25519_TOKEN),
+                Some(scrypto_encode(&ed25519_token)),
+            ));
 
             // Instantiate system component
             let system_vault = Vault::new(minted_xrd);
@@ -201,6 +357,9 @@ pub trait SubstateStore {
                 &system_vault,
                 id_gen.next(),
             );
+            let mut system_vault_id = scrypto_encode(&SYSTEM_COMPONENT);
+            system_vault_id.extend(scrypto_encode(&XRD_VAULT_ID));
+            updates.push((system_vault_id, Some(scrypto_encode(&system_vault))));
 
             let system_component = Component::new(
                 SYSTEM_PACKAGE,
@@ -209,9 +368,89 @@ pub trait SubstateStore {
                 scrypto_encode(&SystemComponentState { xrd: XRD_VAULT }),
             );
             self.put_encoded_substate(&SYSTEM_COMPONENT, &system_component, id_gen.next());
+            updates.push((
+                scrypto_encode(&SYSTEM_COMPONENT),
+                Some(scrypto_encode(&system_component)),
+            ));
+
+            self.increase_state_version();
+            let version = self.get_state_version();
+            self.update_state_tree(version, &updates);
         }
     }
 
+    /// Returns the ID of the network this ledger belongs to.
+    fn get_network_id(&self) -> u32 {
+        LOCAL_NETWORK_ID
+    }
+
+    /// Returns whether a transaction with the given intent hash has been committed.
+    fn is_intent_committed(&self, intent_hash: &Hash) -> bool;
+
+    /// Records that a transaction with the given intent hash has been committed.
+    fn put_committed_intent(&mut self, intent_hash: Hash);
+
+    /// Starts a commit, so that all writes until `end_commit` can be applied atomically.
+    ///
+    /// Writes made during a commit need not be visible to reads before it ends.
+    fn begin_commit(&mut self) {}
+
+    /// Ends a commit, applying all writes since `begin_commit`.
+    fn end_commit(&mut self) {}
+
+    /// Returns the current state version, which is zero for an empty ledger and is bumped on
+    /// bootstrap and on each commit.
+    fn get_state_version(&self) -> u64;
+
+    /// Completes the next state version, to which all updates since the last one belong.
+    fn increase_state_version(&mut self);
+
+    /// Returns the value of a substate as of the given state version.
+    fn get_substate_at<T: Encode>(&self, address: &T, version: u64) -> Option<Substate>;
+
+    /// Returns the value of a child substate as of the given state version.
+    fn get_child_substate_at<T: Encode>(
+        &self,
+        address: &T,
+        key: &[u8],
+        version: u64,
+    ) -> Option<Substate>;
+
+    /// Returns the oldest state version that can be rolled back to, which is the state version
+    /// of the imported snapshot, if any, and zero otherwise.
+    fn get_base_state_version(&self) -> u64;
+
+    /// Reverts all updates made after the given state version, including the transactions
+    /// committed since.
+    ///
+    /// # Panics
+    /// Panics if the version is below the base state version, as no history is kept before it.
+    fn rollback_to(&mut self, version: u64);
+
+    /// Returns the hash of a node of the state tree as of the given state version, or `None` if
+    /// it has never been set.
+    fn get_state_tree_node(&self, key: &StateTreeNodeKey, version: u64) -> Option<Hash>;
+
+    /// Sets the hash of a node of the state tree, as of the given state version.
+    fn put_state_tree_node(&mut self, key: StateTreeNodeKey, version: u64, node: Hash);
+
+    /// Returns the state root, which commits to the value of every substate, as of the given
+    /// state version.
+    fn get_state_root(&self, version: u64) -> Hash {
+        state_tree::get_state_root(self, version)
+    }
+
+    /// Returns a proof of the value of the substate with the given ID, as of the given state
+    /// version, which can be verified against the state root of that version.
+    fn get_substate_proof(&self, id: &[u8], version: u64) -> SubstateProof {
+        state_tree::prove_substate(self, id, version)
+    }
+
+    /// Updates the state tree with the new value of each substate written in a state version.
+    fn update_state_tree(&mut self, version: u64, updates: &[(Vec<u8>, Option<Vec<u8>>)]) {
+        state_tree::update_state_tree(self, version, updates)
+    }
+
     fn get_epoch(&self) -> u64;
 
     fn set_epoch(&mut self, epoch: u64);
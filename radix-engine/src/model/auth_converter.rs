@@ -237,3 +237,45 @@ pub fn convert(schema: &Type, dom: &Value, method_auth: &AccessRule) -> MethodAu
         AccessRule::DenyAll => MethodAuthorization::DenyAll,
     }
 }
+
+/// Returns whether an access rule reads any of its values through a schema path.
+pub fn has_schema_path(method_auth: &AccessRule) -> bool {
+    match method_auth {
+        AccessRule::Protected(auth_rule) => auth_rule_has_schema_path(auth_rule),
+        AccessRule::AllowAll | AccessRule::DenyAll => false,
+    }
+}
+
+fn auth_rule_has_schema_path(auth_rule: &AccessRuleNode) -> bool {
+    match auth_rule {
+        AccessRuleNode::ProofRule(proof_rule) => proof_rule_has_schema_path(proof_rule),
+        AccessRuleNode::AnyOf(rules) | AccessRuleNode::AllOf(rules) => {
+            rules.iter().any(auth_rule_has_schema_path)
+        }
+    }
+}
+
+fn proof_rule_has_schema_path(proof_rule: &ProofRule) -> bool {
+    match proof_rule {
+        ProofRule::Require(resource) => matches!(resource, SoftResourceOrNonFungible::Dynamic(_)),
+        ProofRule::AmountOf(amount, resource) => {
+            matches!(amount, SoftDecimal::Dynamic(_))
+                || matches!(resource, SoftResource::Dynamic(_))
+        }
+        ProofRule::AllOf(resources) | ProofRule::AnyOf(resources) => {
+            resource_list_has_schema_path(resources)
+        }
+        ProofRule::CountOf(count, resources) => {
+            matches!(count, SoftCount::Dynamic(_)) || resource_list_has_schema_path(resources)
+        }
+    }
+}
+
+fn resource_list_has_schema_path(list: &SoftResourceOrNonFungibleList) -> bool {
+    match list {
+        SoftResourceOrNonFungibleList::Static(resources) => resources
+            .iter()
+            .any(|resource| matches!(resource, SoftResourceOrNonFungible::Dynamic(_))),
+        SoftResourceOrNonFungibleList::Dynamic(_) => true,
+    }
+}
@@ -12,7 +12,8 @@ use scrypto::rust::vec::Vec;
 use scrypto::values::ScryptoValue;
 
 use crate::model::{
-    Proof, ProofError, ResourceContainer, ResourceContainerError, ResourceContainerId,
+    AmountOrIds, Proof, ProofError, ResourceContainer, ResourceContainerError,
+    ResourceContainerId, ResourceMovement,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -133,6 +134,10 @@ impl Bucket {
         self.borrow_container().total_ids()
     }
 
+    pub fn total_amount_or_ids(&self) -> AmountOrIds {
+        self.borrow_container().total_amount_or_ids()
+    }
+
     pub fn is_locked(&self) -> bool {
         self.borrow_container().is_locked()
     }
@@ -229,6 +234,11 @@ impl Bucket {
             .borrow_global_mut_resource_manager(resource_address)
             .unwrap();
         resource_manager.burn(self.total_amount());
+        system_api.record_resource_movement(
+            ResourceMovement::Burn,
+            resource_address,
+            self.total_amount_or_ids(),
+        );
         if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
             for id in self.total_ids().unwrap() {
                 let non_fungible_address = NonFungibleAddress::new(resource_address, id);
@@ -0,0 +1,151 @@
+use sbor::any::Value;
+use sbor::describe::Fields;
+use sbor::*;
+use scrypto::core::ScryptoActorInfo;
+use scrypto::engine::types::*;
+use scrypto::rust::string::String;
+use scrypto::rust::vec::Vec;
+use scrypto::types::ScryptoType;
+
+use crate::model::AmountOrIds;
+
+/// An event emitted by a blueprint.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct Event {
+    /// The blueprint or component which emitted the event
+    pub actor: ScryptoActorInfo,
+    /// The event name, as declared in the blueprint ABI
+    pub name: String,
+    /// The SBOR-encoded event data
+    pub data: Vec<u8>,
+}
+
+/// Checks whether event data, or any other SBOR value, is of the type described by a schema.
+///
+/// Generic parameters of custom types are not checked.
+pub fn matches_schema(value: &Value, schema: &Type) -> bool {
+    match (value, schema) {
+        (Value::Unit, Type::Unit)
+        | (Value::Bool { .. }, Type::Bool)
+        | (Value::I8 { .. }, Type::I8)
+        | (Value::I16 { .. }, Type::I16)
+        | (Value::I32 { .. }, Type::I32)
+        | (Value::I64 { .. }, Type::I64)
+        | (Value::I128 { .. }, Type::I128)
+        | (Value::U8 { .. }, Type::U8)
+        | (Value::U16 { .. }, Type::U16)
+        | (Value::U32 { .. }, Type::U32)
+        | (Value::U64 { .. }, Type::U64)
+        | (Value::U128 { .. }, Type::U128)
+        | (Value::String { .. }, Type::String) => true,
+        (Value::Struct { fields }, Type::Struct { fields: schema, .. }) => {
+            matches_fields(fields, schema)
+        }
+        (Value::Enum { name, fields }, Type::Enum { variants, .. }) => variants
+            .iter()
+            .find(|variant| &variant.name == name)
+            .map(|variant| matches_fields(fields, &variant.fields))
+            .unwrap_or(false),
+        (Value::Option { value }, Type::Option { value: schema }) => match value.as_ref() {
+            Some(value) => matches_schema(value, schema),
+            None => true,
+        },
+        (Value::Array { elements, .. }, Type::Array { element, length }) => {
+            elements.len() == *length as usize && matches_all(elements, element)
+        }
+        (Value::Tuple { elements }, Type::Tuple { elements: schemas }) => {
+            elements.len() == schemas.len()
+                && elements
+                    .iter()
+                    .zip(schemas)
+                    .all(|(value, schema)| matches_schema(value, schema))
+        }
+        (Value::Result { value }, Type::Result { okay, error }) => match value.as_ref() {
+            Ok(value) => matches_schema(value, okay),
+            Err(value) => matches_schema(value, error),
+        },
+        (Value::Vec { elements, .. }, Type::Vec { element })
+        | (Value::TreeSet { elements, .. }, Type::TreeSet { element })
+        | (Value::HashSet { elements, .. }, Type::HashSet { element }) => {
+            matches_all(elements, element)
+        }
+        // Map entries are flattened into alternating keys and values
+        (Value::TreeMap { elements, .. }, Type::TreeMap { key, value })
+        | (Value::HashMap { elements, .. }, Type::HashMap { key, value }) => {
+            elements.len() % 2 == 0
+                && elements
+                    .chunks(2)
+                    .all(|pair| matches_schema(&pair[0], key) && matches_schema(&pair[1], value))
+        }
+        (Value::Custom { type_id, .. }, Type::Custom { name, .. }) => {
+            ScryptoType::from_id(*type_id)
+                .map(|scrypto_type| &scrypto_type.name() == name)
+                .unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+fn matches_all(values: &[Value], schema: &Type) -> bool {
+    values.iter().all(|value| matches_schema(value, schema))
+}
+
+fn matches_fields(values: &[Value], schema: &Fields) -> bool {
+    match schema {
+        Fields::Named { named } => {
+            values.len() == named.len()
+                && values
+                    .iter()
+                    .zip(named)
+                    .all(|(value, (_, schema))| matches_schema(value, schema))
+        }
+        Fields::Unnamed { unnamed } => {
+            values.len() == unnamed.len()
+                && values
+                    .iter()
+                    .zip(unnamed)
+                    .all(|(value, schema)| matches_schema(value, schema))
+        }
+        Fields::Unit => values.is_empty(),
+    }
+}
+
+/// A resource movement, as observed by the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceMovement {
+    VaultDeposit(VaultId),
+    VaultWithdrawal(VaultId),
+    Mint,
+    Burn,
+}
+
+/// An event emitted by the engine for each resource movement.
+///
+/// The component owning a vault is `None` if the vault could not be found in any component.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub enum ResourceEvent {
+    /// Resource deposited into a vault.
+    VaultDeposit {
+        vault_id: VaultId,
+        component_address: Option<ComponentAddress>,
+        resource_address: ResourceAddress,
+        resource: AmountOrIds,
+    },
+    /// Resource withdrawn from a vault.
+    VaultWithdrawal {
+        vault_id: VaultId,
+        component_address: Option<ComponentAddress>,
+        resource_address: ResourceAddress,
+        resource: AmountOrIds,
+    },
+    /// Resource minted.
+    Mint {
+        resource_address: ResourceAddress,
+        resource: AmountOrIds,
+    },
+    /// Resource burnt.
+    Burn {
+        resource_address: ResourceAddress,
+        resource: AmountOrIds,
+    },
+}
@@ -0,0 +1,35 @@
+use scrypto::core::SNodeRef;
+use scrypto::engine::types::*;
+use scrypto::rust::string::String;
+use scrypto::rust::vec::Vec;
+use scrypto::values::*;
+
+use crate::errors::RuntimeError;
+use crate::model::AmountOrIds;
+
+/// A node in the call tree of a transaction, recorded for each invocation when tracing is on.
+#[derive(Debug, Clone)]
+pub struct ExecutionTrace {
+    /// The invoked node
+    pub snode_ref: SNodeRef,
+    /// The invoked function or method
+    pub function: String,
+    /// The decoded arguments
+    pub args: Vec<ScryptoValue>,
+    /// The buckets passed in as arguments
+    pub buckets_in: Vec<(BucketId, ResourceAddress, AmountOrIds)>,
+    /// The proofs passed in as arguments
+    pub proofs_in: Vec<(ProofId, ResourceAddress, AmountOrIds)>,
+    /// The buckets returned to the caller
+    pub buckets_out: Vec<(BucketId, ResourceAddress, AmountOrIds)>,
+    /// The proofs returned to the caller
+    pub proofs_out: Vec<(ProofId, ResourceAddress, AmountOrIds)>,
+    /// The output, or the error the invocation failed with
+    pub output: Result<ScryptoValue, RuntimeError>,
+    /// The cost units consumed, including by nested invocations
+    pub cost_units: u32,
+    /// The execution time in microseconds, including nested invocations
+    pub execution_time: Option<u128>,
+    /// The nested invocations, in order
+    pub children: Vec<ExecutionTrace>,
+}
@@ -2,6 +2,8 @@ mod auth_converter;
 mod auth_zone;
 mod bucket;
 mod component;
+mod event;
+mod execution_trace;
 mod method_authorization;
 mod non_fungible;
 mod package;
@@ -16,21 +18,24 @@ mod vault;
 mod worktop;
 
 pub use auth_zone::{AuthZone, AuthZoneError};
-pub use auth_converter::convert;
+pub use auth_converter::{convert, has_schema_path};
 pub use bucket::{Bucket, BucketError};
 pub use component::Component;
+pub use event::{matches_schema, Event, ResourceEvent, ResourceMovement};
+pub use execution_trace::ExecutionTrace;
 pub use method_authorization::{
     HardProofRule, HardResourceOrNonFungible, MethodAuthorization, MethodAuthorizationError,
 };
 pub use non_fungible::NonFungible;
 pub use package::{Package, PackageError};
 pub use proof::*;
-pub use receipt::Receipt;
+pub use receipt::{Receipt, ReceiptSummary};
 pub use resource::*;
 pub use resource_manager::{ResourceManager, ResourceManagerError};
 pub use transaction_process::{TransactionProcess};
 pub use transaction::{
-    Instruction, SignedTransaction, Transaction,
+    Instruction, PreviewFlags, SignedTransaction, Transaction, TransactionHeader,
+    LOCAL_NETWORK_ID,
 };
 pub use validated_transaction::{ValidatedTransaction, ValidatedInstruction};
 pub use vault::{Vault, VaultError};
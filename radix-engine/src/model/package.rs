@@ -1,25 +1,43 @@
 use sbor::*;
-use scrypto::abi::{Function, Method};
+use scrypto::abi::{Event, Function, Method, Mutability};
 use scrypto::buffer::scrypto_decode;
+use scrypto::crypto::{hash, Hash};
 use scrypto::rust::collections::HashMap;
 use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 use scrypto::values::ScryptoValue;
+use wasm_instrument::gas_metering::{self, ConstantCostRules};
+use wasm_instrument::inject_stack_limiter;
+use wasm_instrument::parity_wasm;
 use wasmi::{
-    ExternVal, ImportsBuilder, MemoryRef, Module, ModuleInstance, ModuleRef, NopExternals,
-    RuntimeValue,
+    ExternVal, Externals, ImportsBuilder, Module, ModuleInstance, RuntimeArgs, RuntimeValue, Trap,
+    TrapKind,
 };
 
-use crate::engine::{EnvModuleResolver, SystemApi};
+use crate::engine::{
+    EnvModuleResolver, SystemApi, COST_UNIT_FUNCTION_INDEX, COST_UNIT_FUNCTION_NAME,
+    MAX_ABI_COST_UNITS, MAX_STACK_HEIGHT, MAX_WASM_MEMORY_PAGES, STACK_HEIGHT_GLOBAL_NAME,
+};
 use crate::errors::WasmValidationError;
+use crate::fee::FeeTable;
+
+/// The output of a blueprint ABI export, i.e. state schema, functions, methods and events.
+pub type BlueprintAbi = (Type, Vec<Function>, Vec<Method>, Vec<Event>);
 
 /// A collection of blueprints, compiled and published as a single unit.
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct Package {
     code: Vec<u8>,
+    code_hash: Hash,
+    /// Maximum size of the linear memory declared by the instrumented code, in pages
+    max_memory_pages: u32,
     blueprints: HashMap<String, Type>,
+    /// Events declared by each blueprint, with their schemas
+    events: HashMap<String, Vec<Event>>,
+    /// Mutability of the methods declared by each blueprint
+    methods: HashMap<String, HashMap<String, Mutability>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -30,8 +48,40 @@ pub enum PackageError {
     MethodNotFound(String),
 }
 
+/// Meters the ABI exports run while a package is published, and traps on any other host call.
+struct AbiExternals {
+    cost_units_left: u32,
+    /// Whether the exports have run out of cost units
+    exhausted: bool,
+}
+
+impl Externals for AbiExternals {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        match index {
+            COST_UNIT_FUNCTION_INDEX => {
+                let cost_units: u32 = args.nth_checked(0)?;
+                match self.cost_units_left.checked_sub(cost_units) {
+                    Some(cost_units_left) => {
+                        self.cost_units_left = cost_units_left;
+                        Ok(None)
+                    }
+                    None => {
+                        self.exhausted = true;
+                        Err(Trap::new(TrapKind::Unreachable))
+                    }
+                }
+            }
+            _ => Err(Trap::new(TrapKind::Unreachable)),
+        }
+    }
+}
+
 impl Package {
-    /// Validates and creates a package
+    /// Validates, instruments and creates a package
     pub fn new(code: Vec<u8>) -> Result<Self, WasmValidationError> {
         // Parse
         let parsed = Self::parse_module(&code)?;
@@ -55,10 +105,29 @@ impl Package {
         let module = instance.assert_no_start();
 
         // Check memory export
+        if !matches!(module.export_by_name("memory"), Some(ExternVal::Memory(_))) {
+            return Err(WasmValidationError::NoValidMemoryExport);
+        }
+
+        // Instrument
+        let (code, max_memory_pages) = Self::instrument_code(&code)?;
+        let code_hash = hash(&code);
+
+        // The ABI exports are run on the instrumented code, so that they are metered
+        let module = ModuleInstance::new(
+            &Self::parse_module(&code)?,
+            &ImportsBuilder::new().with_resolver("env", &EnvModuleResolver),
+        )
+        .map_err(|_| WasmValidationError::InvalidModule)?
+        .assert_no_start();
         let memory = match module.export_by_name("memory") {
             Some(ExternVal::Memory(mem)) => mem,
             _ => return Err(WasmValidationError::NoValidMemoryExport),
         };
+        let mut externals = AbiExternals {
+            cost_units_left: MAX_ABI_COST_UNITS,
+            exhausted: false,
+        };
 
         // TODO: Currently a hack so that we don't require a package_init function.
         // TODO: Fix this by implement package metadata along with the code during compilation.
@@ -72,81 +141,225 @@ impl Package {
             .collect();
 
         let mut blueprints = HashMap::new();
+        let mut events = HashMap::new();
+        let mut methods = HashMap::new();
 
         for method_name in blueprint_abi_methods {
             let rtn = module
-                .invoke_export(&method_name, &[], &mut NopExternals)
-                .map_err(|e| WasmValidationError::NoPackageInitExport(e.into()))?
+                .invoke_export(&method_name, &[], &mut externals)
+                .map_err(|e| {
+                    if externals.exhausted {
+                        WasmValidationError::AbiCostUnitLimitExceeded
+                    } else {
+                        WasmValidationError::NoPackageInitExport(e.into())
+                    }
+                })?
                 .ok_or(WasmValidationError::InvalidPackageInit)?;
 
-            let blueprint_type: Type = match rtn {
+            let (blueprint_type, blueprint_methods, blueprint_events) = match rtn {
                 RuntimeValue::I32(ptr) => {
                     let len: u32 = memory
                         .get_value(ptr as u32)
                         .map_err(|_| WasmValidationError::InvalidPackageInit)?;
 
-                    // SECURITY: meter before allocating memory
+                    // SECURITY: check bounds before allocating memory
+                    if (ptr as u32 as usize)
+                        .saturating_add(4)
+                        .saturating_add(len as usize)
+                        > memory.current_size().0 * 65536
+                    {
+                        return Err(WasmValidationError::InvalidPackageInit);
+                    }
                     let mut data = vec![0u8; len as usize];
                     memory
                         .get_into((ptr + 4) as u32, &mut data)
                         .map_err(|_| WasmValidationError::InvalidPackageInit)?;
 
-                    let result: (Type, Vec<Function>, Vec<Method>) = scrypto_decode(&data)
+                    let result = Self::decode_abi(&data)
                         .map_err(|_| WasmValidationError::InvalidPackageInit)?;
-                    Ok(result.0)
+                    Ok((result.0, result.2, result.3))
                 }
                 _ => Err(WasmValidationError::InvalidPackageInit),
             }?;
 
             if let Type::Struct { name, fields: _ } = &blueprint_type {
+                events.insert(name.clone(), blueprint_events);
+                methods.insert(
+                    name.clone(),
+                    blueprint_methods
+                        .into_iter()
+                        .map(|m| (m.name, m.mutability))
+                        .collect(),
+                );
                 blueprints.insert(name.clone(), blueprint_type);
             } else {
                 return Err(WasmValidationError::InvalidPackageInit);
             }
         }
 
-        Ok(Self { blueprints, code })
+        Ok(Self {
+            code,
+            code_hash,
+            max_memory_pages,
+            blueprints,
+            events,
+            methods,
+        })
     }
 
     pub fn code(&self) -> &[u8] {
         &self.code
     }
 
+    /// Returns the hash of the instrumented code.
+    pub fn code_hash(&self) -> Hash {
+        self.code_hash
+    }
+
+    /// Returns the maximum size of the linear memory, in pages, declared by the instrumented code.
+    pub fn max_memory_pages(&self) -> u32 {
+        self.max_memory_pages
+    }
+
     pub fn contains_blueprint(&self, blueprint_name: &str) -> bool {
         self.blueprints.contains_key(blueprint_name)
     }
 
+    /// Returns the schema of an event, if the event is declared by the blueprint.
+    pub fn event_schema(&self, blueprint_name: &str, event_name: &str) -> Option<&Type> {
+        self.events
+            .get(blueprint_name)
+            .and_then(|events| events.iter().find(|e| e.name == event_name))
+            .map(|e| &e.schema)
+    }
+
+    /// Returns the declared mutability of a blueprint method, if the method exists.
+    pub fn method_mutability(
+        &self,
+        blueprint_name: &str,
+        method_name: &str,
+    ) -> Option<&Mutability> {
+        self.methods
+            .get(blueprint_name)
+            .and_then(|methods| methods.get(method_name))
+    }
+
     pub fn load_blueprint_schema(&self, blueprint_name: &str) -> Result<&Type, PackageError> {
         self.blueprints
             .get(blueprint_name)
             .ok_or(PackageError::BlueprintNotFound)
     }
 
-    pub fn load_module(&self) -> Result<(ModuleRef, MemoryRef), PackageError> {
-        let module = Self::parse_module(&self.code).unwrap();
-        let inst = Self::instantiate_module(&module).unwrap();
-        Ok(inst)
+    /// Decodes the output of a blueprint ABI export.
+    ///
+    /// Blueprints compiled before events were introduced export no event list.
+    pub fn decode_abi(data: &[u8]) -> Result<BlueprintAbi, DecodeError> {
+        scrypto_decode::<BlueprintAbi>(data).or_else(|e| {
+            scrypto_decode::<(Type, Vec<Function>, Vec<Method>)>(data)
+                .map(|(schema, functions, methods)| (schema, functions, methods, Vec::new()))
+                .map_err(|_| e)
+        })
     }
 
-    fn parse_module(code: &[u8]) -> Result<Module, WasmValidationError> {
-        Module::from_buffer(code).map_err(|_| WasmValidationError::InvalidModule)
+    /// Injects cost unit metering into each basic block, limits the stack height and caps the
+    /// memory size.
+    ///
+    /// Returns the instrumented code, along with the maximum memory size it declares.
+    fn instrument_code(code: &[u8]) -> Result<(Vec<u8>, u32), WasmValidationError> {
+        let module: parity_wasm::elements::Module = parity_wasm::deserialize_buffer(code)
+            .map_err(|_| WasmValidationError::InvalidModule)?;
+
+        // The metering function can only be imported by the instrumentation itself
+        if let Some(imports) = module.import_section() {
+            if imports
+                .entries()
+                .iter()
+                .any(|entry| entry.field() == COST_UNIT_FUNCTION_NAME)
+            {
+                return Err(WasmValidationError::ReservedImportNotAllowed);
+            }
+        }
+
+        let fee_table = FeeTable::new();
+        let rules = ConstantCostRules::new(
+            fee_table.wasm_instruction(),
+            fee_table.wasm_memory_grow_per_page(),
+        );
+        let module = gas_metering::inject(module, &rules, "env")
+            .map_err(|_| WasmValidationError::FailedToInstrumentCode)?;
+        let module = inject_stack_limiter(module, MAX_STACK_HEIGHT)
+            .map_err(|_| WasmValidationError::FailedToInstrumentCode)?;
+        let module = Self::export_stack_height(module)?;
+        let (module, max_memory_pages) = Self::cap_memory(module)?;
+
+        let code = parity_wasm::serialize(module)
+            .map_err(|_| WasmValidationError::FailedToInstrumentCode)?;
+        Ok((code, max_memory_pages))
     }
 
-    fn instantiate_module(module: &Module) -> Result<(ModuleRef, MemoryRef), WasmValidationError> {
-        // Instantiate
-        let instance = ModuleInstance::new(
-            module,
-            &ImportsBuilder::new().with_resolver("env", &EnvModuleResolver),
-        )
-        .map_err(|_| WasmValidationError::InvalidModule)?
-        .assert_no_start();
+    /// Caps the declared maximum size of the memory to `MAX_WASM_MEMORY_PAGES`, so that
+    /// growing it any further fails within the instance.
+    fn cap_memory(
+        mut module: parity_wasm::elements::Module,
+    ) -> Result<(parity_wasm::elements::Module, u32), WasmValidationError> {
+        // An imported memory could not be capped
+        if module.import_count(parity_wasm::elements::ImportCountType::Memory) > 0 {
+            return Err(WasmValidationError::NoValidMemoryExport);
+        }
 
-        // Find memory export
-        if let Some(ExternVal::Memory(memory)) = instance.export_by_name("memory") {
-            Ok((instance, memory))
-        } else {
-            Err(WasmValidationError::NoValidMemoryExport)
+        let mut max_memory_pages = 0;
+        if let Some(section) = module.memory_section_mut() {
+            for entry in section.entries_mut() {
+                let limits = entry.limits();
+                if limits.initial() > MAX_WASM_MEMORY_PAGES {
+                    return Err(WasmValidationError::TooManyMemoryPages);
+                }
+                let maximum = limits
+                    .maximum()
+                    .map_or(MAX_WASM_MEMORY_PAGES, |max| max.min(MAX_WASM_MEMORY_PAGES));
+                *entry = parity_wasm::elements::MemoryType::new(limits.initial(), Some(maximum));
+                max_memory_pages = max_memory_pages.max(maximum);
+            }
         }
+        Ok((module, max_memory_pages))
+    }
+
+    /// Exports the stack height counter, which the stack limiter adds as the last global, so
+    /// that engines can tell a stack overflow apart from other traps.
+    fn export_stack_height(
+        mut module: parity_wasm::elements::Module,
+    ) -> Result<parity_wasm::elements::Module, WasmValidationError> {
+        let imported_globals =
+            module.import_count(parity_wasm::elements::ImportCountType::Global) as u32;
+        let globals = module
+            .global_section()
+            .map(|section| section.entries().len() as u32)
+            .unwrap_or(0);
+        if globals == 0 {
+            return Err(WasmValidationError::FailedToInstrumentCode);
+        }
+
+        let exports = module
+            .export_section_mut()
+            .ok_or(WasmValidationError::FailedToInstrumentCode)?;
+        if exports
+            .entries()
+            .iter()
+            .any(|entry| entry.field() == STACK_HEIGHT_GLOBAL_NAME)
+        {
+            return Err(WasmValidationError::FailedToInstrumentCode);
+        }
+        exports
+            .entries_mut()
+            .push(parity_wasm::elements::ExportEntry::new(
+                STACK_HEIGHT_GLOBAL_NAME.to_string(),
+                parity_wasm::elements::Internal::Global(imported_globals + globals - 1),
+            ));
+        Ok(module)
+    }
+
+    fn parse_module(code: &[u8]) -> Result<Module, WasmValidationError> {
+        Module::from_buffer(code).map_err(|_| WasmValidationError::InvalidModule)
     }
 
     pub fn static_main<S: SystemApi>(
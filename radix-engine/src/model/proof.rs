@@ -10,7 +10,7 @@ use scrypto::values::ScryptoValue;
 use crate::engine::SystemApi;
 
 use crate::model::{
-    LockedAmountOrIds, ResourceContainer, ResourceContainerError, ResourceContainerId,
+    AmountOrIds, LockedAmountOrIds, ResourceContainer, ResourceContainerError, ResourceContainerId,
 };
 
 #[derive(Debug)]
@@ -320,6 +320,13 @@ impl Proof {
             .map_err(|_| ProofError::NonFungibleOperationNotAllowed)
     }
 
+    pub fn total_amount_or_ids(&self) -> AmountOrIds {
+        match &self.total_locked {
+            LockedAmountOrIds::Amount(amount) => AmountOrIds::Amount(*amount),
+            LockedAmountOrIds::Ids(ids) => AmountOrIds::Ids(ids.clone()),
+        }
+    }
+
     pub fn is_restricted(&self) -> bool {
         self.restricted
     }
@@ -1,6 +1,8 @@
 use colored::*;
+use sbor::*;
 use scrypto::engine::types::*;
 use scrypto::rust::borrow::ToOwned;
+use scrypto::rust::collections::BTreeMap;
 use scrypto::rust::fmt;
 use scrypto::rust::format;
 use scrypto::rust::string::String;
@@ -8,8 +10,9 @@ use scrypto::rust::string::ToString;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 
-use crate::engine::CommitReceipt;
+use crate::engine::{BalanceChange, CommitReceipt};
 use crate::errors::*;
+use crate::fee::FeeSummary;
 use crate::model::*;
 
 /// Represents a transaction receipt.
@@ -19,10 +22,80 @@ pub struct Receipt {
     pub result: Result<(), RuntimeError>,
     pub outputs: Vec<ScryptoValue>,
     pub logs: Vec<(Level, String)>,
+    pub events: Vec<Event>,
+    pub execution_trace: Vec<ExecutionTrace>,
+    pub resource_events: Vec<ResourceEvent>,
     pub new_package_addresses: Vec<PackageAddress>,
     pub new_component_addresses: Vec<ComponentAddress>,
     pub new_resource_addresses: Vec<ResourceAddress>,
     pub execution_time: Option<u128>,
+    pub fee_summary: FeeSummary,
+}
+
+/// A summary of a transaction receipt, as kept in the transaction history.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ReceiptSummary {
+    /// The error message, if the transaction failed
+    pub result: Result<(), String>,
+    pub new_package_addresses: Vec<PackageAddress>,
+    pub new_component_addresses: Vec<ComponentAddress>,
+    pub new_resource_addresses: Vec<ResourceAddress>,
+    pub logs: Vec<(Level, String)>,
+    pub events: Vec<Event>,
+}
+
+impl Receipt {
+    /// Returns a summary of this receipt.
+    pub fn summary(&self) -> ReceiptSummary {
+        ReceiptSummary {
+            result: self.result.clone().map_err(|e| e.to_string()),
+            new_package_addresses: self.new_package_addresses.clone(),
+            new_component_addresses: self.new_component_addresses.clone(),
+            new_resource_addresses: self.new_resource_addresses.clone(),
+            logs: self.logs.clone(),
+            events: self.events.clone(),
+        }
+    }
+
+    /// Returns the net balance changes of each component, per resource, as committed by the
+    /// transaction, including the fee payment.
+    ///
+    /// This is empty for failed transactions; see `fee_summary` for the fee charged.
+    pub fn balance_changes(
+        &self,
+    ) -> BTreeMap<ComponentAddress, BTreeMap<ResourceAddress, BalanceChange>> {
+        self.commit_receipt
+            .as_ref()
+            .map(|commit_receipt| commit_receipt.balance_changes.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Writes an execution trace node and its children, indented by call depth.
+fn fmt_execution_trace(
+    f: &mut fmt::Formatter<'_>,
+    trace: &ExecutionTrace,
+    indent: &str,
+    last: bool,
+) -> fmt::Result {
+    write!(
+        f,
+        "\n{}{} {:?} {} ({} cost units)",
+        indent,
+        if last { "└─" } else { "├─" },
+        trace.snode_ref,
+        trace.function.cyan(),
+        trace.cost_units
+    )?;
+    if let Err(error) = &trace.output {
+        write!(f, " {}", error.to_string().red())?;
+    }
+
+    let child_indent = format!("{}{}", indent, if last { "   " } else { "│  " });
+    for (i, child) in trace.children.iter().enumerate() {
+        fmt_execution_trace(f, child, &child_indent, i == trace.children.len() - 1)?;
+    }
+    Ok(())
 }
 
 macro_rules! prefix {
@@ -40,6 +113,13 @@ impl fmt::Debug for Receipt {
         write!(
             f,
             "{} {}",
+            "Transaction Hash:".bold().green(),
+            self.validated_transaction.raw_hash
+        )?;
+
+        write!(
+            f,
+            "\n{} {}",
             "Transaction Status:".bold().green(),
             match &self.result {
                 Ok(()) => "SUCCESS".blue(),
@@ -57,6 +137,21 @@ impl fmt::Debug for Receipt {
                 .unwrap_or(String::from("?"))
         )?;
 
+        write!(
+            f,
+            "\n{} {} / {}",
+            "Cost Units:".bold().green(),
+            self.fee_summary.cost_unit_consumed,
+            self.fee_summary.cost_unit_limit
+        )?;
+
+        write!(
+            f,
+            "\n{} {} XRD",
+            "Fee Paid:".bold().green(),
+            self.fee_summary.fee_paid
+        )?;
+
         write!(f, "\n{}", "Instructions:".bold().green())?;
         for (i, inst) in self.validated_transaction.instructions.iter().enumerate() {
             write!(
@@ -87,6 +182,75 @@ impl fmt::Debug for Receipt {
             write!(f, "\n{} [{:5}] {}", prefix!(i, self.logs), l, m)?;
         }
 
+        write!(f, "\n{} {}", "Events:".bold().green(), self.events.len())?;
+        for (i, event) in self.events.iter().enumerate() {
+            write!(
+                f,
+                "\n{} {} {} {}",
+                prefix!(i, self.events),
+                match event.actor.component_address() {
+                    Some(component_address) => component_address.to_string(),
+                    None => format!(
+                        "{}::{}",
+                        event.actor.package_address(),
+                        event.actor.blueprint_name()
+                    ),
+                },
+                event.name.cyan(),
+                ScryptoValue::from_slice(&event.data)
+                    .map(|value| value.to_string())
+                    .unwrap_or_default()
+            )?;
+        }
+
+        if !self.execution_trace.is_empty() {
+            write!(f, "\n{}", "Execution Trace:".bold().green())?;
+            for (i, trace) in self.execution_trace.iter().enumerate() {
+                fmt_execution_trace(f, trace, "", i == self.execution_trace.len() - 1)?;
+            }
+        }
+
+        write!(
+            f,
+            "\n{} {}",
+            "Resource Events:".bold().green(),
+            self.resource_events.len()
+        )?;
+        for (i, event) in self.resource_events.iter().enumerate() {
+            write!(f, "\n{} {:?}", prefix!(i, self.resource_events), event)?;
+        }
+
+        let balance_changes: Vec<(ComponentAddress, ResourceAddress, BalanceChange)> = self
+            .balance_changes()
+            .into_iter()
+            .flat_map(|(component_address, changes)| {
+                changes.into_iter().map(move |(resource_address, change)| {
+                    (component_address, resource_address, change)
+                })
+            })
+            .collect();
+        write!(
+            f,
+            "\n{} {}",
+            "Balance Changes:".bold().green(),
+            balance_changes.len()
+        )?;
+        for (i, (component_address, resource_address, change)) in balance_changes.iter().enumerate()
+        {
+            write!(
+                f,
+                "\n{} {} {}: {}",
+                prefix!(i, balance_changes),
+                component_address,
+                resource_address,
+                match change {
+                    BalanceChange::Fungible(delta) => delta.to_string(),
+                    BalanceChange::NonFungible { added, removed } =>
+                        format!("+{:?} -{:?}", added, removed),
+                }
+            )?;
+        }
+
         write!(
             f,
             "\n{} {}",
@@ -53,6 +53,13 @@ pub enum LockedAmountOrIds {
     Ids(BTreeSet<NonFungibleId>),
 }
 
+/// The amount or non-fungible IDs of a resource.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub enum AmountOrIds {
+    Amount(Decimal),
+    Ids(BTreeSet<NonFungibleId>),
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum ResourceContainerId {
     Bucket(BucketId),
@@ -360,6 +367,14 @@ impl ResourceContainer {
         Ok(total)
     }
 
+    /// Returns the total amount, or the total non-fungible IDs if non-fungible.
+    pub fn total_amount_or_ids(&self) -> AmountOrIds {
+        match self {
+            Self::Fungible { .. } => AmountOrIds::Amount(self.total_amount()),
+            Self::NonFungible { .. } => AmountOrIds::Ids(self.total_ids().unwrap()),
+        }
+    }
+
     pub fn is_locked(&self) -> bool {
         match self {
             Self::Fungible { locked_amounts, .. } => !locked_amounts.is_empty(),
@@ -6,7 +6,9 @@ use scrypto::engine::types::*;
 use scrypto::prelude::AccessRule::{AllowAll, DenyAll};
 use scrypto::prelude::ResourceMethod::Withdraw;
 use scrypto::resource::Mutability::LOCKED;
-use scrypto::resource::ResourceMethod::{Burn, Mint, UpdateMetadata, UpdateNonFungibleData};
+use scrypto::resource::ResourceMethod::{
+    Burn, Freeze, Mint, Recall, UpdateMetadata, UpdateNonFungibleData,
+};
 use scrypto::resource::*;
 use scrypto::rust::collections::*;
 use scrypto::rust::string::String;
@@ -14,7 +16,7 @@ use scrypto::rust::string::ToString;
 use scrypto::rust::vec::*;
 use scrypto::values::ScryptoValue;
 
-use crate::model::{convert, MethodAuthorization, ResourceContainer};
+use crate::model::{convert, MethodAuthorization, ResourceContainer, ResourceMovement};
 
 /// Converts soft authorization rule to a hard authorization rule.
 /// Currently required as all auth is defined by soft authorization rules.
@@ -33,6 +35,11 @@ pub enum ResourceManagerError {
     InvalidMintPermission,
     ResourceTypeDoesNotMatch,
     MaxMintAmountExceeded,
+    MaxSupplyExceeded,
+    InvalidMaxSupply,
+    InvalidNonFungibleId(NonFungibleId),
+    SequentialIdNotSupported,
+    SequentialIdsExhausted,
     InvalidNonFungibleData,
     NonFungibleAlreadyExists(NonFungibleAddress),
     NonFungibleNotFound(NonFungibleAddress),
@@ -108,6 +115,12 @@ pub struct ResourceManager {
     method_table: HashMap<String, Option<ResourceMethod>>,
     authorization: HashMap<ResourceMethod, MethodEntry>,
     total_supply: Decimal,
+    /// The cap on total supply, if any; only fungible resources can be capped
+    max_supply: Option<Decimal>,
+    /// The type of the ids, for non-fungible resources
+    non_fungible_id_type: Option<NonFungibleIdType>,
+    /// The sequence number to try first when minting a non-fungible with a sequential id
+    next_sequential_id: u64,
 }
 
 impl ResourceManager {
@@ -115,15 +128,25 @@ impl ResourceManager {
         resource_type: ResourceType,
         metadata: HashMap<String, String>,
         mut auth: HashMap<ResourceMethod, (AccessRule, Mutability)>,
+        max_supply: Option<Decimal>,
+        non_fungible_id_type: Option<NonFungibleIdType>,
     ) -> Result<Self, ResourceManagerError> {
         let mut method_table: HashMap<String, Option<ResourceMethod>> = HashMap::new();
         method_table.insert("mint".to_string(), Some(Mint));
+        if let ResourceType::NonFungible = resource_type {
+            method_table.insert("mint_next_non_fungible".to_string(), Some(Mint));
+        }
         method_table.insert("burn".to_string(), Some(Burn));
         method_table.insert("take_from_vault".to_string(), Some(Withdraw));
+        method_table.insert("lock_fee".to_string(), Some(Withdraw));
         method_table.insert("put_into_vault".to_string(), Some(Deposit));
         method_table.insert("update_metadata".to_string(), Some(UpdateMetadata));
+        method_table.insert("recall_from_vault".to_string(), Some(Recall));
+        method_table.insert("freeze_vault".to_string(), Some(Freeze));
+        method_table.insert("unfreeze_vault".to_string(), Some(Freeze));
         if let ResourceType::NonFungible = resource_type {
             method_table.insert("take_non_fungibles_from_vault".to_string(), Some(Withdraw));
+            method_table.insert("recall_non_fungibles_from_vault".to_string(), Some(Recall));
         }
 
         for pub_method in [
@@ -133,12 +156,14 @@ impl ResourceManager {
             "get_metadata",
             "get_resource_type",
             "get_total_supply",
+            "get_max_supply",
             "take_from_bucket",
             "put_into_bucket",
             "get_bucket_amount",
             "get_bucket_resource_address",
             "get_vault_amount",
             "get_vault_resource_address",
+            "is_vault_frozen",
             "create_vault_proof",
             "create_vault_proof_by_amount",
             "create_vault_proof_by_ids",
@@ -157,6 +182,7 @@ impl ResourceManager {
                 "get_non_fungible",
                 "get_non_fungible_ids_in_bucket",
                 "get_non_fungible_ids_in_vault",
+                "get_non_fungible_id_type",
             ] {
                 method_table.insert(pub_method.to_string(), None);
             }
@@ -170,19 +196,43 @@ impl ResourceManager {
             (Deposit, (AllowAll, LOCKED)),
             (UpdateMetadata, (DenyAll, LOCKED)),
             (UpdateNonFungibleData, (DenyAll, LOCKED)),
+            (Recall, (DenyAll, LOCKED)),
+            (Freeze, (DenyAll, LOCKED)),
         ] {
             let entry = auth.remove(&auth_entry_key).unwrap_or(default);
             authorization.insert(auth_entry_key, MethodEntry::new(entry));
         }
 
+        let non_fungible_id_type = match resource_type {
+            ResourceType::Fungible { .. } => {
+                if non_fungible_id_type.is_some() {
+                    return Err(ResourceManagerError::ResourceTypeDoesNotMatch);
+                }
+                None
+            }
+            ResourceType::NonFungible => {
+                Some(non_fungible_id_type.unwrap_or(NonFungibleIdType::Bytes))
+            }
+        };
+
         let resource_manager = Self {
             resource_type,
             metadata,
             method_table,
             authorization,
             total_supply: 0.into(),
+            max_supply,
+            non_fungible_id_type,
+            next_sequential_id: 1,
         };
 
+        if let Some(max_supply) = max_supply {
+            if let ResourceType::NonFungible = resource_type {
+                return Err(ResourceManagerError::InvalidMaxSupply);
+            }
+            resource_manager.check_amount(max_supply)?;
+        }
+
         Ok(resource_manager)
     }
 
@@ -221,18 +271,32 @@ impl ResourceManager {
         self.total_supply
     }
 
+    pub fn max_supply(&self) -> Option<Decimal> {
+        self.max_supply
+    }
+
+    pub fn non_fungible_id_type(&self) -> Option<NonFungibleIdType> {
+        self.non_fungible_id_type
+    }
+
     fn mint<S: SystemApi>(
         &mut self,
         mint_params: MintParams,
         self_address: ResourceAddress,
         system_api: &mut S,
     ) -> Result<ResourceContainer, ResourceManagerError> {
-        match mint_params {
+        let container = match mint_params {
             MintParams::Fungible { amount } => self.mint_fungible(amount, self_address),
             MintParams::NonFungible { entries } => {
                 self.mint_non_fungibles(entries, self_address, system_api)
             }
-        }
+        }?;
+        system_api.record_resource_movement(
+            ResourceMovement::Mint,
+            self_address,
+            container.total_amount_or_ids(),
+        );
+        Ok(container)
     }
 
     pub fn mint_fungible(
@@ -250,6 +314,12 @@ impl ResourceManager {
                 return Err(ResourceManagerError::MaxMintAmountExceeded);
             }
 
+            if let Some(max_supply) = self.max_supply {
+                if self.total_supply + amount > max_supply {
+                    return Err(ResourceManagerError::MaxSupplyExceeded);
+                }
+            }
+
             self.total_supply += amount;
 
             Ok(ResourceContainer::new_fungible(
@@ -306,6 +376,12 @@ impl ResourceManager {
         // Allocate non-fungibles
         let mut ids = BTreeSet::new();
         for (id, data) in entries {
+            if let Some(id_type) = self.non_fungible_id_type {
+                if !id_type.is_valid(&id) {
+                    return Err(ResourceManagerError::InvalidNonFungibleId(id));
+                }
+            }
+
             let non_fungible_address = NonFungibleAddress::new(self_address, id.clone());
             if system_api.get_non_fungible(&non_fungible_address).is_some() {
                 return Err(ResourceManagerError::NonFungibleAlreadyExists(
@@ -324,6 +400,34 @@ impl ResourceManager {
         Ok(ResourceContainer::new_non_fungible(self_address, ids))
     }
 
+    fn next_non_fungible_id<S: SystemApi>(
+        &mut self,
+        self_address: ResourceAddress,
+        system_api: &mut S,
+    ) -> Result<NonFungibleId, ResourceManagerError> {
+        let id_type = self
+            .non_fungible_id_type
+            .ok_or(ResourceManagerError::ResourceTypeDoesNotMatch)?;
+        if !id_type.is_sequential() {
+            return Err(ResourceManagerError::SequentialIdNotSupported);
+        }
+
+        // Skip over any ids which were minted explicitly
+        loop {
+            let id = id_type
+                .sequential_id(self.next_sequential_id)
+                .ok_or(ResourceManagerError::SequentialIdsExhausted)?;
+            self.next_sequential_id = self
+                .next_sequential_id
+                .checked_add(1)
+                .ok_or(ResourceManagerError::SequentialIdsExhausted)?;
+            let non_fungible_address = NonFungibleAddress::new(self_address, id.clone());
+            if system_api.get_non_fungible(&non_fungible_address).is_none() {
+                return Ok(id);
+            }
+        }
+    }
+
     pub fn burn(&mut self, amount: Decimal) {
         self.total_supply -= amount;
     }
@@ -347,44 +451,61 @@ impl ResourceManager {
         }
     }
 
+    /// Creates a resource from the first four arguments of a `create` call, i.e. its type,
+    /// metadata, authorization and optional initial supply.
+    fn create<S: SystemApi>(
+        args: &[ScryptoValue],
+        options: ResourceOptions,
+        system_api: &mut S,
+    ) -> Result<ScryptoValue, ResourceManagerError> {
+        let resource_type =
+            scrypto_decode(&args[0].raw).map_err(ResourceManagerError::InvalidRequestData)?;
+        let metadata =
+            scrypto_decode(&args[1].raw).map_err(ResourceManagerError::InvalidRequestData)?;
+        let auth =
+            scrypto_decode(&args[2].raw).map_err(ResourceManagerError::InvalidRequestData)?;
+        let mint_params_maybe: Option<MintParams> =
+            scrypto_decode(&args[3].raw).map_err(ResourceManagerError::InvalidRequestData)?;
+        let resource_manager = ResourceManager::new(
+            resource_type,
+            metadata,
+            auth,
+            options.max_supply,
+            options.non_fungible_id_type,
+        )?;
+        let resource_address = system_api.create_resource(resource_manager);
+
+        let bucket_id = if let Some(mint_params) = mint_params_maybe {
+            let mut resource_manager = system_api
+                .borrow_global_mut_resource_manager(resource_address)
+                .unwrap();
+            let container = resource_manager.mint(mint_params, resource_address, system_api)?;
+            system_api.return_borrowed_global_resource_manager(resource_address, resource_manager);
+
+            let bucket_id = system_api
+                .create_bucket(container)
+                .map_err(|_| ResourceManagerError::CouldNotCreateBucket)?;
+            Some(scrypto::resource::Bucket(bucket_id))
+        } else {
+            None
+        };
+
+        Ok(ScryptoValue::from_value(&(resource_address, bucket_id)))
+    }
+
     pub fn static_main<S: SystemApi>(
         function: &str,
         args: Vec<ScryptoValue>,
         system_api: &mut S,
     ) -> Result<ScryptoValue, ResourceManagerError> {
         match function {
-            "create" => {
-                let resource_type = scrypto_decode(&args[0].raw)
-                    .map_err(ResourceManagerError::InvalidRequestData)?;
-                let metadata = scrypto_decode(&args[1].raw)
+            // Kept for callers built before resource options were introduced, such as the system
+            // blueprint
+            "create" => Self::create(&args, ResourceOptions::default(), system_api),
+            "create_with_options" => {
+                let options: ResourceOptions = scrypto_decode(&args[4].raw)
                     .map_err(ResourceManagerError::InvalidRequestData)?;
-                let auth = scrypto_decode(&args[2].raw)
-                    .map_err(ResourceManagerError::InvalidRequestData)?;
-                let mint_params_maybe: Option<MintParams> = scrypto_decode(&args[3].raw)
-                    .map_err(ResourceManagerError::InvalidRequestData)?;
-                let resource_manager = ResourceManager::new(resource_type, metadata, auth)?;
-                let resource_address = system_api.create_resource(resource_manager);
-
-                let bucket_id = if let Some(mint_params) = mint_params_maybe {
-                    let mut resource_manager = system_api
-                        .borrow_global_mut_resource_manager(resource_address)
-                        .unwrap();
-                    let container =
-                        resource_manager.mint(mint_params, resource_address, system_api)?;
-                    system_api.return_borrowed_global_resource_manager(
-                        resource_address,
-                        resource_manager,
-                    );
-
-                    let bucket_id = system_api
-                        .create_bucket(container)
-                        .map_err(|_| ResourceManagerError::CouldNotCreateBucket)?;
-                    Some(scrypto::resource::Bucket(bucket_id))
-                } else {
-                    None
-                };
-
-                Ok(ScryptoValue::from_value(&(resource_address, bucket_id)))
+                Self::create(&args, options, system_api)
             }
             _ => Err(ResourceManagerError::MethodNotFound(function.to_string())),
         }
@@ -428,9 +549,36 @@ impl ResourceManager {
                     bucket_id,
                 )))
             }
+            "mint_next_non_fungible" => {
+                let immutable_data: Vec<u8> = scrypto_decode(&args[0].raw)
+                    .map_err(ResourceManagerError::InvalidRequestData)?;
+                let mutable_data: Vec<u8> = scrypto_decode(&args[1].raw)
+                    .map_err(ResourceManagerError::InvalidRequestData)?;
+                let id = self.next_non_fungible_id(resource_address, system_api)?;
+                let mut entries = HashMap::new();
+                entries.insert(id, (immutable_data, mutable_data));
+                let container = self.mint(
+                    MintParams::NonFungible { entries },
+                    resource_address,
+                    system_api,
+                )?;
+                let bucket_id = system_api
+                    .create_bucket(container)
+                    .map_err(|_| ResourceManagerError::CouldNotCreateBucket)?;
+                Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(
+                    bucket_id,
+                )))
+            }
             "get_metadata" => Ok(ScryptoValue::from_value(&self.metadata)),
             "get_resource_type" => Ok(ScryptoValue::from_value(&self.resource_type)),
             "get_total_supply" => Ok(ScryptoValue::from_value(&self.total_supply)),
+            "get_max_supply" => Ok(ScryptoValue::from_value(&self.max_supply)),
+            "get_non_fungible_id_type" => {
+                let id_type = self
+                    .non_fungible_id_type
+                    .ok_or(ResourceManagerError::ResourceTypeDoesNotMatch)?;
+                Ok(ScryptoValue::from_value(&id_type))
+            }
             "update_metadata" => {
                 let new_metadata: HashMap<String, String> = scrypto_decode(&args[0].raw)
                     .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
@@ -12,18 +12,67 @@ use crate::engine::*;
 use crate::errors::*;
 use crate::model::{ValidatedInstruction, ValidatedTransaction};
 
+/// The network ID of local ledgers, e.g. the in-memory ledger used in tests.
+pub const LOCAL_NETWORK_ID: u32 = 0;
+
 /// Represents an unsigned transaction
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct Transaction {
+    pub header: TransactionHeader,
     pub instructions: Vec<Instruction>,
 }
 
+/// Represents the header of a transaction, which scopes where and when it can be committed.
+///
+/// The hash of the header and instructions is the intent hash, and a ledger commits each
+/// intent at most once.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct TransactionHeader {
+    /// The network this transaction is intended for
+    pub network_id: u32,
+    /// The first epoch in which this transaction can be committed
+    pub start_epoch_inclusive: u64,
+    /// The first epoch in which this transaction can no longer be committed
+    pub end_epoch_exclusive: u64,
+    /// Distinguishes otherwise identical intents
+    pub nonce: u64,
+    /// The notary, who must sign this transaction; fees are paid from the vaults locked by
+    /// `lock_fee`, not charged to the notary
+    pub notary_public_key: Option<PublicKey>,
+}
+
+impl Default for TransactionHeader {
+    /// Returns a header for the local network, which never expires.
+    fn default() -> Self {
+        Self {
+            network_id: LOCAL_NETWORK_ID,
+            start_epoch_inclusive: 0,
+            end_epoch_exclusive: u64::MAX,
+            nonce: 0,
+            notary_public_key: None,
+        }
+    }
+}
+
 /// Represents a signed transaction
+///
+/// Signatures can be collected over several steps, as each one only covers the unsigned
+/// transaction, e.g. when the signers of a multi-signature account are on different machines.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct SignedTransaction {
     /// The unsigned transaction
     pub transaction: Transaction,
     /// The signatures. Public keys are for signature algorithm that doesn't support public key recovery, e.g. ed25519.
-    pub signatures: Vec<(EcdsaPublicKey, EcdsaSignature)>,
+    pub signatures: Vec<(PublicKey, Signature)>,
+}
+
+/// Options for previewing a transaction, none of which are allowed when committing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PreviewFlags {
+    /// Accepts the public keys of the signatures as signers, without verifying the signatures.
+    pub skip_signature_check: bool,
+    /// Ignores all method and resource access rules.
+    pub skip_auth_check: bool,
 }
 
 
@@ -124,11 +173,6 @@ pub enum Instruction {
 
     /// Publishes a package.
     PublishPackage { code: Vec<u8> },
-
-    /// Specifies transaction nonce
-    Nonce {
-        nonce: u64, // TODO: may be replaced with substate id for entropy
-    },
 }
 
 impl Transaction {
@@ -136,42 +180,101 @@ impl Transaction {
         scrypto_encode(self)
     }
 
+    /// Returns the intent hash, which covers the header and instructions.
     pub fn raw_hash(&self) -> Hash {
         hash(self.to_vec())
     }
 
     pub fn add_nonce(&mut self, nonce: u64) {
-        self.instructions.push(Instruction::Nonce { nonce });
+        self.header.nonce = nonce;
     }
 
-    // TODO: introduce a `Signer` trait
-    pub fn sign<'a, T: AsRef<[&'a EcdsaPrivateKey]>>(self, sks: T) -> SignedTransaction {
-        let msg = self.to_vec();
-        let signatures = sks
-            .as_ref()
-            .iter()
-            .map(|sk| (sk.public_key(), sk.sign(&msg)))
-            .collect();
-
+    /// Wraps this transaction for signing, with no signature yet.
+    pub fn into_signed(self) -> SignedTransaction {
         SignedTransaction {
             transaction: self,
-            signatures: signatures,
+            signatures: Vec::new(),
         }
     }
+
+    // TODO: introduce a `Signer` trait
+    pub fn sign<'a, T: AsRef<[&'a EcdsaPrivateKey]>>(self, sks: T) -> SignedTransaction {
+        self.into_signed().sign(sks)
+    }
+
+    pub fn sign_ed25519<'a, T: AsRef<[&'a Ed25519PrivateKey]>>(
+        self,
+        sks: T,
+    ) -> SignedTransaction {
+        self.into_signed().sign_ed25519(sks)
+    }
 }
 
 impl SignedTransaction {
+    /// Adds signatures by the given ECDSA keys to those already collected.
+    pub fn sign<'a, T: AsRef<[&'a EcdsaPrivateKey]>>(mut self, sks: T) -> Self {
+        let msg = self.transaction.to_vec();
+        for sk in sks.as_ref() {
+            self.signatures
+                .push((sk.public_key().into(), sk.sign(&msg).into()));
+        }
+        self
+    }
+
+    /// Adds signatures by the given ED25519 keys to those already collected.
+    pub fn sign_ed25519<'a, T: AsRef<[&'a Ed25519PrivateKey]>>(mut self, sks: T) -> Self {
+        let msg = self.transaction.to_vec();
+        for sk in sks.as_ref() {
+            self.signatures
+                .push((sk.public_key().into(), sk.sign(&msg).into()));
+        }
+        self
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        scrypto_encode(self)
+    }
+
     pub fn validate(&self) -> Result<ValidatedTransaction, TransactionValidationError> {
+        self.validate_with_signature_check(true)
+    }
+
+    /// Validates the transaction for a preview, which may skip the signature check.
+    pub fn validate_preview(
+        &self,
+        flags: &PreviewFlags,
+    ) -> Result<ValidatedTransaction, TransactionValidationError> {
+        self.validate_with_signature_check(!flags.skip_signature_check)
+    }
+
+    fn validate_with_signature_check(
+        &self,
+        check_signatures: bool,
+    ) -> Result<ValidatedTransaction, TransactionValidationError> {
         let mut instructions = vec![];
         let mut signers = vec![];
 
+        // check header; the network, epoch and replay checks need the ledger
+        let header = &self.transaction.header;
+        if header.start_epoch_inclusive >= header.end_epoch_exclusive {
+            return Err(TransactionValidationError::InvalidEpochRange {
+                start_epoch_inclusive: header.start_epoch_inclusive,
+                end_epoch_exclusive: header.end_epoch_exclusive,
+            });
+        }
+        if let Some(notary_public_key) = &header.notary_public_key {
+            if !self.signatures.iter().any(|(pk, _)| pk == notary_public_key) {
+                return Err(TransactionValidationError::MissingNotarySignature);
+            }
+        }
+
         // verify signature (may defer to runtime)
         let msg = self.transaction.to_vec();
         for (pk, sig) in &self.signatures {
-            if !EcdsaVerifier::verify(&msg, pk, sig) {
+            if check_signatures && !SignatureVerifier::verify(&msg, pk, sig) {
                 return Err(TransactionValidationError::InvalidSignature);
             }
-            signers.push(pk.clone());
+            signers.push(*pk);
         }
 
         // semantic analysis
@@ -339,16 +442,15 @@ impl SignedTransaction {
                 Instruction::PublishPackage { code } => {
                     instructions.push(ValidatedInstruction::PublishPackage { code });
                 }
-                Instruction::Nonce { .. } => {
-                    // TODO: validate nonce
-                }
             }
         }
 
         Ok(ValidatedTransaction {
             raw_hash: self.transaction.raw_hash(),
+            header: self.transaction.header.clone(),
             instructions,
             signers,
+            signed_transaction: self.to_vec(),
         })
     }
 
@@ -392,6 +494,7 @@ mod tests {
         assert_eq!(
             SignedTransaction {
                 transaction: Transaction {
+                    header: TransactionHeader::default(),
                     instructions: vec![Instruction::CallMethod {
                         component_address: ComponentAddress([1u8; 26]),
                         method: "test".to_owned(),
@@ -416,6 +519,7 @@ mod tests {
         assert_eq!(
             SignedTransaction {
                 transaction: Transaction {
+                    header: TransactionHeader::default(),
                     instructions: vec![Instruction::CallMethod {
                         component_address: ComponentAddress([1u8; 26]),
                         method: "test".to_owned(),
@@ -435,4 +539,43 @@ mod tests {
             ))),
         );
     }
+
+    #[test]
+    fn should_reject_transaction_with_invalid_ed25519_signature() {
+        let sk = Ed25519PrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let other_sk = Ed25519PrivateKey::from_bytes(&[2u8; 32]).unwrap();
+        let transaction = Transaction {
+            header: TransactionHeader::default(),
+            instructions: Vec::new(),
+        };
+        let mut signed = transaction.sign_ed25519([&sk]);
+        assert!(signed.validate().is_ok());
+
+        signed.signatures[0].0 = other_sk.public_key().into();
+        assert_eq!(
+            signed.validate(),
+            Err(TransactionValidationError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn should_accept_signatures_added_in_separate_steps() {
+        let sk0 = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let sk1 = Ed25519PrivateKey::from_bytes(&[2u8; 32]).unwrap();
+        let transaction = Transaction {
+            header: TransactionHeader::default(),
+            instructions: Vec::new(),
+        };
+
+        let partially_signed = transaction.clone().into_signed().sign([&sk0]);
+        let encoded = partially_signed.to_vec();
+        let decoded: SignedTransaction = scrypto::buffer::scrypto_decode(&encoded).unwrap();
+        let signed = decoded.sign_ed25519([&sk1]);
+
+        assert_eq!(
+            signed.validate().unwrap().signers,
+            vec![sk0.public_key().into(), sk1.public_key().into()]
+        );
+        assert_eq!(signed, transaction.sign([&sk0]).sign_ed25519([&sk1]));
+    }
 }
@@ -1,16 +1,21 @@
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
-use scrypto::rust::collections::{BTreeSet};
+use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::string::String;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 
+use crate::model::TransactionHeader;
+
 /// Represents a validated transaction
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidatedTransaction {
     pub raw_hash: Hash,
+    pub header: TransactionHeader,
     pub instructions: Vec<ValidatedInstruction>,
-    pub signers: Vec<EcdsaPublicKey>,
+    pub signers: Vec<PublicKey>,
+    /// The SBOR-encoded signed transaction, as recorded in the transaction history
+    pub signed_transaction: Vec<u8>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -83,4 +88,4 @@ pub enum ValidatedInstruction {
     PublishPackage {
         code: Vec<u8>,
     },
-}
\ No newline at end of file
+}
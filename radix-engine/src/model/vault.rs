@@ -1,6 +1,8 @@
 use sbor::*;
 use scrypto::buffer::scrypto_decode;
+use scrypto::constants::RADIX_TOKEN;
 use scrypto::engine::types::*;
+use scrypto::resource::AccessRules;
 use scrypto::rust::cell::{Ref, RefCell, RefMut};
 use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::collections::HashMap;
@@ -12,7 +14,8 @@ use scrypto::values::ScryptoValue;
 use crate::engine::SystemApi;
 
 use crate::model::{
-    Bucket, Proof, ProofError, ResourceContainer, ResourceContainerError, ResourceContainerId,
+    convert, AmountOrIds, Bucket, MethodAuthorization, Proof, ProofError, ResourceContainer,
+    ResourceContainerError, ResourceContainerId, ResourceMovement,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -24,26 +27,54 @@ pub enum VaultError {
     CouldNotTakeBucket,
     ProofError(ProofError),
     CouldNotCreateProof,
+    LockFeeNotRadixToken,
+    CouldNotLockFee,
+    VaultFrozen,
 }
 
 /// A persistent resource container.
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct Vault {
     container: Rc<RefCell<ResourceContainer>>,
+    /// Whether withdraws and deposits are blocked, except for recalls
+    frozen: bool,
+    /// Rules on calls to the listed vault functions, checked on top of those of the resource
+    access_rules: Option<AccessRules>,
 }
 
 impl Vault {
     pub fn new(container: ResourceContainer) -> Self {
         Self {
             container: Rc::new(RefCell::new(container)),
+            frozen: false,
+            access_rules: None,
         }
     }
 
+    pub fn with_access_rules(container: ResourceContainer, access_rules: AccessRules) -> Self {
+        Self {
+            container: Rc::new(RefCell::new(container)),
+            frozen: false,
+            access_rules: Some(access_rules),
+        }
+    }
+
+    /// Returns the authorization this vault's own access rules require for a function, if any.
+    ///
+    /// Functions not listed by the rules are only guarded by the resource, whatever the default
+    /// rule is.
+    pub fn get_auth(&self, function: &str) -> Option<MethodAuthorization> {
+        self.access_rules
+            .as_ref()
+            .and_then(|rules| rules.iter().find(|(method_name, _)| *method_name == function))
+            .map(|(_, method_auth)| convert(&Type::Unit, &Value::Unit, method_auth))
+    }
+
     pub fn put(&mut self, other: Bucket) -> Result<(), ResourceContainerError> {
         self.borrow_container_mut().put(other.into_container()?)
     }
 
-    fn take(&mut self, amount: Decimal) -> Result<ResourceContainer, VaultError> {
+    pub fn take(&mut self, amount: Decimal) -> Result<ResourceContainer, VaultError> {
         let container = self
             .borrow_container_mut()
             .take_by_amount(amount)
@@ -136,6 +167,10 @@ impl Vault {
         self.borrow_container().total_ids()
     }
 
+    pub fn total_amount_or_ids(&self) -> AmountOrIds {
+        self.borrow_container().total_amount_or_ids()
+    }
+
     pub fn is_locked(&self) -> bool {
         self.borrow_container().is_locked()
     }
@@ -144,6 +179,17 @@ impl Vault {
         self.borrow_container().is_empty()
     }
 
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    fn check_not_frozen(&self) -> Result<(), VaultError> {
+        if self.frozen {
+            return Err(VaultError::VaultFrozen);
+        }
+        Ok(())
+    }
+
     fn borrow_container(&self) -> Ref<ResourceContainer> {
         self.container.borrow()
     }
@@ -161,26 +207,69 @@ impl Vault {
     ) -> Result<ScryptoValue, VaultError> {
         match function {
             "put_into_vault" => {
+                self.check_not_frozen()?;
                 let bucket: scrypto::resource::Bucket =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let bucket = system_api.take_bucket(bucket.0).map_err(|_| VaultError::CouldNotTakeBucket)?;
+                let resource = bucket.total_amount_or_ids();
                 self.put(bucket).map_err(VaultError::ResourceContainerError)?;
+                system_api.record_resource_movement(ResourceMovement::VaultDeposit(vault_id), self.resource_address(), resource);
                 Ok(ScryptoValue::from_value(&()))
             }
             "take_from_vault" => {
+                self.check_not_frozen()?;
                 let amount: Decimal =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let container = self.take(amount)?;
+                system_api.record_resource_movement(ResourceMovement::VaultWithdrawal(vault_id), self.resource_address(), container.total_amount_or_ids());
                 let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
                 Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
             }
             "take_non_fungibles_from_vault" => {
+                self.check_not_frozen()?;
                 let non_fungible_ids: BTreeSet<NonFungibleId> =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let container = self.take_non_fungibles(&non_fungible_ids)?;
+                system_api.record_resource_movement(ResourceMovement::VaultWithdrawal(vault_id), self.resource_address(), container.total_amount_or_ids());
                 let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
                 Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
             }
+            "lock_fee" => {
+                self.check_not_frozen()?;
+                let amount: Decimal =
+                    scrypto_decode(&args[0].raw).map_err(VaultError::InvalidRequestData)?;
+                if self.resource_address() != RADIX_TOKEN {
+                    return Err(VaultError::LockFeeNotRadixToken);
+                }
+                let fee = self.take(amount)?;
+                system_api.lock_fee(vault_id, fee).map_err(|_| VaultError::CouldNotLockFee)?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "recall_from_vault" => {
+                let amount: Decimal =
+                    scrypto_decode(&args[0].raw).map_err(VaultError::InvalidRequestData)?;
+                let container = self.take(amount)?;
+                system_api.record_resource_movement(ResourceMovement::VaultWithdrawal(vault_id), self.resource_address(), container.total_amount_or_ids());
+                let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
+                Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
+            }
+            "recall_non_fungibles_from_vault" => {
+                let non_fungible_ids: BTreeSet<NonFungibleId> =
+                    scrypto_decode(&args[0].raw).map_err(VaultError::InvalidRequestData)?;
+                let container = self.take_non_fungibles(&non_fungible_ids)?;
+                system_api.record_resource_movement(ResourceMovement::VaultWithdrawal(vault_id), self.resource_address(), container.total_amount_or_ids());
+                let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
+                Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
+            }
+            "freeze_vault" => {
+                self.frozen = true;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "unfreeze_vault" => {
+                self.frozen = false;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "is_vault_frozen" => Ok(ScryptoValue::from_value(&self.frozen)),
             "get_vault_amount" => {
                 let amount = self.total_amount();
                 Ok(ScryptoValue::from_value(&amount))
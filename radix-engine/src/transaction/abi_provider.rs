@@ -1,6 +1,4 @@
-use sbor::Type;
 use scrypto::abi;
-use scrypto::buffer::*;
 use scrypto::crypto::hash;
 use scrypto::engine::types::*;
 use scrypto::rust::borrow::ToOwned;
@@ -54,24 +52,33 @@ impl BasicAbiProvider {
             .put_encoded_substate(package_address, &package, id_gen.next());
         self
     }
-}
 
-impl AbiProvider for BasicAbiProvider {
-    fn export_abi(
+    /// Exports the ABI of a blueprint, running its code on the given engine, so that the
+    /// modules cached by the engine can be reused.
+    pub fn export_abi_with_engine(
         &self,
         package_address: PackageAddress,
         blueprint_name: &str,
+        wasm_engine: &mut dyn WasmEngine,
     ) -> Result<abi::Blueprint, RuntimeError> {
         // Deterministic transaction context
         let mut ledger = self.substate_store.clone();
         let transaction_hash = hash([]);
 
         // Start a process and run abi generator
-        let mut track = Track::new(&mut ledger, transaction_hash, Vec::new());
+        let mut track = Track::new(
+            &mut ledger,
+            transaction_hash,
+            Vec::new(),
+            u32::MAX,
+            wasm_engine,
+        );
         let mut proc = track.start_process(self.trace);
-        let output: (Type, Vec<abi::Function>, Vec<abi::Method>) = proc
+        let output = proc
             .call_abi(package_address, blueprint_name)
-            .and_then(|rtn| scrypto_decode(&rtn.raw).map_err(RuntimeError::AbiValidationError))?;
+            .and_then(|rtn| {
+                Package::decode_abi(&rtn.raw).map_err(RuntimeError::AbiValidationError)
+            })?;
 
         // Return ABI
         Ok(abi::Blueprint {
@@ -79,8 +86,23 @@ impl AbiProvider for BasicAbiProvider {
             blueprint_name: blueprint_name.to_owned(),
             functions: output.1,
             methods: output.2,
+            events: output.3,
         })
     }
+}
+
+impl AbiProvider for BasicAbiProvider {
+    fn export_abi(
+        &self,
+        package_address: PackageAddress,
+        blueprint_name: &str,
+    ) -> Result<abi::Blueprint, RuntimeError> {
+        self.export_abi_with_engine(
+            package_address,
+            blueprint_name,
+            &mut DefaultWasmEngine::new(),
+        )
+    }
 
     fn export_abi_by_component(
         &self,
@@ -4,7 +4,7 @@ use scrypto::buffer::*;
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
 use scrypto::prelude::{AccessRuleNode, Burn, AccessRule, Mint, Withdraw};
-use scrypto::resource::{require, LOCKED};
+use scrypto::resource::{require, require_n_of, LOCKED};
 use scrypto::rust::borrow::ToOwned;
 use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::collections::*;
@@ -28,6 +28,8 @@ pub struct TransactionBuilder {
     id_validator: IdValidator,
     /// Instructions generated.
     instructions: Vec<Instruction>,
+    /// Header of the transaction, except the nonce which is given when building.
+    header: TransactionHeader,
 }
 
 impl TransactionBuilder {
@@ -36,9 +38,29 @@ impl TransactionBuilder {
         Self {
             id_validator: IdValidator::new(),
             instructions: Vec::new(),
+            header: TransactionHeader::default(),
         }
     }
 
+    /// Sets the network the transaction is intended for.
+    pub fn network_id(&mut self, network_id: u32) -> &mut Self {
+        self.header.network_id = network_id;
+        self
+    }
+
+    /// Sets the epochs in which the transaction can be committed.
+    pub fn epochs(&mut self, start_epoch_inclusive: u64, end_epoch_exclusive: u64) -> &mut Self {
+        self.header.start_epoch_inclusive = start_epoch_inclusive;
+        self.header.end_epoch_exclusive = end_epoch_exclusive;
+        self
+    }
+
+    /// Sets the notary, whose signature the transaction will require.
+    pub fn notary(&mut self, notary_public_key: PublicKey) -> &mut Self {
+        self.header.notary_public_key = Some(notary_public_key);
+        self
+    }
+
     /// Adds a raw instruction.
     pub fn add_instruction(
         &mut self,
@@ -101,7 +123,7 @@ impl TransactionBuilder {
             Instruction::CallMethodWithAllResources { .. } => {
                 self.id_validator.move_all_resources().unwrap();
             }
-            Instruction::PublishPackage { .. } | Instruction::Nonce { .. } => {}
+            Instruction::PublishPackage { .. } => {}
         }
 
         self.instructions.push(inst);
@@ -415,10 +437,9 @@ impl TransactionBuilder {
 
     /// Builds a transaction with the given nonce.
     pub fn build(&self, nonce: u64) -> Transaction {
-        let mut instructions = self.instructions.clone();
-        instructions.push(Instruction::Nonce { nonce });
-
-        Transaction { instructions }
+        let mut transaction = self.build_with_no_nonce();
+        transaction.add_nonce(nonce);
+        transaction
     }
 
     /// Builds a transaction with no nonce
@@ -426,6 +447,7 @@ impl TransactionBuilder {
     /// Nonce can be later filled by a third party or wallet.
     pub fn build_with_no_nonce(&self) -> Transaction {
         Transaction {
+            header: self.header.clone(),
             instructions: self.instructions.clone(),
         }
     }
@@ -615,6 +637,22 @@ impl TransactionBuilder {
         .0
     }
 
+    /// Creates an account with some initial resource, which can only be withdrawn from
+    /// with signatures by at least `threshold` of the given public keys.
+    pub fn new_multisig_account_with_resource(
+        &mut self,
+        threshold: u8,
+        public_keys: &[PublicKey],
+        bucket_id: BucketId,
+    ) -> &mut Self {
+        let signer_badges: Vec<NonFungibleAddress> = public_keys
+            .iter()
+            .map(NonFungibleAddress::from_public_key)
+            .collect();
+        let withdraw_auth = rule!(require_n_of(threshold, signer_badges));
+        self.new_account_with_resource(&withdraw_auth, bucket_id)
+    }
+
     /// Withdraws resource from an account.
     pub fn withdraw_from_account(
         &mut self,
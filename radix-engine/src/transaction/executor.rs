@@ -1,30 +1,45 @@
-use scrypto::crypto::hash;
+use lru::LruCache;
+use scrypto::buffer::scrypto_encode;
+use scrypto::crypto::{hash, Hash};
 use scrypto::engine::types::*;
 use scrypto::resource::*;
+use scrypto::rust::cell::RefCell;
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
-use scrypto::rust::string::ToString;
 use scrypto::{abi, rule, access_rule_node};
 
 use crate::engine::*;
 use crate::errors::*;
+use crate::fee::*;
 use crate::ledger::*;
 use crate::model::*;
 use crate::transaction::*;
 
+/// The number of blueprint ABIs an executor keeps in its cache.
+const ABI_CACHE_SIZE: usize = 128;
+
 /// An executor that runs transactions.
-pub struct TransactionExecutor<'l, L: SubstateStore> {
+pub struct TransactionExecutor<'l, L: SubstateStore, W: WasmEngine = DefaultWasmEngine> {
     substate_store: &'l mut L,
+    /// Shared with ABI exports, which only have an immutable reference to the executor
+    wasm_engine: RefCell<W>,
+    /// Exported blueprint ABIs, keyed by package address, code hash and blueprint name
+    abi_cache: RefCell<LruCache<(PackageAddress, Hash, String), abi::Blueprint>>,
     trace: bool,
+    cost_unit_limit: u32,
+    cost_unit_price: Decimal,
+    limits: ExecutionLimits,
 }
 
-impl<'l, L: SubstateStore> NonceProvider for TransactionExecutor<'l, L> {
+impl<'l, L: SubstateStore, W: WasmEngine> NonceProvider for TransactionExecutor<'l, L, W> {
     fn get_nonce<PKS: AsRef<[EcdsaPublicKey]>>(&self, _intended_signers: PKS) -> u64 {
         self.substate_store.get_nonce()
     }
 }
 
-impl<'l, L: SubstateStore> AbiProvider for TransactionExecutor<'l, L> {
+impl<'l, L: SubstateStore, W: WasmEngine> AbiProvider for TransactionExecutor<'l, L, W> {
     fn export_abi(
         &self,
         package_address: PackageAddress,
@@ -36,9 +51,24 @@ impl<'l, L: SubstateStore> AbiProvider for TransactionExecutor<'l, L> {
             .map(|(package, _)| package)
             .ok_or(RuntimeError::PackageNotFound(package_address))?;
 
-        BasicAbiProvider::new(self.trace)
+        let key = (
+            package_address,
+            package.code_hash(),
+            blueprint_name.to_string(),
+        );
+        if let Some(abi) = self.abi_cache.borrow_mut().get(&key) {
+            return Ok(abi.clone());
+        }
+
+        let abi = BasicAbiProvider::new(self.trace)
             .with_package(&package_address, package)
-            .export_abi(package_address, blueprint_name)
+            .export_abi_with_engine(
+                package_address,
+                blueprint_name,
+                &mut *self.wasm_engine.borrow_mut(),
+            )?;
+        self.abi_cache.borrow_mut().put(key, abi.clone());
+        Ok(abi)
     }
 
     fn export_abi_by_component(
@@ -50,25 +80,60 @@ impl<'l, L: SubstateStore> AbiProvider for TransactionExecutor<'l, L> {
             .get_decoded_substate(&component_address)
             .map(|(component, _)| component)
             .ok_or(RuntimeError::ComponentNotFound(component_address))?;
-        let package: Package = self
-            .substate_store
-            .get_decoded_substate(&component.package_address())
-            .map(|(package, _)| package)
-            .unwrap();
-        BasicAbiProvider::new(self.trace)
-            .with_package(&component.package_address(), package)
-            .export_abi(component.package_address(), component.blueprint_name())
+        self.export_abi(component.package_address(), component.blueprint_name())
     }
 }
 
 impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
     pub fn new(substate_store: &'l mut L, trace: bool) -> Self {
+        Self::with_wasm_engine(substate_store, DefaultWasmEngine::new(), trace)
+    }
+}
+
+impl<'l, L: SubstateStore, W: WasmEngine> TransactionExecutor<'l, L, W> {
+    /// Creates an executor which runs blueprints on the given WASM engine.
+    pub fn with_wasm_engine(substate_store: &'l mut L, wasm_engine: W, trace: bool) -> Self {
         Self {
             substate_store,
+            wasm_engine: RefCell::new(wasm_engine),
+            abi_cache: RefCell::new(LruCache::new(ABI_CACHE_SIZE)),
             trace,
+            cost_unit_limit: DEFAULT_COST_UNIT_LIMIT,
+            cost_unit_price: DEFAULT_COST_UNIT_PRICE,
+            limits: ExecutionLimits::default(),
         }
     }
 
+    /// Returns the maximum number of cost units a transaction can consume.
+    pub fn cost_unit_limit(&self) -> u32 {
+        self.cost_unit_limit
+    }
+
+    /// Sets the maximum number of cost units a transaction can consume.
+    pub fn set_cost_unit_limit(&mut self, cost_unit_limit: u32) {
+        self.cost_unit_limit = cost_unit_limit;
+    }
+
+    /// Returns the price of a cost unit, in XRD.
+    pub fn cost_unit_price(&self) -> Decimal {
+        self.cost_unit_price
+    }
+
+    /// Sets the price of a cost unit, in XRD.
+    pub fn set_cost_unit_price(&mut self, cost_unit_price: Decimal) {
+        self.cost_unit_price = cost_unit_price;
+    }
+
+    /// Returns the limits on call depth, memory and substate size enforced on transactions.
+    pub fn limits(&self) -> &ExecutionLimits {
+        &self.limits
+    }
+
+    /// Sets the limits on call depth, memory and substate size enforced on transactions.
+    pub fn set_limits(&mut self, limits: ExecutionLimits) {
+        self.limits = limits;
+    }
+
     /// Returns an immutable reference to the ledger.
     pub fn substate_store(&self) -> &L {
         self.substate_store
@@ -89,11 +154,22 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         (public_key, private_key)
     }
 
+    /// Generates a new ED25519 key pair.
+    pub fn new_ed25519_key_pair(&mut self) -> (Ed25519PublicKey, Ed25519PrivateKey) {
+        let private_key = Ed25519PrivateKey::from_bytes(
+            hash(self.substate_store.get_and_increase_nonce().to_le_bytes()).as_ref(),
+        )
+        .unwrap();
+        let public_key = private_key.public_key();
+        (public_key, private_key)
+    }
+
     /// Creates an account with 1,000,000 XRD in balance.
     pub fn new_account_with_auth_rule(&mut self, withdraw_auth: &AccessRule) -> ComponentAddress {
         let receipt = self
             .validate_and_execute(
                 &TransactionBuilder::new()
+                    .network_id(self.substate_store.get_network_id())
                     .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
                     .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
                         builder.new_account_with_resource(withdraw_auth, bucket_id)
@@ -125,6 +201,7 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         let receipt = self
             .validate_and_execute(
                 &TransactionBuilder::new()
+                    .network_id(self.substate_store.get_network_id())
                     .publish_package(code.as_ref())
                     .build(self.get_nonce([]))
                     .sign([]),
@@ -138,7 +215,7 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         }
     }
 
-    /// Overwrites a package.
+    /// Overwrites a package, in a state version of its own, so that it can be rolled back.
     pub fn overwrite_package(
         &mut self,
         package_address: PackageAddress,
@@ -148,8 +225,14 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         let mut id_gen = SubstateIdGenerator::new(tx_hash);
 
         let package = Package::new(code)?;
+        self.substate_store.begin_commit();
         self.substate_store
             .put_encoded_substate(&package_address, &package, id_gen.next());
+        self.complete_state_version(&[(
+            scrypto_encode(&package_address),
+            Some(scrypto_encode(&package)),
+        )]);
+        self.substate_store.end_commit();
         Ok(())
     }
 
@@ -158,56 +241,158 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         signed: &SignedTransaction,
     ) -> Result<Receipt, TransactionValidationError> {
         let validated = signed.validate()?;
-        let receipt = self.execute(validated);
-        Ok(receipt)
+        self.check_header(&validated)?;
+        Ok(self.execute(validated))
+    }
+
+    /// Checks the transaction is intended for this ledger at its current epoch, and has not
+    /// been committed before.
+    pub fn check_header(
+        &self,
+        validated: &ValidatedTransaction,
+    ) -> Result<(), TransactionValidationError> {
+        let header = &validated.header;
+        let network_id = self.substate_store.get_network_id();
+        if header.network_id != network_id {
+            return Err(TransactionValidationError::InvalidNetwork {
+                expected: network_id,
+                actual: header.network_id,
+            });
+        }
+        let current_epoch = self.substate_store.get_epoch();
+        if current_epoch < header.start_epoch_inclusive
+            || current_epoch >= header.end_epoch_exclusive
+        {
+            return Err(TransactionValidationError::EpochOutOfRange {
+                current_epoch,
+                start_epoch_inclusive: header.start_epoch_inclusive,
+                end_epoch_exclusive: header.end_epoch_exclusive,
+            });
+        }
+        if self.substate_store.is_intent_committed(&validated.raw_hash) {
+            return Err(TransactionValidationError::IntentAlreadyCommitted(
+                validated.raw_hash,
+            ));
+        }
+        Ok(())
     }
 
     pub fn execute(&mut self, validated: ValidatedTransaction) -> Receipt {
+        self.run(validated, None)
+    }
+
+    /// Runs a transaction against the current state and returns the receipt it would produce,
+    /// including the state updates, without committing anything or bumping the nonce.
+    pub fn preview(&mut self, validated: &ValidatedTransaction, flags: PreviewFlags) -> Receipt {
+        self.run(validated.clone(), Some(flags))
+    }
+
+    fn run(&mut self, validated: ValidatedTransaction, preview: Option<PreviewFlags>) -> Receipt {
         #[cfg(not(feature = "alloc"))]
         let now = std::time::Instant::now();
 
+        if preview.is_none() {
+            self.substate_store.begin_commit();
+        }
         let mut track = Track::new(
             self.substate_store,
             validated.raw_hash.clone(),
             validated.signers.clone(),
+            self.cost_unit_limit,
+            self.wasm_engine.get_mut(),
         );
-        let mut proc = track.start_process(self.trace);
+        track.set_limits(self.limits);
+        if matches!(preview, Some(PreviewFlags { skip_auth_check: true, .. })) {
+            track.disable_auth_check();
+        }
+        let tx_base_fee = track.fee_table().tx_base_fee();
+        let mut error = track.consume_cost_units(tx_base_fee).err();
 
         let txn_process = TransactionProcess::new(validated.clone());
-        let mut txn_snode = SNodeState::Transaction(txn_process);
-        let error = match proc.run(&mut txn_snode, "execute".to_string(), vec![]) {
-            Ok(_) => None,
-            Err(e) => Some(e),
-        };
+        let mut txn_snode = SNodeState::Transaction(Box::new(txn_process));
+        if error.is_none() {
+            let mut proc = track.start_process(self.trace);
+            error = match proc.run(&mut txn_snode, "execute".to_string(), vec![]) {
+                Ok(_) => None,
+                Err(e) => Some(e),
+            };
+        }
         let outputs = if let SNodeState::Transaction(txn_process) = txn_snode {
             txn_process.outputs().to_vec()
         } else {
             panic!("Should not get here");
         };
 
+        // charge for state updates and check the fee can be paid
+        if error.is_none() {
+            error = track.consume_substate_writes().err();
+        }
+        let cost_unit_consumed = track.cost_unit_counter().consumed();
+        if error.is_none() {
+            let required = self.cost_unit_price * cost_unit_consumed;
+            let locked = track.locked_fee();
+            if locked < required {
+                error = Some(RuntimeError::InsufficientFeeLocked { required, locked });
+            }
+        }
+
         // prepare data for receipts
         let new_package_addresses = track.new_package_addresses();
         let new_component_addresses = track.new_component_addresses();
         let new_resource_addresses = track.new_resource_addresses();
         let logs = track.logs().clone();
+        let execution_trace = track.execution_trace().clone();
+        // events of failed transactions are dropped
+        let events = if error.is_none() {
+            track.events().clone()
+        } else {
+            Vec::new()
+        };
 
         // commit state updates
-        let commit_receipt = if error.is_none() {
-            let receipt = track.commit();
-            self.substate_store.increase_nonce();
-            Some(receipt)
+        let mut substate_updates = None;
+        let (commit_receipt, fee_paid, resource_events) = if error.is_none() {
+            let fee_paid = track.pay_fees(cost_unit_consumed, self.cost_unit_price);
+            let resource_events = track.resource_events();
+            let receipt = if preview.is_some() {
+                track.preview_commit()
+            } else {
+                let receipt = track.commit();
+                substate_updates = Some(receipt.substate_updates());
+                receipt
+            };
+            (Some(receipt), fee_paid, resource_events)
         } else {
-            None
+            // state updates are discarded, but the locked fee is still charged
+            let fee_locks = track.fee_locks();
+            let mut fee_paid = Decimal::zero();
+            let mut resource_events = Vec::new();
+            if !fee_locks.is_empty() && self.cost_unit_price.is_positive() {
+                let mut track = Track::new(
+                    self.substate_store,
+                    validated.raw_hash,
+                    validated.signers.clone(),
+                    self.cost_unit_limit,
+                    self.wasm_engine.get_mut(),
+                );
+                for (component_address, vault_id, amount) in fee_locks {
+                    track.relock_fee(component_address, vault_id, amount);
+                }
+                fee_paid = track.pay_fees(cost_unit_consumed, self.cost_unit_price);
+                resource_events = track.resource_events();
+                if preview.is_none() {
+                    substate_updates = Some(track.commit().substate_updates());
+                }
+            }
+            (None, fee_paid, resource_events)
         };
 
-
-
         #[cfg(feature = "alloc")]
         let execution_time = None;
         #[cfg(not(feature = "alloc"))]
         let execution_time = Some(now.elapsed().as_millis());
 
-        Receipt {
+        let receipt = Receipt {
             commit_receipt,
             validated_transaction: validated.clone(),
             result: match error {
@@ -216,10 +401,62 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
             },
             outputs,
             logs,
+            events,
+            execution_trace,
+            resource_events,
             new_package_addresses,
             new_component_addresses,
             new_resource_addresses,
             execution_time,
+            fee_summary: FeeSummary {
+                cost_unit_limit: self.cost_unit_limit,
+                cost_unit_consumed,
+                cost_unit_price: self.cost_unit_price,
+                fee_paid,
+            },
+        };
+
+        // failed transactions are kept in the history too, if their fee has been committed
+        if let Some(substate_updates) = substate_updates {
+            self.complete_commit(&receipt, &substate_updates);
+        }
+        if preview.is_none() {
+            self.substate_store.end_commit();
         }
+        receipt
+    }
+
+    /// Completes the state version of a committed transaction and appends the transaction to
+    /// the history.
+    fn complete_commit(
+        &mut self,
+        receipt: &Receipt,
+        substate_updates: &[(Vec<u8>, Option<Vec<u8>>)],
+    ) {
+        let validated = &receipt.validated_transaction;
+        self.substate_store.increase_nonce();
+        let version = self.complete_state_version(substate_updates);
+        self.substate_store.put_committed_intent(validated.raw_hash);
+        let index = self.substate_store.get_transaction_count();
+        self.substate_store.put_transaction(CommittedTransaction {
+            hash: validated.raw_hash,
+            index,
+            state_version: version,
+            transaction: validated.signed_transaction.clone(),
+            receipt: receipt.summary(),
+        });
+    }
+
+    /// Completes the next state version, to which the given substate updates belong, and
+    /// returns it.
+    fn complete_state_version(
+        &mut self,
+        substate_updates: &[(Vec<u8>, Option<Vec<u8>>)],
+    ) -> u64 {
+        // read before the bump, as buffered writes may not be visible until the commit ends
+        let version = self.substate_store.get_state_version() + 1;
+        self.substate_store.increase_state_version();
+        self.substate_store.update_state_tree(version, substate_updates);
+        version
     }
 }
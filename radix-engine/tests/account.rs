@@ -2,6 +2,7 @@
 pub mod test_runner;
 
 use crate::test_runner::TestRunner;
+use radix_engine::engine::{BalanceChange, SubstateKey};
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
 use radix_engine::model::*;
@@ -122,3 +123,205 @@ fn test_account_balance() {
         ScryptoValue::from_value(&Decimal::from(1000000))
     );
 }
+
+#[test]
+fn transfer_should_be_in_balance_changes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(dec!("100"), RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.as_ref().expect("Should be okay");
+    let balance_changes = receipt.balance_changes();
+    assert_eq!(balance_changes.len(), 2);
+    assert_eq!(
+        balance_changes[&account][&RADIX_TOKEN],
+        BalanceChange::Fungible(dec!("-100"))
+    );
+    assert_eq!(
+        balance_changes[&other_account][&RADIX_TOKEN],
+        BalanceChange::Fungible(dec!("100"))
+    );
+}
+
+#[test]
+fn non_fungible_transfer_should_be_in_balance_changes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+    let resource_address = test_runner.create_non_fungible_resource(account);
+    let mut ids = BTreeSet::new();
+    ids.insert(NonFungibleId::from_u32(1));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_ids(&ids, resource_address, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.as_ref().expect("Should be okay");
+    let balance_changes = receipt.balance_changes();
+    assert_eq!(
+        balance_changes[&account][&resource_address],
+        BalanceChange::NonFungible {
+            added: BTreeSet::new(),
+            removed: ids.clone(),
+        }
+    );
+    assert_eq!(
+        balance_changes[&other_account][&resource_address],
+        BalanceChange::NonFungible {
+            added: ids,
+            removed: BTreeSet::new(),
+        }
+    );
+}
+
+#[test]
+fn unchanged_balance_should_not_be_in_balance_changes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.as_ref().expect("Should be okay");
+    assert!(receipt.balance_changes().is_empty());
+}
+
+#[test]
+fn transfer_should_be_in_substate_changes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(dec!("100"), RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.as_ref().expect("Should be okay");
+    let commit_receipt = receipt.commit_receipt.unwrap();
+    assert_eq!(
+        commit_receipt.substate_changes.len(),
+        commit_receipt.up_substates.len()
+    );
+    let change = commit_receipt
+        .substate_changes
+        .iter()
+        .find(|change| matches!(change.key, SubstateKey::Vault(owner, _) if owner == account))
+        .unwrap();
+    let old_vault: radix_engine::model::Vault =
+        scrypto_decode(change.old_value.as_ref().unwrap()).unwrap();
+    let new_vault: radix_engine::model::Vault =
+        scrypto_decode(change.new_value.as_ref().unwrap()).unwrap();
+    assert_eq!(old_vault.total_amount(), dec!("1000000"));
+    assert_eq!(new_vault.total_amount(), dec!("999900"));
+}
+
+#[test]
+fn new_account_should_be_in_substate_changes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", args![])
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.new_account_with_resource(&rule!(allow_all), bucket_id)
+        })
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.as_ref().expect("Should be okay");
+    let account = receipt.new_component_addresses[0];
+    let commit_receipt = receipt.commit_receipt.unwrap();
+    let change = commit_receipt
+        .substate_changes
+        .iter()
+        .find(|change| change.key == SubstateKey::Component(account))
+        .unwrap();
+    assert_eq!(change.old_value, None);
+    let component: radix_engine::model::Component =
+        scrypto_decode(change.new_value.as_ref().unwrap()).unwrap();
+    assert_eq!(component.blueprint_name(), "Account");
+    let change = commit_receipt
+        .substate_changes
+        .iter()
+        .find(|change| matches!(change.key, SubstateKey::Vault(owner, _) if owner == SYSTEM_COMPONENT))
+        .unwrap();
+    assert!(change.old_value.is_some());
+}
+
+#[test]
+fn substate_changes_should_be_in_deterministic_order() {
+    // Arrange
+    let run = || {
+        let mut substate_store = InMemorySubstateStore::with_bootstrap();
+        let mut test_runner = TestRunner::new(&mut substate_store);
+        let mut builder = test_runner.new_transaction_builder();
+        builder.call_method(SYSTEM_COMPONENT, "free_xrd", args![]);
+        for _ in 0..10 {
+            builder.take_from_worktop_by_amount(dec!("1"), RADIX_TOKEN, |builder, bucket_id| {
+                builder.new_account_with_resource(&rule!(allow_all), bucket_id)
+            });
+        }
+        builder.take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.new_account_with_resource(&rule!(allow_all), bucket_id)
+        });
+        let transaction = builder.build(test_runner.get_nonce([])).sign([]);
+        let receipt = test_runner.validate_and_execute(&transaction);
+        receipt.result.as_ref().expect("Should be okay");
+        receipt
+            .commit_receipt
+            .unwrap()
+            .substate_changes
+            .into_iter()
+            .map(|change| change.key)
+            .collect::<Vec<SubstateKey>>()
+    };
+
+    // Act
+    let first = run();
+    let second = run();
+
+    // Assert
+    assert_eq!(first, second);
+}
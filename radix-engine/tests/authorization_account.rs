@@ -6,6 +6,7 @@ pub mod test_runner;
 use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::SignedTransaction;
 use scrypto::prelude::*;
 
 fn test_auth_rule(
@@ -328,3 +329,118 @@ fn cannot_withdraw_from_my_any_xrd_auth_account_with_less_than_amount_of_proof()
     let error = receipt.result.expect_err("Should be an error");
     assert_auth_error!(error);
 }
+
+#[test]
+fn can_withdraw_from_my_ed25519_account_with_ed25519_signature() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, sk, auth) = test_runner.new_ed25519_key_pair_with_pk_address();
+    let account = test_runner.new_account_with_auth_rule(&rule!(require(auth)));
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign_ed25519([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn cannot_withdraw_from_my_ed25519_account_with_ecdsa_signature() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, auth) = test_runner.new_ed25519_key_pair_with_pk_address();
+    let account = test_runner.new_account_with_auth_rule(&rule!(require(auth)));
+    let (pk, sk, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
+
+#[test]
+fn can_withdraw_from_my_2_of_2_account_with_ecdsa_and_ed25519_signatures() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk0, sk0, auth0) = test_runner.new_key_pair_with_pk_address();
+    let (_, sk1, auth1) = test_runner.new_ed25519_key_pair_with_pk_address();
+    let account =
+        test_runner.new_account_with_auth_rule(&rule!(require_all_of(vec![auth0, auth1])));
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk0]));
+    let mut signatures = transaction.clone().sign([&sk0]).signatures;
+    signatures.extend(transaction.clone().sign_ed25519([&sk1]).signatures);
+    let receipt = test_runner.validate_and_execute(&SignedTransaction {
+        transaction,
+        signatures,
+    });
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn can_withdraw_from_my_2_of_3_multisig_account_with_signatures_added_in_separate_steps() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk0, sk0) = test_runner.new_key_pair();
+    let (pk1, _) = test_runner.new_key_pair();
+    let (pk2, sk2) = test_runner.new_key_pair();
+    let (_, _, other_account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.new_multisig_account_with_resource(
+                2,
+                &[pk0.into(), pk1.into(), pk2.into()],
+                bucket_id,
+            )
+        })
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let account = receipt.new_component_addresses[0];
+
+    // Act
+    let unsigned = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk0, pk2]))
+        .into_signed();
+    let partially_signed = unsigned.sign([&sk0]);
+    let partial_receipt = test_runner.validate_and_execute(&partially_signed);
+    let fully_signed = partially_signed.sign([&sk2]);
+    let receipt = test_runner.validate_and_execute(&fully_signed);
+
+    // Assert
+    let error = partial_receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+    receipt.result.expect("Should be okay");
+}
@@ -6,6 +6,7 @@ pub mod test_runner;
 use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::VaultError;
 use scrypto::prelude::*;
 
 enum Action {
@@ -136,3 +137,169 @@ fn cannot_deposit_with_wrong_auth() {
 fn can_deposit_with_right_auth() {
     test_resource_auth(Action::Deposit, true, true, false);
 }
+
+enum RegulatedAction {
+    Recall,
+    Freeze,
+}
+
+fn test_regulated_resource_auth(action: RegulatedAction, use_other_auth: bool, expect_err: bool) {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+    let (token_address, recall_auth, freeze_auth) = test_runner.create_regulated_token(account);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(token_address, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+    let vault_id = test_runner.get_vault_id(other_account, token_address);
+    let package = test_runner.publish_package("resource_creator");
+
+    let (function, args, right_auth, other_auth) = match action {
+        RegulatedAction::Recall => (
+            "recall",
+            args![token_address, other_account, vault_id, Decimal::one()],
+            recall_auth,
+            freeze_auth,
+        ),
+        RegulatedAction::Freeze => (
+            "freeze",
+            args![token_address, other_account, vault_id],
+            freeze_auth,
+            recall_auth,
+        ),
+    };
+    let auth_to_use = if use_other_auth {
+        other_auth
+    } else {
+        right_auth
+    };
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(auth_to_use, account)
+        .call_function(package, "ResourceCreator", function, args)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    if expect_err {
+        let err = receipt.result.expect_err("Should be a runtime error");
+        assert_auth_error!(err);
+    } else {
+        receipt.result.expect("Should be okay.");
+    }
+}
+
+#[test]
+fn can_recall_with_auth() {
+    test_regulated_resource_auth(RegulatedAction::Recall, false, false);
+}
+
+#[test]
+fn cannot_recall_with_wrong_auth() {
+    test_regulated_resource_auth(RegulatedAction::Recall, true, true);
+}
+
+#[test]
+fn can_freeze_with_auth() {
+    test_regulated_resource_auth(RegulatedAction::Freeze, false, false);
+}
+
+#[test]
+fn cannot_freeze_with_wrong_auth() {
+    test_regulated_resource_auth(RegulatedAction::Freeze, true, true);
+}
+
+#[test]
+fn frozen_vault_should_block_withdraws_until_unfrozen() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (token_address, _, freeze_auth) = test_runner.create_regulated_token(account);
+    let vault_id = test_runner.get_vault_id(account, token_address);
+    let package = test_runner.publish_package("resource_creator");
+    let set_frozen = |test_runner: &mut TestRunner, function: &str| {
+        let transaction = test_runner
+            .new_transaction_builder()
+            .create_proof_from_account(freeze_auth, account)
+            .call_function(
+                package,
+                "ResourceCreator",
+                function,
+                args![token_address, account, vault_id],
+            )
+            .build(test_runner.get_nonce([pk]))
+            .sign([&sk]);
+        test_runner
+            .validate_and_execute(&transaction)
+            .result
+            .expect("Should be okay");
+    };
+    let withdraw = |test_runner: &mut TestRunner| {
+        let transaction = test_runner
+            .new_transaction_builder()
+            .withdraw_from_account_by_amount(Decimal::one(), token_address, account)
+            .call_method_with_all_resources(account, "deposit_batch")
+            .build(test_runner.get_nonce([pk]))
+            .sign([&sk]);
+        test_runner.validate_and_execute(&transaction).result.err()
+    };
+
+    // Act
+    set_frozen(&mut test_runner, "freeze");
+    let frozen_error = withdraw(&mut test_runner);
+    set_frozen(&mut test_runner, "unfreeze");
+    let unfrozen_error = withdraw(&mut test_runner);
+
+    // Assert
+    assert_eq!(
+        frozen_error,
+        Some(RuntimeError::VaultError(VaultError::VaultFrozen))
+    );
+    assert_eq!(unfrozen_error, None);
+}
+
+#[test]
+fn cannot_recall_through_another_resource_manager() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (token_address, recall_auth, _) = test_runner.create_regulated_token(account);
+    let vault_id = test_runner.get_vault_id(account, token_address);
+    let package = test_runner.publish_package("resource_creator");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(recall_auth, account)
+        .call_function(
+            package,
+            "ResourceCreator",
+            "recall",
+            args![RADIX_TOKEN, account, vault_id, Decimal::one()],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result.expect_err("Should be a runtime error"),
+        RuntimeError::VaultResourceMismatch(vault_id, RADIX_TOKEN)
+    );
+}
@@ -4,6 +4,7 @@ pub mod test_runner;
 use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::Receipt;
 use scrypto::prelude::*;
 
 #[test]
@@ -66,3 +67,100 @@ fn can_withdraw_restricted_transfer_from_my_account_with_auth() {
     // Assert
     receipt.result.expect("Should be okay.");
 }
+
+fn test_vault_access_rules(use_admin_badge: bool) -> Receipt {
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let admin_badge = test_runner.create_fungible_resource(Decimal::one(), 0, account);
+    let package = test_runner.publish_package("vault");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Treasury", "new", args![admin_badge])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let treasury = receipt.new_component_addresses[0];
+
+    let mut builder = test_runner.new_transaction_builder();
+    if use_admin_badge {
+        builder.create_proof_from_account(admin_badge, account);
+    }
+    let transaction = builder
+        .call_method(treasury, "withdraw", args![Decimal::one()])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    test_runner.validate_and_execute(&transaction)
+}
+
+#[test]
+fn can_take_from_vault_with_access_rules_with_auth() {
+    // Act
+    let receipt = test_vault_access_rules(true);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn cannot_take_from_vault_with_access_rules_without_auth() {
+    // Act
+    let receipt = test_vault_access_rules(false);
+
+    // Assert
+    let err = receipt.result.expect_err("Should be a runtime error");
+    assert_auth_error!(err);
+}
+
+#[test]
+fn can_use_unlisted_functions_of_vault_with_partial_access_rules_without_auth() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let admin_badge = test_runner.create_fungible_resource(Decimal::one(), 0, account);
+    let package = test_runner.publish_package("vault");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Treasury", "new", args![admin_badge])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay.");
+    let treasury = receipt.new_component_addresses[0];
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(treasury, "balance", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn cannot_create_vault_with_schema_path_access_rules() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("vault");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Treasury", "new_with_schema_path_rule", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let err = receipt.result.expect_err("Should be a runtime error");
+    assert_eq!(
+        err,
+        RuntimeError::SchemaPathInVaultAccessRules("take_from_vault".to_string())
+    );
+}
@@ -114,6 +114,175 @@ fn reentrancy_should_not_be_possible() {
     assert_eq!(error, RuntimeError::ComponentReentrancy(component_address))
 }
 
+#[test]
+fn immutable_reentrancy_should_be_possible() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ReentrantComponent", "new", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "read_self", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn mutable_call_during_immutable_borrow_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ReentrantComponent", "new", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "read_then_call_self", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(error, RuntimeError::ComponentReentrancy(component_address))
+}
+
+#[test]
+fn immutable_call_during_mutable_borrow_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ReentrantComponent", "new", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "call_then_read_self", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(error, RuntimeError::ComponentReentrancy(component_address))
+}
+
+#[test]
+fn vault_withdrawal_from_immutable_method_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ImmutableComponent", "new", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "take_from_vault", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(error, RuntimeError::ImmutableComponentWrite(component_address))
+}
+
+#[test]
+fn vault_freeze_from_immutable_method_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ImmutableComponent", "new", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "freeze_vault", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(error, RuntimeError::ImmutableComponentWrite(component_address))
+}
+
+#[test]
+fn lazy_map_write_from_immutable_method_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "ImmutableComponent", "new", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "write_lazy_map", vec![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(error, RuntimeError::ImmutableComponentWrite(component_address))
+}
+
 #[test]
 fn missing_component_address_should_cause_error() {
     // Arrange
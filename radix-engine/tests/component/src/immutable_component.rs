@@ -0,0 +1,43 @@
+use scrypto::engine::api::*;
+use scrypto::engine::call_engine;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct ImmutableComponent {
+        vault: Vault,
+        map: LazyMap<String, String>,
+    }
+
+    impl ImmutableComponent {
+        pub fn new() -> ComponentAddress {
+            let bucket = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_MAXIMUM)
+                .initial_supply(100);
+            Self {
+                vault: Vault::with_bucket(bucket),
+                map: LazyMap::new(),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn take_from_vault(&self) -> Bucket {
+            // Bypasses `&mut self` by taking through a copy of the vault handle
+            Vault(self.vault.0).take(1)
+        }
+
+        pub fn freeze_vault(&self) {
+            // Invokes the vault directly, as `ResourceManager::freeze` goes through the resource
+            let input = InvokeSNodeInput {
+                snode_ref: SNodeRef::VaultRef(self.vault.0),
+                function: "freeze_vault".to_string(),
+                args: args![],
+            };
+            let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        }
+
+        pub fn write_lazy_map(&self) {
+            self.map.insert("key".to_owned(), "value".to_owned());
+        }
+    }
+}
@@ -3,6 +3,7 @@ pub mod auth_list_component;
 pub mod chess;
 pub mod component;
 pub mod cross_component;
+pub mod immutable_component;
 pub mod package;
 pub mod reentrant_component;
 pub mod typed_cross_component_call;
@@ -10,11 +10,34 @@ blueprint! {
 
         pub fn func(&mut self) {}
 
+        pub fn read(&self) {}
+
         pub fn call_self(&mut self) {
             if let ScryptoActor::Component(addr) = Runtime::actor().actor() {
                 let self_component = borrow_component!(addr);
                 self_component.call("func", vec![])
             }
         }
+
+        pub fn read_self(&self) {
+            if let ScryptoActor::Component(addr) = Runtime::actor().actor() {
+                let self_component = borrow_component!(addr);
+                self_component.call("read", vec![])
+            }
+        }
+
+        pub fn read_then_call_self(&self) {
+            if let ScryptoActor::Component(addr) = Runtime::actor().actor() {
+                let self_component = borrow_component!(addr);
+                self_component.call("func", vec![])
+            }
+        }
+
+        pub fn call_then_read_self(&mut self) {
+            if let ScryptoActor::Component(addr) = Runtime::actor().actor() {
+                let self_component = borrow_component!(addr);
+                self_component.call("read", vec![])
+            }
+        }
     }
 }
@@ -0,0 +1,357 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::*;
+use radix_engine::model::{AmountOrIds, ResourceEvent};
+use scrypto::abi;
+use scrypto::engine::api::{EmitEventInput, EMIT_EVENT};
+use scrypto::prelude::*;
+
+#[derive(TypeId, Encode, Decode, Describe)]
+struct Deposited {
+    amount: Decimal,
+}
+
+#[test]
+fn emitted_event_should_be_in_receipt() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("event");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "EventEmitter", "emit", args![dec!("10")])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(receipt.events.len(), 1);
+    let event = &receipt.events[0];
+    assert_eq!(event.actor.package_address(), &package);
+    assert_eq!(event.actor.blueprint_name(), "EventEmitter");
+    assert_eq!(event.name, "Deposited");
+    let data: Deposited = scrypto_decode(&event.data).unwrap();
+    assert_eq!(data.amount, dec!("10"));
+}
+
+#[test]
+fn events_of_failed_transaction_should_be_dropped() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("event");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "EventEmitter", "emit_and_panic", args![dec!("10")])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect_err("Should be an error.");
+    assert!(receipt.events.is_empty());
+}
+
+#[test]
+fn undeclared_event_should_cause_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("event");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "EventEmitter", "emit_undeclared", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(
+        error,
+        RuntimeError::EventNotDeclared("Undeclared".to_string())
+    );
+}
+
+#[test]
+fn undeclared_event_from_wasm_should_cause_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let input = scrypto_encode(&EmitEventInput {
+        name: "Undeclared".to_string(),
+        data: scrypto_encode(&()),
+    });
+    let data: String = input.iter().map(|b| format!("\\{:02x}", b)).collect();
+    let code = TestRunner::compile_wat(
+        "Test",
+        &format!(
+            r#"
+                (import "env" "radix_engine" (func $radix_engine (param i32 i32 i32) (result i32)))
+                (data (i32.const 2048) "{data}")
+                (func $main (result i32)
+                    (call $radix_engine (i32.const {op}) (i32.const 2048) (i32.const {len}))
+                )
+            "#,
+            data = data,
+            op = EMIT_EVENT,
+            len = input.len()
+        ),
+    );
+    let transaction = test_runner
+        .new_transaction_builder()
+        .publish_package(&code)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let package = test_runner
+        .validate_and_execute(&transaction)
+        .new_package_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Test", "f", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(
+        error,
+        RuntimeError::EventNotDeclared("Undeclared".to_string())
+    );
+    assert!(receipt.events.is_empty());
+}
+
+/// Compiles a blueprint which declares the `Deposited` event and emits an event of the given
+/// name and data.
+fn compile_event_emitter(name: &str, data: Vec<u8>) -> Vec<u8> {
+    let input = scrypto_encode(&EmitEventInput {
+        name: name.to_string(),
+        data,
+    });
+    let data: String = input.iter().map(|b| format!("\\{:02x}", b)).collect();
+    TestRunner::compile_wat_with_events(
+        "Test",
+        vec![abi::Event {
+            name: "Deposited".to_string(),
+            schema: Deposited::describe(),
+        }],
+        &format!(
+            r#"
+                (import "env" "radix_engine" (func $radix_engine (param i32 i32 i32) (result i32)))
+                (data (i32.const 2048) "{data}")
+                (func $main (result i32)
+                    (call $radix_engine (i32.const {op}) (i32.const 2048) (i32.const {len}))
+                    drop
+                    (i32.const 4096)
+                )
+                (data (i32.const 4096) "\01\00\00\00\00")
+                (func $alloc (param i32) (result i32)
+                    (i32.const 8192)
+                )
+                (export "scrypto_alloc" (func $alloc))
+            "#,
+            data = data,
+            op = EMIT_EVENT,
+            len = input.len()
+        ),
+    )
+}
+
+#[test]
+fn event_matching_schema_from_wasm_should_be_in_receipt() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let code = compile_event_emitter(
+        "Deposited",
+        scrypto_encode(&Deposited { amount: dec!("10") }),
+    );
+    let transaction = test_runner
+        .new_transaction_builder()
+        .publish_package(&code)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let package = test_runner
+        .validate_and_execute(&transaction)
+        .new_package_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Test", "f", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(receipt.events.len(), 1);
+    assert_eq!(receipt.events[0].name, "Deposited");
+}
+
+#[test]
+fn event_not_matching_schema_from_wasm_should_cause_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let code = compile_event_emitter("Deposited", scrypto_encode(&"10".to_string()));
+    let transaction = test_runner
+        .new_transaction_builder()
+        .publish_package(&code)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let package = test_runner
+        .validate_and_execute(&transaction)
+        .new_package_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Test", "f", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(
+        error,
+        RuntimeError::EventSchemaMismatch("Deposited".to_string())
+    );
+    assert!(receipt.events.is_empty());
+}
+
+#[test]
+fn events_should_be_declared_in_abi() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("event");
+
+    // Act
+    let abi = test_runner.export_abi(package, "EventEmitter");
+
+    // Assert
+    assert_eq!(abi.events.len(), 1);
+    assert_eq!(abi.events[0].name, "Deposited");
+}
+
+#[test]
+fn vault_movements_should_be_in_receipt() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", args![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(receipt.resource_events.len(), 2);
+    match &receipt.resource_events[0] {
+        ResourceEvent::VaultWithdrawal {
+            component_address,
+            resource_address,
+            resource,
+            ..
+        } => {
+            assert_eq!(*component_address, Some(SYSTEM_COMPONENT));
+            assert_eq!(*resource_address, RADIX_TOKEN);
+            assert_eq!(*resource, AmountOrIds::Amount(dec!("1000000")));
+        }
+        e => panic!("Unexpected event: {:?}", e),
+    }
+    match &receipt.resource_events[1] {
+        ResourceEvent::VaultDeposit {
+            component_address,
+            resource_address,
+            resource,
+            ..
+        } => {
+            assert_eq!(*component_address, Some(account));
+            assert_eq!(*resource_address, RADIX_TOKEN);
+            assert_eq!(*resource, AmountOrIds::Amount(dec!("1000000")));
+        }
+        e => panic!("Unexpected event: {:?}", e),
+    }
+}
+
+#[test]
+fn mint_and_burn_should_be_in_receipt() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .new_badge_fixed(HashMap::new(), dec!("1"))
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let badge = test_runner
+        .validate_and_execute(&transaction)
+        .new_resource_addresses[0];
+    let transaction = test_runner
+        .new_transaction_builder()
+        .new_token_mutable(HashMap::new(), badge)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let token = test_runner
+        .validate_and_execute(&transaction)
+        .new_resource_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(badge, account)
+        .mint(dec!("100"), token)
+        .burn(dec!("40"), token)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+    assert_eq!(
+        receipt.resource_events[0],
+        ResourceEvent::Mint {
+            resource_address: token,
+            resource: AmountOrIds::Amount(dec!("100")),
+        }
+    );
+    assert_eq!(
+        receipt.resource_events[1],
+        ResourceEvent::Burn {
+            resource_address: token,
+            resource: AmountOrIds::Amount(dec!("40")),
+        }
+    );
+    assert!(matches!(
+        &receipt.resource_events[2],
+        ResourceEvent::VaultDeposit { component_address, resource_address, resource, .. }
+            if *component_address == Some(account)
+                && *resource_address == token
+                && *resource == AmountOrIds::Amount(dec!("60"))
+    ));
+}
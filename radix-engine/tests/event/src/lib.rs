@@ -0,0 +1,29 @@
+use scrypto::prelude::*;
+
+#[derive(TypeId, Encode, Decode, Describe)]
+pub struct Deposited {
+    pub amount: Decimal,
+}
+
+#[derive(TypeId, Encode, Decode, Describe)]
+pub struct Undeclared {}
+
+blueprint! {
+    #[events(Deposited)]
+    struct EventEmitter {}
+
+    impl EventEmitter {
+        pub fn emit(amount: Decimal) {
+            Runtime::emit_event(Deposited { amount });
+        }
+
+        pub fn emit_and_panic(amount: Decimal) {
+            Runtime::emit_event(Deposited { amount });
+            panic!("Oops");
+        }
+
+        pub fn emit_undeclared() {
+            Runtime::emit_event(Undeclared {});
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::*;
+use scrypto::core::{SNodeRef, ScryptoActor};
+use scrypto::prelude::*;
+
+#[test]
+fn call_tree_should_be_in_receipt() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", args![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.as_ref().expect("Should be okay.");
+    let free_xrd = &receipt.execution_trace[0];
+    assert!(matches!(
+        free_xrd.snode_ref,
+        SNodeRef::Scrypto(ScryptoActor::Component(component_address)) if component_address == SYSTEM_COMPONENT
+    ));
+    assert_eq!(free_xrd.function, "free_xrd");
+    assert_eq!(free_xrd.buckets_out.len(), 1);
+    assert_eq!(free_xrd.buckets_out[0].1, RADIX_TOKEN);
+    assert_eq!(
+        free_xrd.buckets_out[0].2,
+        AmountOrIds::Amount(dec!("1000000"))
+    );
+    let take_from_vault = &free_xrd.children[0];
+    assert!(matches!(take_from_vault.snode_ref, SNodeRef::VaultRef(_)));
+    assert_eq!(take_from_vault.function, "take_from_vault");
+    assert!(free_xrd.cost_units >= take_from_vault.cost_units);
+
+    let deposit_batch = receipt
+        .execution_trace
+        .iter()
+        .find(|trace| trace.function == "deposit_batch")
+        .unwrap();
+    assert_eq!(deposit_batch.buckets_in.len(), 1);
+    assert_eq!(
+        deposit_batch.buckets_in[0].2,
+        AmountOrIds::Amount(dec!("1000000"))
+    );
+    assert!(deposit_batch
+        .children
+        .iter()
+        .any(|trace| trace.function == "put_into_vault"));
+}
+
+#[test]
+fn failed_invocation_should_be_in_execution_trace() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_auth_error!(error);
+    let withdraw = &receipt.execution_trace[0];
+    assert_eq!(withdraw.function, "withdraw");
+    assert_eq!(withdraw.output, Err(error));
+    assert!(withdraw.children.is_empty());
+}
@@ -0,0 +1,202 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::fee::{CostUnitCounterError, FeeTable};
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+fn create_fee_component(executor: &mut TransactionExecutor<InMemorySubstateStore>) -> ComponentAddress {
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "fee")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(package, "Fee", "new", args![scrypto::resource::Bucket(bucket_id)])
+        })
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    receipt.new_component_addresses[0]
+}
+
+fn get_balance(executor: &mut TransactionExecutor<InMemorySubstateStore>, component: ComponentAddress) -> Decimal {
+    let cost_unit_price = executor.cost_unit_price();
+    executor.set_cost_unit_price(Decimal::zero());
+    let transaction = TransactionBuilder::new()
+        .call_method(component, "balance", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    executor.set_cost_unit_price(cost_unit_price);
+    scrypto_decode(&receipt.outputs[0].raw).unwrap()
+}
+
+#[test]
+fn infinite_loop_should_exceed_cost_unit_limit() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let code = TestRunner::compile_wat(
+        "Test",
+        r#"
+            (func $main (result i32)
+                (loop $spin
+                    br $spin
+                )
+                i32.const 0
+            )
+        "#,
+    );
+    let package = executor.publish_package(code).unwrap();
+    executor.set_cost_unit_limit(1_000_000);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Test", "f", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::CostingError(CostUnitCounterError::LimitExceeded {
+            limit: 1_000_000,
+            consumed: receipt.fee_summary.cost_unit_consumed,
+        })
+    );
+}
+
+#[test]
+fn each_snode_invocation_should_consume_cost_units() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let transaction = TransactionBuilder::new()
+        .clear_auth_zone()
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let cost_unit_consumed = receipt.fee_summary.cost_unit_consumed;
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .clear_auth_zone()
+        .clear_auth_zone()
+        .clear_auth_zone()
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert_eq!(
+        receipt.fee_summary.cost_unit_consumed,
+        cost_unit_consumed + 2 * FeeTable::new().invoke_snode()
+    );
+}
+
+#[test]
+fn locked_fee_should_be_paid_and_rest_refunded() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let component = create_fee_component(&mut executor);
+    let balance = get_balance(&mut executor, component);
+    executor.set_cost_unit_price(Decimal::from("0.001"));
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(component, "lock_fee", args![Decimal::from(1000)])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let fee_summary = receipt.fee_summary;
+    assert_eq!(
+        fee_summary.fee_paid,
+        Decimal::from("0.001") * fee_summary.cost_unit_consumed
+    );
+    assert_eq!(
+        get_balance(&mut executor, component),
+        balance - fee_summary.fee_paid
+    );
+}
+
+#[test]
+fn transaction_without_locked_fee_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let component = create_fee_component(&mut executor);
+    executor.set_cost_unit_price(Decimal::from("0.001"));
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(component, "balance", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    match runtime_error {
+        RuntimeError::InsufficientFeeLocked { .. } => {}
+        _ => panic!("Should be insufficient fee error but was {}", runtime_error),
+    }
+}
+
+#[test]
+fn locked_fee_should_be_paid_by_failed_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let component = create_fee_component(&mut executor);
+    let balance = get_balance(&mut executor, component);
+    executor.set_cost_unit_price(Decimal::from("0.001"));
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(component, "lock_fee_and_panic", args![Decimal::from(1000)])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect_err("Should be runtime error");
+    let fee_summary = receipt.fee_summary;
+    assert!(fee_summary.fee_paid.is_positive());
+    assert_eq!(
+        get_balance(&mut executor, component),
+        balance - fee_summary.fee_paid
+    );
+}
+
+#[test]
+fn failed_transaction_paying_fee_should_increase_nonce() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let component = create_fee_component(&mut executor);
+    executor.set_cost_unit_price(Decimal::from("0.001"));
+    let nonce = executor.get_nonce([]);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(component, "lock_fee_and_panic", args![Decimal::from(1000)])
+        .build(nonce)
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect_err("Should be runtime error");
+    assert!(receipt.fee_summary.fee_paid.is_positive());
+    assert_eq!(executor.get_nonce([]), nonce + 1);
+}
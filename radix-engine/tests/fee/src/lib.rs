@@ -0,0 +1,30 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Fee {
+        xrd: Vault,
+    }
+
+    impl Fee {
+        pub fn new(xrd: Bucket) -> ComponentAddress {
+            Self {
+                xrd: Vault::with_bucket(xrd),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn lock_fee(&mut self, amount: Decimal) {
+            self.xrd.lock_fee(amount);
+        }
+
+        pub fn lock_fee_and_panic(&mut self, amount: Decimal) {
+            self.xrd.lock_fee(amount);
+            panic!("Oops");
+        }
+
+        pub fn balance(&self) -> Decimal {
+            self.xrd.amount()
+        }
+    }
+}
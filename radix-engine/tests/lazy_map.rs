@@ -1,3 +1,4 @@
+use radix_engine::engine::*;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
 use radix_engine::transaction::*;
@@ -227,3 +228,305 @@ fn create_lazy_map_and_put() {
     // Assert
     assert!(receipt.result.is_ok());
 }
+
+#[test]
+fn remove_from_new_lazy_map_should_return_value() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "lazy_map")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "LazyMapTest", "new_lazy_map_with_remove", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let removed: Option<String> = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(removed, Some("world".to_owned()));
+}
+
+#[test]
+fn remove_from_committed_lazy_map_should_delete_entry() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "lazy_map")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "LazyMapTest",
+            "new_lazy_map_with_entries",
+            args![3u32],
+        )
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(component_address, "remove", args!["key1".to_owned()])
+        .call_method(component_address, "contains", args!["key1".to_owned()])
+        .call_method(
+            component_address,
+            "range",
+            args![Option::<String>::None, 10u32],
+        )
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let removed: Option<String> = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(removed, Some("value1".to_owned()));
+    let contains: bool = scrypto_decode(&receipt.outputs[1].raw).unwrap();
+    assert!(!contains);
+    let entries: Vec<(String, String)> = scrypto_decode(&receipt.outputs[2].raw).unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            ("key0".to_owned(), "value0".to_owned()),
+            ("key2".to_owned(), "value2".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn range_should_page_through_lazy_map() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "lazy_map")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "LazyMapTest",
+            "new_lazy_map_with_entries",
+            args![5u32],
+        )
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(
+            component_address,
+            "range",
+            args![Option::<String>::None, 2u32],
+        )
+        .call_method(
+            component_address,
+            "range",
+            args![Some("key1".to_owned()), 2u32],
+        )
+        .call_method(
+            component_address,
+            "range",
+            args![Some("key3".to_owned()), 2u32],
+        )
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let keys: Vec<Vec<String>> = receipt.outputs[0..3]
+        .iter()
+        .map(|output| {
+            let entries: Vec<(String, String)> = scrypto_decode(&output.raw).unwrap();
+            entries.into_iter().map(|(key, _)| key).collect()
+        })
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            vec!["key0".to_owned(), "key1".to_owned()],
+            vec!["key2".to_owned(), "key3".to_owned()],
+            vec!["key4".to_owned()],
+        ]
+    );
+}
+
+#[test]
+fn range_above_max_page_size_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "lazy_map")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "LazyMapTest",
+            "new_lazy_map_with_entries",
+            args![5u32],
+        )
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(
+            component_address,
+            "range",
+            args![Option::<String>::None, MAX_LAZY_MAP_PAGE_SIZE + 1],
+        )
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::MaxLazyMapPageSizeExceeded(MAX_LAZY_MAP_PAGE_SIZE)
+    );
+}
+
+#[test]
+fn cannot_remove_lazy_map_entry_holding_lazy_map() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "lazy_map")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "LazyMapTest",
+            "new_lazy_map_with_entries",
+            args![0u32],
+        )
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(component_address, "remove_lazy_map", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    match runtime_error {
+        RuntimeError::LazyMapRemoved(_) => {}
+        _ => panic!("Should be lazy map removed error but was {}", runtime_error),
+    }
+}
+
+#[test]
+fn removed_entries_should_be_tombstoned_until_commit() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::new();
+    let mut wasm_engine = DefaultWasmEngine::new();
+    let component_address = ComponentAddress([1u8; 26]);
+    let lazy_map_id = (hash([]), 0);
+    let key = |k: &str| scrypto_encode(&k.to_owned());
+    let mut track = Track::new(
+        &mut ledger,
+        hash([]),
+        Vec::new(),
+        u32::MAX,
+        &mut wasm_engine,
+    );
+    for k in ["a", "b", "c"] {
+        track.put_lazy_map_entry(component_address, lazy_map_id, key(k), key(k));
+    }
+    track.commit();
+
+    // Act
+    let mut track = Track::new(
+        &mut ledger,
+        hash([1]),
+        Vec::new(),
+        u32::MAX,
+        &mut wasm_engine,
+    );
+    let removed = track.remove_lazy_map_entry(component_address, &lazy_map_id, &key("b"));
+    track.put_lazy_map_entry(component_address, lazy_map_id, key("d"), key("d"));
+    let all = track.list_lazy_map_entries(component_address, &lazy_map_id, None, 10);
+    let page = track.list_lazy_map_entries(component_address, &lazy_map_id, Some(&key("a")), 1);
+    let receipt = track.commit();
+
+    // Assert
+    assert_eq!(removed, Some(key("b")));
+    let all_keys: Vec<Vec<u8>> = all.into_iter().map(|(k, _)| k).collect();
+    assert_eq!(all_keys, vec![key("a"), key("c"), key("d")]);
+    assert_eq!(page, vec![(key("c"), key("c"))]);
+    let change = receipt
+        .substate_changes
+        .iter()
+        .find(|change| {
+            change.key == SubstateKey::LazyMapEntry(component_address, lazy_map_id, key("b"))
+        })
+        .expect("Removal should be in substate changes");
+    assert_eq!(change.old_value, Some(key("b")));
+    assert_eq!(change.new_value, None);
+    let mut child_key = scrypto_encode(&lazy_map_id);
+    child_key.extend(key("b"));
+    assert!(ledger
+        .get_child_substate(&component_address, &child_key)
+        .is_none());
+}
+
+#[test]
+fn listing_lazy_map_entries_should_meter_shadowed_reads() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::new();
+    let mut wasm_engine = DefaultWasmEngine::new();
+    let component_address = ComponentAddress([1u8; 26]);
+    let lazy_map_id = (hash([]), 0);
+    let key = |k: &str| scrypto_encode(&k.to_owned());
+    let mut track = Track::new(
+        &mut ledger,
+        hash([]),
+        Vec::new(),
+        u32::MAX,
+        &mut wasm_engine,
+    );
+    for k in ["a", "b", "c"] {
+        track.put_lazy_map_entry(component_address, lazy_map_id, key(k), key(k));
+    }
+    track.commit();
+    let mut track = Track::new(
+        &mut ledger,
+        hash([1]),
+        Vec::new(),
+        u32::MAX,
+        &mut wasm_engine,
+    );
+    for k in ["a", "b", "c"] {
+        track.put_lazy_map_entry(component_address, lazy_map_id, key(k), key("x"));
+    }
+    let consumed = track.cost_unit_counter().consumed();
+
+    // Act
+    let entries = track.list_lazy_map_entries(component_address, &lazy_map_id, None, 1);
+
+    // Assert
+    assert_eq!(entries, vec![(key("a"), key("x"))]);
+    assert_eq!(
+        track.cost_unit_counter().consumed() - consumed,
+        3 * key("a").len() as u32 * track.fee_table().substate_read_per_byte()
+    );
+}
@@ -84,6 +84,57 @@ blueprint! {
             .globalize()
         }
 
+        pub fn new_lazy_map_with_remove() -> Option<String> {
+            let map = LazyMap::new();
+            map.insert("hello".to_owned(), "world".to_owned());
+            let removed = map.remove(&"hello".to_owned());
+            assert!(!map.contains_key(&"hello".to_owned()));
+            LazyMapTest {
+                map,
+                vector: Vec::new(),
+                lazy_maps: LazyMap::new(),
+            }
+            .instantiate()
+            .globalize();
+            removed
+        }
+
+        pub fn new_lazy_map_with_entries(count: u32) -> ComponentAddress {
+            let map = LazyMap::new();
+            for i in 0..count {
+                map.insert(format!("key{}", i), format!("value{}", i));
+            }
+            let lazy_maps = LazyMap::new();
+            lazy_maps.insert("hello".to_owned(), LazyMap::new());
+            LazyMapTest {
+                map,
+                vector: Vec::new(),
+                lazy_maps,
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn insert(&mut self, key: String, value: String) {
+            self.map.insert(key, value);
+        }
+
+        pub fn remove(&mut self, key: String) -> Option<String> {
+            self.map.remove(&key)
+        }
+
+        pub fn contains(&self, key: String) -> bool {
+            self.map.contains_key(&key)
+        }
+
+        pub fn range(&self, after: Option<String>, limit: u32) -> Vec<(String, String)> {
+            self.map.range(after.as_ref(), limit)
+        }
+
+        pub fn remove_lazy_map(&mut self) -> () {
+            self.lazy_maps.remove(&"hello".to_owned());
+        }
+
         pub fn overwrite_lazy_map(&mut self) -> () {
             self.lazy_maps.insert("hello".to_owned(), LazyMap::new())
         }
@@ -0,0 +1,187 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::engine::{ExecutionLimits, MAX_WASM_MEMORY_PAGES};
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn nested_call_should_exceed_call_depth_limit() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    executor.set_limits(ExecutionLimits {
+        max_call_depth: 1,
+        ..Default::default()
+    });
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(runtime_error, RuntimeError::MaxCallDepthExceeded(1));
+}
+
+#[test]
+fn memory_grow_beyond_max_pages_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let code = TestRunner::compile_wat(
+        "Test",
+        &format!(
+            r#"
+            (func $main (result i32)
+                (if (i32.eq (memory.grow (i32.const {})) (i32.const -1))
+                    (then unreachable)
+                )
+                i32.const 0
+            )
+            "#,
+            MAX_WASM_MEMORY_PAGES
+        ),
+    );
+    let package = executor.publish_package(code).unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Test", "f", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(runtime_error, RuntimeError::InvokeError);
+}
+
+#[test]
+fn package_declaring_more_memory_should_exceed_memory_limit() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let code = TestRunner::compile_wat(
+        "Test",
+        r#"
+            (func $main (result i32)
+                i32.const 0
+            )
+        "#,
+    );
+    let package = executor.publish_package(code).unwrap();
+    executor.set_limits(ExecutionLimits {
+        max_wasm_memory_pages: 64,
+        ..Default::default()
+    });
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Test", "f", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(runtime_error, RuntimeError::MaxWasmMemoryPagesExceeded(64));
+}
+
+#[test]
+fn large_component_state_should_exceed_substate_size_limit() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    executor.set_limits(ExecutionLimits {
+        max_substate_size: 1,
+        ..Default::default()
+    });
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.new_account_with_resource(&rule!(allow_all), bucket_id)
+        })
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(runtime_error, RuntimeError::MaxSubstateSizeExceeded(1));
+}
+
+#[test]
+fn minted_bucket_should_exceed_bucket_limit() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    executor.set_limits(ExecutionLimits {
+        max_buckets_per_frame: 0,
+        ..Default::default()
+    });
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(runtime_error, RuntimeError::MaxBucketsPerFrameExceeded(0));
+}
+
+#[test]
+fn bucket_proof_should_exceed_proof_limit() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    executor.set_limits(ExecutionLimits {
+        max_proofs_per_frame: 0,
+        ..Default::default()
+    });
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.create_proof_from_bucket(bucket_id, |builder, _| builder)
+        })
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(runtime_error, RuntimeError::MaxProofsPerFrameExceeded(0));
+}
+
+#[test]
+fn default_limits_should_allow_simple_transfer() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.new_account_with_resource(&rule!(allow_all), bucket_id)
+        })
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
@@ -2,7 +2,9 @@
 pub mod test_runner;
 
 use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
+use radix_engine::model::{NonFungible, ResourceManagerError};
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
 
@@ -78,6 +80,64 @@ fn can_burn_non_fungible() {
     receipt.result.expect("Should be okay.");
 }
 
+#[test]
+fn can_mint_non_fungibles_with_sequential_ids() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "create_sequential_non_fungible",
+            vec![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn cannot_mint_non_fungible_with_wrong_id_type() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "mint_non_fungible_with_wrong_id_type",
+            vec![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::ResourceManagerError(ResourceManagerError::InvalidNonFungibleId(
+            NonFungibleId::from_u32(1)
+        ))
+    );
+}
+
 #[test]
 fn test_non_fungible() {
     let mut ledger = InMemorySubstateStore::with_bootstrap();
@@ -141,3 +201,63 @@ fn test_singleton_non_fungible() {
     println!("{:?}", receipt);
     assert!(receipt.result.is_ok());
 }
+
+#[derive(NonFungibleData, Debug, PartialEq)]
+struct Ticket {
+    seat: u32,
+    #[scrypto(mutable)]
+    checked_in: bool,
+}
+
+#[test]
+fn can_list_non_fungibles_page_by_page() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let resource_address = ResourceAddress([9u8; 26]);
+    for seat in 1..=5u32 {
+        let ticket = Ticket {
+            seat,
+            checked_in: false,
+        };
+        let non_fungible = NonFungible::new(ticket.immutable_data(), ticket.mutable_data());
+        substate_store.put_encoded_child_substate(
+            &resource_address,
+            &NonFungibleId::from_u32(seat),
+            &Some(non_fungible),
+            (Hash([0u8; 32]), seat),
+        );
+    }
+    // Burnt non-fungibles are kept as empty substates
+    substate_store.put_encoded_child_substate(
+        &resource_address,
+        &NonFungibleId::from_u32(3),
+        &Option::<NonFungible>::None,
+        (Hash([0u8; 32]), 6),
+    );
+
+    // Act
+    let first_page = substate_store.get_non_fungibles(resource_address, None, 2);
+    let second_page =
+        substate_store.get_non_fungibles(resource_address, Some(&first_page[1].id), 2);
+    let third_page =
+        substate_store.get_non_fungibles(resource_address, Some(&second_page[1].id), 2);
+
+    // Assert
+    let ids = |page: &Vec<NonFungibleEntry>| page.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        ids(&first_page),
+        vec![NonFungibleId::from_u32(1), NonFungibleId::from_u32(2)]
+    );
+    assert_eq!(
+        ids(&second_page),
+        vec![NonFungibleId::from_u32(4), NonFungibleId::from_u32(5)]
+    );
+    assert!(third_page.is_empty());
+    assert_eq!(
+        second_page[0].data::<Ticket>(),
+        Ok(Ticket {
+            seat: 4,
+            checked_in: false
+        })
+    );
+}
@@ -91,6 +91,61 @@ blueprint! {
                 ])
         }
 
+        pub fn create_sequential_non_fungible() -> (Bucket, Bucket) {
+            let mint_badge = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(1);
+            let resource_address = ResourceBuilder::new_non_fungible()
+                .id_type(NonFungibleIdType::U64)
+                .mintable(rule!(require(mint_badge.resource_address())), LOCKED)
+                .no_initial_supply();
+            let resource_manager = borrow_resource_manager!(resource_address);
+            assert_eq!(resource_manager.non_fungible_id_type(), NonFungibleIdType::U64);
+
+            let bucket = mint_badge.authorize(|| {
+                let sandwich = || Sandwich {
+                    name: "Sequential".to_owned(),
+                    available: true,
+                };
+                let mut bucket = resource_manager.mint_next_non_fungible(sandwich());
+                bucket.put(
+                    resource_manager.mint_non_fungible(&NonFungibleId::from_u64(2), sandwich()),
+                );
+                bucket.put(resource_manager.mint_next_non_fungible(sandwich()));
+                bucket
+            });
+            assert_eq!(
+                bucket.non_fungible_ids(),
+                BTreeSet::from([
+                    NonFungibleId::from_u64(1),
+                    NonFungibleId::from_u64(2),
+                    NonFungibleId::from_u64(3)
+                ])
+            );
+
+            (mint_badge, bucket)
+        }
+
+        pub fn mint_non_fungible_with_wrong_id_type() -> (Bucket, Bucket) {
+            let mint_badge = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(1);
+            let resource_address = ResourceBuilder::new_non_fungible()
+                .id_type(NonFungibleIdType::U64)
+                .mintable(rule!(require(mint_badge.resource_address())), LOCKED)
+                .no_initial_supply();
+            let non_fungible = mint_badge.authorize(|| {
+                borrow_resource_manager!(resource_address).mint_non_fungible(
+                    &NonFungibleId::from_u32(1),
+                    Sandwich {
+                        name: "Wrong".to_owned(),
+                        available: true,
+                    },
+                )
+            });
+            (mint_badge, non_fungible)
+        }
+
         pub fn verify_does_not_exist(address: NonFungibleAddress) {
             assert_eq!(
                 borrow_resource_manager!(address.resource_address())
@@ -3,10 +3,37 @@ pub mod test_runner;
 
 use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
-use radix_engine::errors::WasmValidationError::NoValidMemoryExport;
+use radix_engine::errors::WasmValidationError;
+use radix_engine::errors::WasmValidationError::{
+    AbiCostUnitLimitExceeded, NoValidMemoryExport, ReservedImportNotAllowed, TooManyMemoryPages,
+};
+use radix_engine::engine::{WasmEngine, WasmInstance, WasmiEngine};
 use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::transaction::*;
 use scrypto::prelude::*;
 use radix_engine::model::PackageError;
+use scrypto::crypto::Hash;
+use scrypto::engine::types::PackageAddress;
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// A WASM engine which counts the modules it instantiates.
+struct CountingWasmEngine {
+    inner: WasmiEngine,
+    instantiations: Rc<Cell<usize>>,
+}
+
+impl WasmEngine for CountingWasmEngine {
+    fn instantiate(
+        &mut self,
+        package_address: PackageAddress,
+        code_hash: Hash,
+        code: &[u8],
+    ) -> Result<Box<dyn WasmInstance>, WasmValidationError> {
+        self.instantiations.set(self.instantiations.get() + 1);
+        self.inner.instantiate(package_address, code_hash, code)
+    }
+}
 
 #[test]
 fn missing_memory_should_cause_error() {
@@ -101,3 +128,205 @@ fn zero_return_len_should_cause_data_validation_error() {
         panic!("{} should be data validation error", error);
     }
 }
+
+#[test]
+fn reserved_import_should_cause_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+
+    // Act
+    let code: Vec<u8> = wabt::wat2wasm(
+        r#"
+            (module
+                (import "env" "gas" (func $gas (param i32)))
+                (memory $0 1)
+                (export "memory" (memory $0))
+            )
+            "#,
+    )
+    .expect("failed to parse wat");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .publish_package(&code)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be error.");
+    assert_eq!(
+        error,
+        RuntimeError::PackageError(PackageError::WasmValidationError(ReservedImportNotAllowed))
+    );
+}
+
+#[test]
+fn too_large_initial_memory_should_cause_error() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+
+    // Act
+    let code: Vec<u8> = wabt::wat2wasm(
+        r#"
+            (module
+                (memory $0 1000)
+                (export "memory" (memory $0))
+            )
+            "#,
+    )
+    .expect("failed to parse wat");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .publish_package(&code)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be error.");
+    assert_eq!(
+        error,
+        RuntimeError::PackageError(PackageError::WasmValidationError(TooManyMemoryPages))
+    );
+}
+
+#[test]
+fn infinite_recursion_should_exceed_max_stack_height() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let code = TestRunner::compile_wat(
+        "Recursion",
+        r#"
+            (func $main (result i32)
+                call $main
+            )
+        "#,
+    );
+    let transaction = test_runner
+        .new_transaction_builder()
+        .publish_package(&code)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let package = test_runner.validate_and_execute(&transaction).new_package_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Recursion", "something", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert_eq!(error, RuntimeError::MaxStackHeightExceeded);
+}
+
+#[test]
+fn infinite_loop_in_abi_export_should_exceed_cost_unit_limit() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+
+    // Act
+    let code: Vec<u8> = wabt::wat2wasm(
+        r#"
+            (module
+                (memory $0 1)
+                (func $abi (result i32)
+                    (loop $spin
+                        br $spin
+                    )
+                    i32.const 0
+                )
+                (export "memory" (memory $0))
+                (export "Test_abi" (func $abi))
+            )
+            "#,
+    )
+    .expect("failed to parse wat");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .publish_package(&code)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be error.");
+    assert_eq!(
+        error,
+        RuntimeError::PackageError(PackageError::WasmValidationError(AbiCostUnitLimitExceeded))
+    );
+}
+
+#[test]
+fn evicted_module_should_be_recompiled() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::with_wasm_engine(
+        &mut substate_store,
+        WasmiEngine::with_cache_size(1),
+        false,
+    );
+    let code = TestRunner::compile_wat(
+        "Unit",
+        r#"
+            (data (i32.const 2048) "\01\00\00\00\00")
+            (func $main (result i32)
+                i32.const 2048
+            )
+        "#,
+    );
+    let package1 = executor.publish_package(code.clone()).unwrap();
+    let package2 = executor.publish_package(code).unwrap();
+
+    // Act & Assert
+    for package in [package1, package2, package1, package1, package2] {
+        let transaction = TransactionBuilder::new()
+            .call_function(package, "Unit", "f", args![])
+            .build(executor.get_nonce([]))
+            .sign([]);
+        let receipt = executor.validate_and_execute(&transaction).unwrap();
+        receipt.result.expect("Should be okay.");
+    }
+}
+
+#[test]
+fn repeated_abi_export_should_not_reinstantiate_module() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let instantiations = Rc::new(Cell::new(0));
+    let mut executor = TransactionExecutor::with_wasm_engine(
+        &mut substate_store,
+        CountingWasmEngine {
+            inner: WasmiEngine::new(),
+            instantiations: instantiations.clone(),
+        },
+        false,
+    );
+    let code = TestRunner::compile_wat(
+        "Unit",
+        r#"
+            (func $main (result i32)
+                unreachable
+            )
+        "#,
+    );
+    let package = executor.publish_package(code).unwrap();
+
+    // Act
+    let before = instantiations.get();
+    let abi = executor.export_abi(package, "Unit").unwrap();
+    let after_first_export = instantiations.get();
+    let cached_abi = executor.export_abi(package, "Unit").unwrap();
+    let after_second_export = instantiations.get();
+
+    // Assert
+    assert_eq!(after_first_export, before + 1);
+    assert_eq!(after_second_export, after_first_export);
+    assert_eq!(cached_abi.blueprint_name, abi.blueprint_name);
+}
@@ -0,0 +1,116 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::engine::BalanceChange;
+use radix_engine::errors::{RuntimeError, TransactionValidationError};
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::*;
+use scrypto::prelude::*;
+use scrypto::values::ScryptoValue;
+
+#[test]
+fn preview_should_not_commit() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+    let nonce = test_runner.get_nonce([pk]);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(dec!("100"), RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(nonce)
+        .sign([&sk]);
+
+    // Act
+    let receipt = test_runner.preview(&transaction, PreviewFlags::default());
+
+    // Assert
+    receipt.result.as_ref().expect("Should be okay");
+    assert!(receipt.commit_receipt.is_some());
+    assert_eq!(
+        receipt.balance_changes()[&account][&RADIX_TOKEN],
+        BalanceChange::Fungible(dec!("-100"))
+    );
+    assert_eq!(test_runner.get_nonce([pk]), nonce);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(account, "balance", args![RADIX_TOKEN])
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    assert_eq!(
+        receipt.outputs[0],
+        ScryptoValue::from_value(&Decimal::from(1000000))
+    );
+}
+
+#[test]
+fn preview_can_skip_auth_check() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+
+    // Act
+    let receipt = test_runner.preview(&transaction, PreviewFlags::default());
+    let unchecked_receipt = test_runner.preview(
+        &transaction,
+        PreviewFlags {
+            skip_auth_check: true,
+            ..Default::default()
+        },
+    );
+
+    // Assert
+    let error = receipt.result.expect_err("Should be runtime error");
+    assert_auth_error!(error);
+    unchecked_receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn preview_can_skip_signature_check() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let nonce = test_runner.get_nonce([pk]);
+    let signatures = test_runner
+        .new_transaction_builder()
+        .build(nonce)
+        .sign([&sk])
+        .signatures;
+    let transaction = SignedTransaction {
+        transaction: test_runner
+            .new_transaction_builder()
+            .withdraw_from_account(RADIX_TOKEN, account)
+            .call_method_with_all_resources(account, "deposit_batch")
+            .build(nonce),
+        signatures,
+    };
+
+    // Act
+    let receipt = test_runner.preview(
+        &transaction,
+        PreviewFlags {
+            skip_signature_check: true,
+            ..Default::default()
+        },
+    );
+
+    // Assert
+    assert_eq!(
+        transaction.validate().err(),
+        Some(TransactionValidationError::InvalidSignature)
+    );
+    receipt.result.expect("Should be okay");
+}
@@ -94,3 +94,92 @@ fn mint_too_much_should_fail() {
         RuntimeError::ResourceManagerError(ResourceManagerError::MaxMintAmountExceeded)
     );
 }
+
+#[test]
+fn mint_up_to_max_supply_should_succeed() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "resource")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "ResourceTest",
+            "create_capped_fungible_and_mint",
+            args![dec!("100"), dec!("60"), dec!("40")],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn mint_over_max_supply_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "resource")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "ResourceTest",
+            "create_capped_fungible_and_mint",
+            args![dec!("100"), dec!("60"), dec!("41")],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::ResourceManagerError(ResourceManagerError::MaxSupplyExceeded)
+    );
+}
+
+#[test]
+fn initial_supply_over_max_supply_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "resource")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(
+            package,
+            "ResourceTest",
+            "create_capped_fungible_and_mint",
+            args![dec!("100"), dec!("101"), dec!("0")],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::ResourceManagerError(ResourceManagerError::MaxSupplyExceeded)
+    );
+}
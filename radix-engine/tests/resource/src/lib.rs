@@ -34,6 +34,25 @@ blueprint! {
             (badge, tokens, token_address)
         }
 
+        pub fn create_capped_fungible_and_mint(
+            max_supply: Decimal,
+            initial_supply: Decimal,
+            amount: Decimal,
+        ) -> (Bucket, Bucket, Bucket) {
+            let badge = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(1);
+            let initial_tokens = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .max_supply(max_supply)
+                .mintable(rule!(require(badge.resource_address())), LOCKED)
+                .initial_supply(initial_supply);
+            let resource_manager = borrow_resource_manager!(initial_tokens.resource_address());
+            assert_eq!(resource_manager.max_supply(), Some(max_supply));
+            let minted_tokens = badge.authorize(|| resource_manager.mint(amount));
+            (badge, initial_tokens, minted_tokens)
+        }
+
         pub fn create_fungible_wrong_resource_flags_should_fail() -> ResourceAddress {
             let token_address = ResourceBuilder::new_fungible()
                 .divisibility(DIVISIBILITY_MAXIMUM)
@@ -1,3 +1,4 @@
+use scrypto::engine::types::VaultId;
 use scrypto::prelude::*;
 
 #[derive(NonFungibleData)]
@@ -52,6 +53,42 @@ blueprint! {
                 .initial_supply(5)
         }
 
+        pub fn create_regulated_token(
+            recall_auth: ResourceAddress,
+            freeze_auth: ResourceAddress,
+        ) -> Bucket {
+            ResourceBuilder::new_fungible()
+                .divisibility(0)
+                .recallable(rule!(require(recall_auth)), LOCKED)
+                .freezable(rule!(require(freeze_auth)), LOCKED)
+                .initial_supply(5)
+        }
+
+        pub fn recall(
+            resource_address: ResourceAddress,
+            component_address: ComponentAddress,
+            vault_id: VaultId,
+            amount: Decimal,
+        ) -> Bucket {
+            borrow_resource_manager!(resource_address).recall(component_address, vault_id, amount)
+        }
+
+        pub fn freeze(
+            resource_address: ResourceAddress,
+            component_address: ComponentAddress,
+            vault_id: VaultId,
+        ) {
+            borrow_resource_manager!(resource_address).freeze(component_address, vault_id);
+        }
+
+        pub fn unfreeze(
+            resource_address: ResourceAddress,
+            component_address: ComponentAddress,
+            vault_id: VaultId,
+        ) {
+            borrow_resource_manager!(resource_address).unfreeze(component_address, vault_id);
+        }
+
         pub fn set_mintable(resource_address: ResourceAddress, auth_address: ResourceAddress) {
             borrow_resource_manager!(resource_address).set_mintable(rule!(require(auth_address)));
         }
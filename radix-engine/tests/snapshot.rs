@@ -0,0 +1,140 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::TransactionValidationError;
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::buffer::{scrypto_decode, scrypto_encode};
+use scrypto::prelude::*;
+
+/// Returns the IDs of all substates, as their values and physical IDs depend on hash map order.
+fn substate_ids(snapshot: &LedgerSnapshot) -> Vec<Vec<u8>> {
+    snapshot
+        .substates
+        .iter()
+        .chain(snapshot.child_substates.iter())
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+#[test]
+fn snapshot_should_survive_encoding_and_import() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut ledger);
+    test_runner.new_account();
+    let snapshot = test_runner.snapshot();
+
+    // Act
+    let decoded: LedgerSnapshot = scrypto_decode(&scrypto_encode(&snapshot)).unwrap();
+    let mut other_ledger = InMemorySubstateStore::new();
+    let other_test_runner = TestRunner::from_snapshot(&mut other_ledger, decoded);
+
+    // Assert
+    assert_eq!(
+        scrypto_encode(&other_test_runner.snapshot()),
+        scrypto_encode(&snapshot)
+    );
+}
+
+#[test]
+fn test_runners_from_same_snapshot_should_reach_same_state() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut ledger);
+    let (_, _, account) = test_runner.new_account();
+    let snapshot = test_runner.snapshot();
+
+    // Act
+    let run = || {
+        let mut ledger = InMemorySubstateStore::new();
+        let mut test_runner = TestRunner::from_snapshot(&mut ledger, snapshot.clone());
+        let transaction = test_runner
+            .new_transaction_builder()
+            .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+            .call_method_with_all_resources(account, "deposit_batch")
+            .build(test_runner.get_nonce([]))
+            .sign([]);
+        let receipt = test_runner.validate_and_execute(&transaction);
+        receipt.result.expect("Should be okay");
+        let (_, _, new_account) = test_runner.new_account();
+        (new_account, substate_ids(&test_runner.snapshot()))
+    };
+    let (first_account, first_state) = run();
+    let (second_account, second_state) = run();
+
+    // Assert
+    assert_eq!(first_account, second_account);
+    assert_eq!(first_state, second_state);
+}
+
+#[test]
+fn transaction_committed_before_snapshot_should_not_be_replayed_after_import() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+    executor
+        .validate_and_execute(&transaction)
+        .unwrap()
+        .result
+        .expect("Should be okay");
+    let snapshot = executor.substate_store().export_snapshot();
+
+    // Act
+    let mut other_ledger = InMemorySubstateStore::new();
+    other_ledger.import_snapshot(snapshot);
+    let mut other_executor = TransactionExecutor::new(&mut other_ledger, false);
+    let replay_result = other_executor.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        replay_result.err(),
+        Some(TransactionValidationError::IntentAlreadyCommitted(
+            transaction.transaction.raw_hash()
+        ))
+    );
+}
+
+#[test]
+fn imported_snapshot_should_be_base_state_version() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    TestRunner::new(&mut ledger).new_account();
+    let snapshot = ledger.export_snapshot();
+
+    // Act
+    let mut other_ledger = InMemorySubstateStore::new();
+    other_ledger.import_snapshot(snapshot.clone());
+    other_ledger.rollback_to(snapshot.state_version);
+
+    // Assert
+    assert_eq!(
+        other_ledger.get_base_state_version(),
+        snapshot.state_version
+    );
+    assert_eq!(
+        substate_ids(&other_ledger.export_snapshot()),
+        substate_ids(&snapshot)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Can't roll back to before the base state version")]
+fn rollback_to_before_imported_snapshot_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    TestRunner::new(&mut ledger).new_account();
+    let snapshot = ledger.export_snapshot();
+    let mut other_ledger = InMemorySubstateStore::new();
+    other_ledger.import_snapshot(snapshot.clone());
+
+    // Act
+    other_ledger.rollback_to(snapshot.state_version - 1);
+}
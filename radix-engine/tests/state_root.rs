@@ -0,0 +1,139 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::buffer::scrypto_encode;
+use scrypto::prelude::*;
+
+fn transfer_free_xrd(test_runner: &mut TestRunner, account: ComponentAddress) {
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn same_transactions_should_produce_same_state_root() {
+    // Arrange
+    let run = || {
+        let mut ledger = InMemorySubstateStore::with_bootstrap();
+        let mut test_runner = TestRunner::new(&mut ledger);
+        let (_, _, account) = test_runner.new_account();
+        transfer_free_xrd(&mut test_runner, account);
+        ledger
+    };
+
+    // Act
+    let first_ledger = run();
+    let second_ledger = run();
+
+    // Assert
+    let version = first_ledger.get_state_version();
+    assert_eq!(second_ledger.get_state_version(), version);
+    assert_eq!(
+        first_ledger.get_state_root(version),
+        second_ledger.get_state_root(version)
+    );
+    assert_ne!(
+        first_ledger.get_state_root(version),
+        first_ledger.get_state_root(version - 1)
+    );
+}
+
+#[test]
+fn imported_snapshot_should_have_same_state_root() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut ledger);
+    let (_, _, account) = test_runner.new_account();
+    transfer_free_xrd(&mut test_runner, account);
+    let snapshot = test_runner.snapshot();
+
+    // Act
+    let mut other_ledger = InMemorySubstateStore::new();
+    TestRunner::from_snapshot(&mut other_ledger, snapshot);
+
+    // Assert
+    let version = ledger.get_state_version();
+    assert_eq!(
+        other_ledger.get_state_root(version),
+        ledger.get_state_root(version)
+    );
+}
+
+#[test]
+fn substate_proof_should_verify_against_state_root() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut ledger);
+    let (_, _, account) = test_runner.new_account();
+    let version = ledger.get_state_version();
+    let state_root = ledger.get_state_root(version);
+    let id = scrypto_encode(&account);
+    let value = ledger.get_substate(&account).unwrap().value;
+    let missing_id = scrypto_encode(&ComponentAddress([1u8; 26]));
+
+    // Act
+    let proof = ledger.get_substate_proof(&id, version);
+    let missing_proof = ledger.get_substate_proof(&missing_id, version);
+
+    // Assert
+    assert!(proof.verify(&state_root, &id, Some(&value)));
+    assert!(!proof.verify(&state_root, &id, Some(&[0u8])));
+    assert!(!proof.verify(&state_root, &id, None));
+    assert!(!proof.verify(&ledger.get_state_root(version - 1), &id, Some(&value)));
+    assert!(missing_proof.verify(&state_root, &missing_id, None));
+}
+
+#[test]
+fn rollback_should_restore_state_root() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut ledger);
+    let (_, _, account) = test_runner.new_account();
+    let version = ledger.get_state_version();
+    let state_root = ledger.get_state_root(version);
+    let mut test_runner = TestRunner::new(&mut ledger);
+    transfer_free_xrd(&mut test_runner, account);
+
+    // Act
+    ledger.rollback_to(version);
+
+    // Assert
+    assert_eq!(ledger.get_state_root(version), state_root);
+    assert_eq!(ledger.get_state_root(version + 1), state_root);
+}
+
+#[test]
+fn overwritten_package_should_be_proven_against_state_root() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let main = "(func $main (result i32) i32.const 0)";
+    let package_address = executor
+        .publish_package(TestRunner::compile_wat("Test", main))
+        .unwrap();
+    let version = executor.substate_store().get_state_version();
+
+    // Act
+    executor
+        .overwrite_package(package_address, TestRunner::compile_wat("Other", main))
+        .unwrap();
+
+    // Assert
+    let ledger = executor.substate_store();
+    let id = scrypto_encode(&package_address);
+    let value = ledger.get_substate(&package_address).unwrap().value;
+    let proof = ledger.get_substate_proof(&id, version + 1);
+    assert!(proof.verify(&ledger.get_state_root(version + 1), &id, Some(&value)));
+    assert_ne!(
+        ledger.get_state_root(version + 1),
+        ledger.get_state_root(version)
+    );
+}
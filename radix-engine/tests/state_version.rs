@@ -0,0 +1,181 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::ledger::*;
+use radix_engine::model::{Package, ResourceManager};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+fn create_mutable_token(
+    executor: &mut TransactionExecutor<InMemorySubstateStore>,
+    account: ComponentAddress,
+) -> (ResourceAddress, ResourceAddress) {
+    let transaction = TransactionBuilder::new()
+        .new_badge_fixed(HashMap::new(), 1.into())
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let minter_resource_address = receipt.new_resource_addresses[0];
+
+    let transaction = TransactionBuilder::new()
+        .new_token_mutable(HashMap::new(), minter_resource_address)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    (receipt.new_resource_addresses[0], minter_resource_address)
+}
+
+fn total_supply_at(
+    executor: &TransactionExecutor<InMemorySubstateStore>,
+    resource_address: ResourceAddress,
+    version: u64,
+) -> Decimal {
+    let (resource_manager, _): (ResourceManager, _) = executor
+        .substate_store()
+        .get_decoded_substate_at(&resource_address, version)
+        .unwrap();
+    resource_manager.total_supply()
+}
+
+#[test]
+fn each_commit_should_bump_state_version() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::new();
+    assert_eq!(ledger.get_state_version(), 0);
+    ledger.bootstrap();
+    assert_eq!(ledger.get_state_version(), 1);
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let (_, _, account) = executor.new_account();
+
+    // Assert
+    assert_eq!(executor.substate_store().get_state_version(), 2);
+    assert!(executor
+        .substate_store()
+        .get_substate_at(&account, 1)
+        .is_none());
+    assert_eq!(
+        executor
+            .substate_store()
+            .get_substate_at(&account, 2)
+            .map(|substate| substate.value),
+        executor
+            .substate_store()
+            .get_substate(&account)
+            .map(|substate| substate.value)
+    );
+}
+
+#[test]
+fn substate_can_be_read_as_of_earlier_state_version() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (pk, sk, account) = executor.new_account();
+    let (token, minter) = create_mutable_token(&mut executor, account);
+    let version_before_mint = executor.substate_store().get_state_version();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .create_proof_from_account(minter, account)
+        .mint(100.into(), token)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let version_after_mint = executor.substate_store().get_state_version();
+    assert_eq!(version_after_mint, version_before_mint + 1);
+    assert_eq!(
+        total_supply_at(&executor, token, version_before_mint),
+        Decimal::zero()
+    );
+    assert_eq!(
+        total_supply_at(&executor, token, version_after_mint),
+        Decimal::from(100)
+    );
+}
+
+#[test]
+fn rollback_should_revert_substates_and_transactions() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (pk, sk, account) = executor.new_account();
+    let (token, minter) = create_mutable_token(&mut executor, account);
+    let version_before_mint = executor.substate_store().get_state_version();
+    let transaction_count = executor.substate_store().get_transaction_count();
+    let transaction = TransactionBuilder::new()
+        .create_proof_from_account(minter, account)
+        .mint(100.into(), token)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    executor
+        .validate_and_execute(&transaction)
+        .unwrap()
+        .result
+        .expect("Should be okay");
+
+    // Act
+    executor
+        .substate_store_mut()
+        .rollback_to(version_before_mint);
+
+    // Assert
+    let store = executor.substate_store();
+    assert_eq!(store.get_state_version(), version_before_mint);
+    assert_eq!(store.get_transaction_count(), transaction_count);
+    let (resource_manager, _): (ResourceManager, _) = store.get_decoded_substate(&token).unwrap();
+    assert_eq!(resource_manager.total_supply(), Decimal::zero());
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn rollback_should_remove_substates_created_since() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let version = executor.substate_store().get_state_version();
+    let (_, _, account) = executor.new_account();
+
+    // Act
+    executor.substate_store_mut().rollback_to(version);
+
+    // Assert
+    assert!(executor.substate_store().get_substate(&account).is_none());
+}
+
+#[test]
+fn rollback_should_revert_overwritten_package() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let main = "(func $main (result i32) i32.const 0)";
+    let package_address = executor
+        .publish_package(TestRunner::compile_wat("Test", main))
+        .unwrap();
+    let version = executor.substate_store().get_state_version();
+    executor
+        .overwrite_package(package_address, TestRunner::compile_wat("Other", main))
+        .unwrap();
+    assert_eq!(executor.substate_store().get_state_version(), version + 1);
+
+    // Act
+    executor.substate_store_mut().rollback_to(version);
+
+    // Assert
+    let (package, _): (Package, _) = executor
+        .substate_store()
+        .get_decoded_substate(&package_address)
+        .unwrap();
+    assert!(package.contains_blueprint("Test"));
+    assert!(!package.contains_blueprint("Other"));
+}
@@ -1,7 +1,9 @@
 use radix_engine::ledger::*;
-use radix_engine::model::{Component, Receipt, SignedTransaction};
+use radix_engine::model::{Component, PreviewFlags, Receipt, SignedTransaction, Vault};
 use radix_engine::transaction::*;
+use sbor::describe::{Fields, Type};
 use scrypto::abi;
+use scrypto::engine::types::VaultId;
 use scrypto::prelude::*;
 
 pub struct TestRunner<'l> {
@@ -15,6 +17,15 @@ impl<'l> TestRunner<'l> {
         Self { executor }
     }
 
+    pub fn from_snapshot(ledger: &'l mut InMemorySubstateStore, snapshot: LedgerSnapshot) -> Self {
+        ledger.import_snapshot(snapshot);
+        Self::new(ledger)
+    }
+
+    pub fn snapshot(&self) -> LedgerSnapshot {
+        self.executor.substate_store().export_snapshot()
+    }
+
     pub fn new_transaction_builder(&self) -> TransactionBuilder {
         TransactionBuilder::new()
     }
@@ -34,6 +45,17 @@ impl<'l> TestRunner<'l> {
         )
     }
 
+    pub fn new_ed25519_key_pair_with_pk_address(
+        &mut self,
+    ) -> (Ed25519PublicKey, Ed25519PrivateKey, NonFungibleAddress) {
+        let (pk, sk) = self.executor.new_ed25519_key_pair();
+        (
+            pk,
+            sk,
+            NonFungibleAddress::new(ED25519_TOKEN, NonFungibleId::from_bytes(pk.to_vec())),
+        )
+    }
+
     pub fn new_account_with_auth_rule(&mut self, withdraw_auth: &AccessRule) -> ComponentAddress {
         self.executor.new_account_with_auth_rule(withdraw_auth)
     }
@@ -46,6 +68,11 @@ impl<'l> TestRunner<'l> {
         self.executor.validate_and_execute(transaction).unwrap()
     }
 
+    pub fn preview(&mut self, transaction: &SignedTransaction, flags: PreviewFlags) -> Receipt {
+        let validated = transaction.validate_preview(&flags).unwrap();
+        self.executor.preview(&validated, flags)
+    }
+
     pub fn publish_package(&mut self, name: &str) -> PackageAddress {
         self.executor.publish_package(&Self::compile(name)).unwrap()
     }
@@ -54,6 +81,53 @@ impl<'l> TestRunner<'l> {
         compile_package!(format!("./tests/{}", name))
     }
 
+    /// Compiles a package with a single unit struct blueprint from WAT.
+    ///
+    /// The given functions must include `$main`, which is exported as the blueprint main, and
+    /// may be preceded by imports.
+    pub fn compile_wat(blueprint_name: &str, funcs: &str) -> Vec<u8> {
+        Self::compile_wat_with_events(blueprint_name, vec![], funcs)
+    }
+
+    /// Compiles a package with a single unit struct blueprint, which declares the given events,
+    /// from WAT.
+    pub fn compile_wat_with_events(
+        blueprint_name: &str,
+        events: Vec<abi::Event>,
+        funcs: &str,
+    ) -> Vec<u8> {
+        let abi: (Type, Vec<abi::Function>, Vec<abi::Method>, Vec<abi::Event>) = (
+            Type::Struct {
+                name: blueprint_name.to_owned(),
+                fields: Fields::Unit,
+            },
+            vec![],
+            vec![],
+            events,
+        );
+        let abi = scrypto_encode(&abi);
+        let mut data = (abi.len() as u32).to_le_bytes().to_vec();
+        data.extend(abi);
+        let data: String = data.iter().map(|b| format!("\\{:02x}", b)).collect();
+
+        wabt::wat2wasm(format!(
+            r#"
+                (module
+                    {funcs}
+                    (memory $0 1)
+                    (data (i32.const 1024) "{data}")
+                    (func $abi (result i32)
+                        i32.const 1024
+                    )
+                    (export "memory" (memory $0))
+                    (export "{blueprint_name}_abi" (func $abi))
+                    (export "{blueprint_name}_main" (func $main))
+                )
+                "#,
+        ))
+        .expect("failed to parse wat")
+    }
+
     pub fn component(&self, component_address: ComponentAddress) -> Component {
         self.executor
             .substate_store()
@@ -62,6 +136,27 @@ impl<'l> TestRunner<'l> {
             .unwrap()
     }
 
+    /// Returns the ID of the vault holding a resource in a component.
+    pub fn get_vault_id(
+        &self,
+        component_address: ComponentAddress,
+        resource_address: ResourceAddress,
+    ) -> VaultId {
+        self.executor
+            .substate_store()
+            .get_child_substates(&component_address, &[], None, usize::MAX)
+            .into_iter()
+            .find_map(|(key, substate)| {
+                let vault: Vault = scrypto_decode(&substate.value).ok()?;
+                if vault.resource_address() == resource_address {
+                    scrypto_decode(&key).ok()
+                } else {
+                    None
+                }
+            })
+            .unwrap()
+    }
+
     pub fn export_abi(
         &self,
         package_address: PackageAddress,
@@ -151,6 +246,28 @@ impl<'l> TestRunner<'l> {
         )
     }
 
+    pub fn create_regulated_token(
+        &mut self,
+        account: ComponentAddress,
+    ) -> (ResourceAddress, ResourceAddress, ResourceAddress) {
+        let recall_auth = self.create_non_fungible_resource(account);
+        let freeze_auth = self.create_non_fungible_resource(account);
+
+        let package = self.publish_package("resource_creator");
+        let transaction = TransactionBuilder::new()
+            .call_function(
+                package,
+                "ResourceCreator",
+                "create_regulated_token",
+                vec![scrypto_encode(&recall_auth), scrypto_encode(&freeze_auth)],
+            )
+            .call_method_with_all_resources(account, "deposit_batch")
+            .build(self.executor.get_nonce([]))
+            .sign([]);
+        let receipt = self.executor.validate_and_execute(&transaction).unwrap();
+        (receipt.new_resource_addresses[0], recall_auth, freeze_auth)
+    }
+
     pub fn create_restricted_burn_token(
         &mut self,
         account: ComponentAddress,
@@ -0,0 +1,168 @@
+use radix_engine::errors::TransactionValidationError;
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn transaction_for_another_network_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .network_id(1)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let result = executor.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(TransactionValidationError::InvalidNetwork {
+            expected: 0,
+            actual: 1
+        })
+    );
+}
+
+#[test]
+fn transaction_should_only_be_committed_within_its_epochs() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .epochs(5, 10)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+
+    // Act
+    executor.substate_store_mut().set_epoch(4);
+    let early_result = executor.validate_and_execute(&transaction);
+    executor.substate_store_mut().set_epoch(10);
+    let late_result = executor.validate_and_execute(&transaction);
+    executor.substate_store_mut().set_epoch(9);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    assert_eq!(
+        early_result.err(),
+        Some(TransactionValidationError::EpochOutOfRange {
+            current_epoch: 4,
+            start_epoch_inclusive: 5,
+            end_epoch_exclusive: 10
+        })
+    );
+    assert_eq!(
+        late_result.err(),
+        Some(TransactionValidationError::EpochOutOfRange {
+            current_epoch: 10,
+            start_epoch_inclusive: 5,
+            end_epoch_exclusive: 10
+        })
+    );
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn transaction_with_empty_epoch_range_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .epochs(5, 5)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let result = executor.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(TransactionValidationError::InvalidEpochRange {
+            start_epoch_inclusive: 5,
+            end_epoch_exclusive: 5
+        })
+    );
+}
+
+#[test]
+fn committed_transaction_should_not_be_replayed() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+
+    // Act
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let replay_result = executor.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert_eq!(
+        replay_result.err(),
+        Some(TransactionValidationError::IntentAlreadyCommitted(
+            transaction.transaction.raw_hash()
+        ))
+    );
+}
+
+#[test]
+fn rejected_transaction_can_be_submitted_again() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let (_, _, other_account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+
+    // Act
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let second_receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect_err("Should be an error");
+    second_receipt.result.expect_err("Should be an error");
+}
+
+#[test]
+fn notary_should_sign_transaction_and_authorize_its_account() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (pk, sk, account) = executor.new_account();
+    let (_, _, other_account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .notary(pk.into())
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(executor.get_nonce([pk]));
+
+    // Act
+    let unsigned_result = executor.validate_and_execute(&transaction.clone().sign([]));
+    let receipt = executor
+        .validate_and_execute(&transaction.sign([&sk]))
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        unsigned_result.err(),
+        Some(TransactionValidationError::MissingNotarySignature)
+    );
+    receipt.result.expect("Should be okay");
+}
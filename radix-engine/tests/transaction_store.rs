@@ -0,0 +1,63 @@
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::buffer::scrypto_decode;
+use scrypto::prelude::*;
+
+#[test]
+fn committed_transactions_can_be_looked_up_by_hash_and_index() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let count_before = executor.substate_store().get_transaction_count();
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+
+    // Act
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let store = executor.substate_store();
+    assert_eq!(store.get_transaction_count(), count_before + 1);
+    let committed = store
+        .get_transaction_by_hash(&transaction.transaction.raw_hash())
+        .unwrap();
+    assert_eq!(committed.index, count_before);
+    assert_eq!(committed.state_version, store.get_state_version());
+    assert!(committed.receipt.result.is_ok());
+    let decoded: SignedTransaction = scrypto_decode(&committed.transaction).unwrap();
+    assert_eq!(decoded, transaction);
+    let by_index = store.get_transaction_by_index(count_before).unwrap();
+    assert_eq!(by_index.hash, committed.hash);
+}
+
+#[test]
+fn rejected_transaction_should_not_be_kept_in_history() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let (_, _, other_account) = executor.new_account();
+    let count_before = executor.substate_store().get_transaction_count();
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+
+    // Act
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect_err("Should be an error");
+    let store = executor.substate_store();
+    assert_eq!(store.get_transaction_count(), count_before);
+    assert!(store
+        .get_transaction_by_hash(&transaction.transaction.raw_hash())
+        .is_none());
+}
@@ -1,2 +1,3 @@
 pub mod non_existent_vault;
+pub mod treasury;
 pub mod vault;
@@ -0,0 +1,38 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Treasury {
+        vault: Vault,
+    }
+
+    impl Treasury {
+        pub fn new(admin_badge: ResourceAddress) -> ComponentAddress {
+            let tokens = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(100);
+            let mut vault = Vault::with_access_rules(
+                tokens.resource_address(),
+                AccessRules::new().method("take_from_vault", rule!(require(admin_badge))),
+            );
+            vault.put(tokens);
+            Treasury { vault }.instantiate().globalize()
+        }
+
+        pub fn new_with_schema_path_rule() -> ComponentAddress {
+            let vault = Vault::with_access_rules(
+                RADIX_TOKEN,
+                AccessRules::new().method("take_from_vault", rule!(require("vault"))),
+            );
+            Treasury { vault }.instantiate().globalize()
+        }
+
+        // Not protected by the component, so only the vault rules guard the tokens
+        pub fn withdraw(&mut self, amount: Decimal) -> Bucket {
+            self.vault.take(amount)
+        }
+
+        pub fn balance(&self) -> Decimal {
+            self.vault.amount()
+        }
+    }
+}
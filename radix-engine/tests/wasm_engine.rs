@@ -0,0 +1,84 @@
+#![cfg(feature = "wasmer")]
+
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::engine::{WasmEngine, WasmerEngine, WasmiEngine};
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::Receipt;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+/// Runs the same transactions on a fresh ledger, with blueprints running on the given engine.
+fn run_transactions<W: WasmEngine>(wasm_engine: W) -> Vec<Receipt> {
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut executor =
+        TransactionExecutor::with_wasm_engine(&mut substate_store, wasm_engine, false);
+    let (pk, sk, account) = executor.new_account();
+    let (_, _, other_account) = executor.new_account();
+    let code = TestRunner::compile_wat(
+        "Recursion",
+        r#"
+            (func $main (result i32)
+                call $main
+            )
+        "#,
+    );
+    let package = executor.publish_package(code).unwrap();
+    let mut receipts = Vec::new();
+
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    receipts.push(executor.validate_and_execute(&transaction).unwrap());
+
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "Recursion", "something", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    receipts.push(executor.validate_and_execute(&transaction).unwrap());
+
+    receipts
+}
+
+/// Returns the IDs of the substates updated by a transaction, in order.
+fn updated_substates(receipt: &Receipt) -> Vec<Vec<u8>> {
+    let mut ids: Vec<Vec<u8>> = receipt
+        .commit_receipt
+        .iter()
+        .flat_map(|commit_receipt| commit_receipt.substate_changes.iter())
+        .map(|change| change.key.to_id())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn wasmi_and_wasmer_should_produce_identical_receipts() {
+    // Act
+    let wasmi_receipts = run_transactions(WasmiEngine::new());
+    let wasmer_receipts = run_transactions(WasmerEngine::new());
+
+    // Assert
+    assert_eq!(wasmi_receipts.len(), wasmer_receipts.len());
+    for (wasmi, wasmer) in wasmi_receipts.iter().zip(wasmer_receipts.iter()) {
+        assert_eq!(wasmi.result, wasmer.result);
+        assert_eq!(wasmi.fee_summary, wasmer.fee_summary);
+        assert_eq!(
+            wasmi.outputs.iter().map(|o| &o.raw).collect::<Vec<_>>(),
+            wasmer.outputs.iter().map(|o| &o.raw).collect::<Vec<_>>()
+        );
+        assert_eq!(wasmi.logs, wasmer.logs);
+        assert_eq!(
+            wasmi.new_component_addresses,
+            wasmer.new_component_addresses
+        );
+        assert_eq!(wasmi.balance_changes(), wasmer.balance_changes());
+        assert_eq!(updated_substates(wasmi), updated_substates(wasmer));
+    }
+    assert!(wasmi_receipts[0].result.is_ok());
+    assert!(wasmi_receipts[1].result.is_err());
+}
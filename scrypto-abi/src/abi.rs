@@ -8,7 +8,7 @@ pub use alloc::string::ToString;
 pub use alloc::vec::Vec;
 
 use sbor::describe::*;
-use sbor::{Decode, Encode, TypeId};
+use sbor::{Decode, Describe, Encode, TypeId};
 
 /// Represents a blueprint.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -18,6 +18,8 @@ pub struct Blueprint {
     pub blueprint_name: String,
     pub functions: Vec<Function>,
     pub methods: Vec<Method>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub events: Vec<Event>,
 }
 
 /// Represents a function.
@@ -39,6 +41,36 @@ pub struct Method {
     pub output: Type,
 }
 
+/// Represents an event.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct Event {
+    pub name: String,
+    pub schema: Type,
+}
+
+impl Event {
+    /// Describes the events of type `T`, named after it as they are emitted.
+    ///
+    /// # Panics
+    /// Panics if `T` is neither a struct nor an enum.
+    pub fn of<T: Describe>() -> Self {
+        let schema = T::describe();
+        let name = Self::name_of(&schema)
+            .map(String::from)
+            .expect("Event must be a struct or an enum");
+        Self { name, schema }
+    }
+
+    /// Returns the name of the events of the given schema, if it is a struct or an enum.
+    pub fn name_of(schema: &Type) -> Option<&str> {
+        match schema {
+            Type::Struct { name, .. } | Type::Enum { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+}
+
 /// Whether a method is going to change the component state.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -1,5 +1,6 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Brace;
 use syn::*;
@@ -100,19 +101,21 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
 
     let abi_ident = format_ident!("{}_abi", bp_ident);
     let (abi_functions, abi_methods) = generate_abi(bp_ident, bp_items)?;
+    let abi_events = generate_abi_events(&bp_strut.attrs)?;
     let output_abi = quote! {
         #[no_mangle]
         pub extern "C" fn #abi_ident() -> *mut u8 {
             use ::sbor::{Describe, Type};
-            use ::scrypto::abi::{Function, Method};
+            use ::scrypto::abi::{Event, Function, Method};
             use ::scrypto::rust::borrow::ToOwned;
             use ::scrypto::rust::vec;
             use ::scrypto::rust::vec::Vec;
 
             let functions: Vec<Function> = vec![ #(#abi_functions),* ];
             let methods: Vec<Method> = vec![ #(#abi_methods),* ];
+            let events: Vec<Event> = vec![ #(#abi_events),* ];
             let schema: Type = blueprint::#bp_ident::describe();
-            let output = (schema, functions, methods);
+            let output = (schema, functions, methods, events);
 
             // serialize the output
             let output_bytes = ::scrypto::buffer::scrypto_encode_for_radix_engine(&output);
@@ -329,6 +332,68 @@ fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr>, Vec<
     Ok((functions, methods))
 }
 
+// Parses the `#[events(..)]` attribute of a blueprint struct and returns ABI of events.
+fn generate_abi_events(attrs: &[Attribute]) -> Result<Vec<Expr>> {
+    let mut events = Vec::<Expr>::new();
+
+    for attr in attrs {
+        if attr.path.is_ident("events") {
+            let paths = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+            for path in paths {
+                check_event_type(&path)?;
+                // Named after the described type, as events are when emitted
+                events.push(parse_quote! {
+                    ::scrypto::abi::Event::of::<#path>()
+                });
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+// Rejects event types which can not be a struct or an enum.
+fn check_event_type(path: &Path) -> Result<()> {
+    let segment = path
+        .segments
+        .last()
+        .ok_or_else(|| Error::new(path.span(), "Missing event type"))?;
+    let name = segment.ident.to_string();
+    let is_builtin = matches!(
+        name.as_str(),
+        "bool"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "str"
+            | "String"
+            | "Option"
+            | "Result"
+            | "Box"
+            | "Vec"
+            | "HashSet"
+            | "HashMap"
+            | "BTreeSet"
+            | "BTreeMap"
+    );
+    if is_builtin || !segment.arguments.is_empty() {
+        return Err(Error::new(
+            path.span(),
+            "Event type must be a struct or an enum",
+        ));
+    }
+    Ok(())
+}
+
 // Parses function items of an `Impl` and returns ABI of functions.
 fn generate_stubs(bp_ident: &Ident, items: &[ImplItem]) -> Result<TokenStream> {
     let bp_name = bp_ident.to_string();
@@ -475,6 +540,33 @@ mod tests {
         handle_blueprint(input).unwrap();
     }
 
+    #[test]
+    fn test_blueprint_events() {
+        let structure: ItemStruct = parse_quote! {
+            #[events(Deposited, events::Withdrawn)]
+            struct Test {}
+        };
+        let events = generate_abi_events(&structure.attrs).unwrap();
+
+        assert_code_eq(
+            quote! { #(#events),* },
+            quote! {
+                ::scrypto::abi::Event::of::<Deposited>(),
+                ::scrypto::abi::Event::of::<events::Withdrawn>()
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_non_struct_event_should_fail() {
+        let structure: ItemStruct = parse_quote! {
+            #[events(u32)]
+            struct Test {}
+        };
+        generate_abi_events(&structure.attrs).unwrap();
+    }
+
     #[test]
     fn test_blueprint() {
         let input = TokenStream::from_str(
@@ -538,7 +630,7 @@ mod tests {
                 #[no_mangle]
                 pub extern "C" fn Test_abi() -> *mut u8 {
                     use ::sbor::{Describe, Type};
-                    use ::scrypto::abi::{Function, Method};
+                    use ::scrypto::abi::{Event, Function, Method};
                     use ::scrypto::rust::borrow::ToOwned;
                     use ::scrypto::rust::vec;
                     use ::scrypto::rust::vec::Vec;
@@ -549,8 +641,9 @@ mod tests {
                         inputs: vec![],
                         output: <u32>::describe(),
                     }];
+                    let events: Vec<Event> = vec![];
                     let schema: Type = blueprint::Test::describe();
-                    let output = (schema, functions, methods);
+                    let output = (schema, functions, methods, events);
                     let output_bytes = ::scrypto::buffer::scrypto_encode_for_radix_engine(&output);
                     ::scrypto::buffer::scrypto_wrap(output_bytes)
                 }
@@ -7,7 +7,13 @@ use scrypto::prelude::*;
 use serde::Serialize;
 use serde_json::{json, to_value, Value};
 
+#[derive(TypeId, Encode, Decode, Describe)]
+pub struct StateChanged {
+    pub new_state: u32,
+}
+
 blueprint! {
+    #[events(StateChanged)]
     struct Simple {
         state: u32,
     }
@@ -35,6 +41,23 @@ blueprint! {
     }
 }
 
+pub type StateChangedAlias = StateChanged;
+
+mod aliased {
+    use super::*;
+
+    blueprint! {
+        #[events(StateChangedAlias)]
+        struct Aliased {}
+
+        impl Aliased {
+            pub fn create() -> ComponentAddress {
+                Self {}.instantiate().globalize()
+            }
+        }
+    }
+}
+
 fn assert_json_eq<T: Serialize>(actual: T, expected: Value) {
     assert_eq!(to_value(&actual).unwrap(), expected);
 }
@@ -42,7 +65,7 @@ fn assert_json_eq<T: Serialize>(actual: T, expected: Value) {
 #[test]
 fn test_simple_abi() {
     let ptr = Simple_abi();
-    let abi: (Type, Vec<abi::Function>, Vec<abi::Method>) =
+    let abi: (Type, Vec<abi::Function>, Vec<abi::Method>, Vec<abi::Event>) =
         unsafe { scrypto_consume(ptr, |slice| scrypto_decode(slice).unwrap()) };
 
     assert_json_eq(
@@ -144,7 +167,35 @@ fn test_simple_abi() {
                         "type": "Unit"
                     }
                 }
+            ],
+            [
+                {
+                    "name": "StateChanged",
+                    "schema": {
+                        "fields": {
+                            "named": [
+                                [
+                                    "new_state",
+                                    { "type": "U32" }
+                                ]
+                            ],
+                            "type": "Named"
+                        },
+                        "name": "StateChanged",
+                        "type": "Struct"
+                    }
+                }
             ]
         ]),
     );
 }
+
+#[test]
+fn test_aliased_event_abi() {
+    let ptr = aliased::Aliased_abi();
+    let abi: (Type, Vec<abi::Function>, Vec<abi::Method>, Vec<abi::Event>) =
+        unsafe { scrypto_consume(ptr, |slice| scrypto_decode(slice).unwrap()) };
+
+    assert_eq!(abi.3.len(), 1);
+    assert_eq!(abi.3[0].name, "StateChanged");
+}
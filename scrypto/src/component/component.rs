@@ -51,7 +51,7 @@ pub trait ComponentState: Encode + Decode {
 }
 
 /// An instance of a blueprint, which lives in the ledger state.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ComponentAddress(pub [u8; 26]);
 
 impl ComponentAddress {}
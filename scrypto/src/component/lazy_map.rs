@@ -54,6 +54,51 @@ impl<K: Encode + Decode, V: Encode + Decode> LazyMap<K, V> {
         };
         let _: PutLazyMapEntryOutput = call_engine(PUT_LAZY_MAP_ENTRY, input);
     }
+
+    /// Removes an entry from this map, returning its value if the key was present.
+    ///
+    /// Values which hold vaults or lazy maps can't be removed.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let input = RemoveLazyMapEntryInput {
+            lazy_map_id: self.id,
+            key: scrypto_encode(key),
+        };
+        let output: RemoveLazyMapEntryOutput = call_engine(REMOVE_LAZY_MAP_ENTRY, input);
+
+        output.value.map(|v| scrypto_decode(&v).unwrap())
+    }
+
+    /// Returns whether this map contains the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        let input = ContainsLazyMapEntryInput {
+            lazy_map_id: self.id,
+            key: scrypto_encode(key),
+        };
+        let output: ContainsLazyMapEntryOutput = call_engine(CONTAINS_LAZY_MAP_ENTRY, input);
+
+        output.contains
+    }
+
+    /// Returns up to `limit` entries whose key comes after `after`, or the first entries
+    /// if `after` is `None`.
+    ///
+    /// Entries are ordered by the bytes of their SBOR-encoded key, so a map can be paged
+    /// through by passing the last key of each page to the next call. A `limit` above
+    /// 1,000 fails the transaction.
+    pub fn range(&self, after: Option<&K>, limit: u32) -> Vec<(K, V)> {
+        let input = ListLazyMapEntriesInput {
+            lazy_map_id: self.id,
+            after: after.map(|key| scrypto_encode(key)),
+            limit,
+        };
+        let output: ListLazyMapEntriesOutput = call_engine(LIST_LAZY_MAP_ENTRIES, input);
+
+        output
+            .entries
+            .into_iter()
+            .map(|(k, v)| (scrypto_decode(&k).unwrap(), scrypto_decode(&v).unwrap()))
+            .collect()
+    }
 }
 
 //========
@@ -25,3 +25,8 @@ pub const RADIX_TOKEN: ResourceAddress = ResourceAddress([
 pub const ECDSA_TOKEN: ResourceAddress = ResourceAddress([
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5,
 ]);
+
+/// The ED25519 virtual resource address.
+pub const ED25519_TOKEN: ResourceAddress = ResourceAddress([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6,
+]);
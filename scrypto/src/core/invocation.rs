@@ -1,3 +1,4 @@
+use crate::component::ComponentAddress;
 use crate::core::ScryptoActor;
 use crate::engine::types::{BucketId, ProofId, VaultId};
 use crate::resource::ResourceAddress;
@@ -17,4 +18,7 @@ pub enum SNodeRef {
     ProofRef(ProofId),
     Proof(ProofId),
     VaultRef(VaultId),
+    /// A vault held by any component, which can only be recalled from or frozen by the manager
+    /// of the resource it holds
+    ComponentVaultRef(ResourceAddress, ComponentAddress, VaultId),
 }
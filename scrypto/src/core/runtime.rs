@@ -1,8 +1,13 @@
+use sbor::{Describe, Encode};
+
+use crate::abi::Event;
+use crate::buffer::*;
 use crate::component::*;
 use crate::core::*;
 use crate::crypto::*;
 use crate::engine::{api::*, call_engine};
 use crate::rust::borrow::ToOwned;
+use crate::rust::string::String;
 use crate::rust::vec::Vec;
 
 /// The transaction runtime.
@@ -69,6 +74,20 @@ impl Runtime {
         output.rtn
     }
 
+    /// Emits an event, which must be declared by the blueprint.
+    ///
+    /// Events are named after their type, which has to be a struct or an enum.
+    pub fn emit_event<T: Encode + Describe>(event: T) {
+        let name = Event::name_of(&T::describe())
+            .map(String::from)
+            .expect("Event must be a struct or an enum");
+        let input = EmitEventInput {
+            name,
+            data: scrypto_encode(&event),
+        };
+        let _: EmitEventOutput = call_engine(EMIT_EVENT, input);
+    }
+
     /// Returns the transaction hash.
     pub fn transaction_hash() -> Hash {
         let input = GetTransactionHashInput {};
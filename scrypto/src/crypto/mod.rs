@@ -1,9 +1,13 @@
 mod ecdsa;
+mod ed25519;
 mod hash;
 mod sha2;
 mod sha3;
+mod signature;
 
 pub use self::ecdsa::*;
+pub use self::ed25519::*;
 pub use self::sha2::{sha256, sha256_twice};
 pub use self::sha3::sha3;
+pub use self::signature::*;
 pub use hash::*;
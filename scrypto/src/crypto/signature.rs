@@ -0,0 +1,79 @@
+use sbor::*;
+
+use crate::crypto::*;
+
+/// Represents a public key of any supported signature scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypeId, Encode, Decode)]
+pub enum PublicKey {
+    Ecdsa(EcdsaPublicKey),
+    Ed25519(Ed25519PublicKey),
+}
+
+/// Represents a signature of any supported signature scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypeId, Encode, Decode)]
+pub enum Signature {
+    Ecdsa(EcdsaSignature),
+    Ed25519(Ed25519Signature),
+}
+
+/// Verifies signatures of any supported scheme.
+pub struct SignatureVerifier;
+
+impl SignatureVerifier {
+    /// Verifies a signature, which fails if the public key and signature are of different schemes.
+    pub fn verify(msg: &[u8], pk: &PublicKey, sig: &Signature) -> bool {
+        match (pk, sig) {
+            (PublicKey::Ecdsa(pk), Signature::Ecdsa(sig)) => EcdsaVerifier::verify(msg, pk, sig),
+            (PublicKey::Ed25519(pk), Signature::Ed25519(sig)) => {
+                Ed25519Verifier::verify(msg, pk, sig)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<EcdsaPublicKey> for PublicKey {
+    fn from(public_key: EcdsaPublicKey) -> Self {
+        PublicKey::Ecdsa(public_key)
+    }
+}
+
+impl From<Ed25519PublicKey> for PublicKey {
+    fn from(public_key: Ed25519PublicKey) -> Self {
+        PublicKey::Ed25519(public_key)
+    }
+}
+
+impl From<EcdsaSignature> for Signature {
+    fn from(signature: EcdsaSignature) -> Self {
+        Signature::Ecdsa(signature)
+    }
+}
+
+impl From<Ed25519Signature> for Signature {
+    fn from(signature: Ed25519Signature) -> Self {
+        Signature::Ed25519(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_reject_signature_of_other_scheme() {
+        let ecdsa_sk = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let ed25519_sk = Ed25519PrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let msg = b"message";
+
+        let ecdsa_pk = PublicKey::from(ecdsa_sk.public_key());
+        let ed25519_pk = PublicKey::from(ed25519_sk.public_key());
+        let ecdsa_sig = Signature::from(ecdsa_sk.sign(msg));
+        let ed25519_sig = Signature::from(ed25519_sk.sign(msg));
+
+        assert!(SignatureVerifier::verify(msg, &ecdsa_pk, &ecdsa_sig));
+        assert!(SignatureVerifier::verify(msg, &ed25519_pk, &ed25519_sig));
+        assert!(!SignatureVerifier::verify(msg, &ecdsa_pk, &ed25519_sig));
+        assert!(!SignatureVerifier::verify(msg, &ed25519_pk, &ecdsa_sig));
+    }
+}
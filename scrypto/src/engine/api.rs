@@ -27,9 +27,17 @@ pub const CREATE_LAZY_MAP: u32 = 0x20;
 pub const GET_LAZY_MAP_ENTRY: u32 = 0x21;
 /// Insert a key-value pair into a lazy map
 pub const PUT_LAZY_MAP_ENTRY: u32 = 0x22;
+/// Remove an entry from a lazy map
+pub const REMOVE_LAZY_MAP_ENTRY: u32 = 0x23;
+/// Check whether a lazy map contains a key
+pub const CONTAINS_LAZY_MAP_ENTRY: u32 = 0x24;
+/// Retrieve a page of lazy map entries, in key order
+pub const LIST_LAZY_MAP_ENTRIES: u32 = 0x25;
 
 /// Create an empty vault
 pub const CREATE_EMPTY_VAULT: u32 = 0x40;
+/// Create an empty vault with its own access rules
+pub const CREATE_EMPTY_VAULT_WITH_ACCESS_RULES: u32 = 0x41;
 
 pub const INVOKE_SNODE: u32 = 0x70;
 
@@ -45,6 +53,8 @@ pub const GET_CURRENT_EPOCH: u32 = 0xf3;
 pub const GET_TRANSACTION_HASH: u32 = 0xf4;
 /// Retrieve the running entity
 pub const GET_ACTOR: u32 = 0xf5;
+/// Emit an event
+pub const EMIT_EVENT: u32 = 0xf6;
 
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct InvokeSNodeInput {
@@ -134,6 +144,41 @@ pub struct PutLazyMapEntryInput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct PutLazyMapEntryOutput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct RemoveLazyMapEntryInput {
+    pub lazy_map_id: LazyMapId,
+    pub key: Vec<u8>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct RemoveLazyMapEntryOutput {
+    pub value: Option<Vec<u8>>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ContainsLazyMapEntryInput {
+    pub lazy_map_id: LazyMapId,
+    pub key: Vec<u8>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ContainsLazyMapEntryOutput {
+    pub contains: bool,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ListLazyMapEntriesInput {
+    pub lazy_map_id: LazyMapId,
+    /// Only entries with a key greater than this one are listed
+    pub after: Option<Vec<u8>>,
+    pub limit: u32,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ListLazyMapEntriesOutput {
+    pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
 //==========
 // vault
 //==========
@@ -143,6 +188,12 @@ pub struct CreateEmptyVaultInput {
     pub resource_address: ResourceAddress,
 }
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct CreateEmptyVaultWithAccessRulesInput {
+    pub resource_address: ResourceAddress,
+    pub access_rules: AccessRules,
+}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct CreateEmptyVaultOutput {
     pub vault_id: VaultId,
@@ -161,6 +212,15 @@ pub struct EmitLogInput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct EmitLogOutput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct EmitEventInput {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct EmitEventOutput {}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct GetCallDataInput {}
 
@@ -8,6 +8,9 @@ pub use crate::core::ScryptoActorInfo;
 pub use crate::crypto::EcdsaPrivateKey;
 pub use crate::crypto::EcdsaPublicKey;
 pub use crate::crypto::EcdsaSignature;
+pub use crate::crypto::Ed25519PrivateKey;
+pub use crate::crypto::Ed25519PublicKey;
+pub use crate::crypto::Ed25519Signature;
 pub use crate::crypto::Hash;
 pub use crate::math::Decimal;
 pub use crate::resource::MintParams;
@@ -6,10 +6,12 @@ mod non_fungible;
 mod non_fungible_address;
 mod non_fungible_data;
 mod non_fungible_id;
+mod non_fungible_id_type;
 mod proof;
 mod proof_rule;
 mod resource_builder;
 mod resource_manager;
+mod resource_options;
 mod resource_type;
 mod schema_path;
 mod system;
@@ -23,6 +25,7 @@ pub use non_fungible::NonFungible;
 pub use non_fungible_address::{NonFungibleAddress, ParseNonFungibleAddressError};
 pub use non_fungible_data::NonFungibleData;
 pub use non_fungible_id::{NonFungibleId, ParseNonFungibleIdError};
+pub use non_fungible_id_type::NonFungibleIdType;
 pub use proof::{ParseProofError, Proof};
 pub use proof_rule::{
     require, require_all_of, require_amount, require_any_of, require_n_of, AccessRuleNode,
@@ -35,6 +38,7 @@ pub use resource_manager::ResourceMethod::*;
 pub use resource_manager::{
     Mutability, ParseResourceAddressError, ResourceAddress, ResourceManager, ResourceMethod,
 };
+pub use resource_options::ResourceOptions;
 pub use resource_type::ResourceType;
 pub use schema_path::SchemaPath;
 pub use system::{init_resource_system, resource_system, ResourceSystem};
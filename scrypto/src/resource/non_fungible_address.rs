@@ -1,5 +1,7 @@
 use sbor::*;
 
+use crate::constants::{ECDSA_TOKEN, ED25519_TOKEN};
+use crate::crypto::PublicKey;
 use crate::misc::*;
 use crate::resource::*;
 use crate::rust::borrow::ToOwned;
@@ -24,6 +26,19 @@ impl NonFungibleAddress {
         }
     }
 
+    /// Returns the address of the virtual signer badge of a public key.
+    pub fn from_public_key(public_key: &PublicKey) -> Self {
+        match public_key {
+            PublicKey::Ecdsa(public_key) => {
+                Self::new(ECDSA_TOKEN, NonFungibleId::from_bytes(public_key.to_vec()))
+            }
+            PublicKey::Ed25519(public_key) => Self::new(
+                ED25519_TOKEN,
+                NonFungibleId::from_bytes(public_key.to_vec()),
+            ),
+        }
+    }
+
     /// Returns the resource address.
     pub fn resource_address(&self) -> ResourceAddress {
         self.resource_address
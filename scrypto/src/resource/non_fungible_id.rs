@@ -1,7 +1,10 @@
 use sbor::*;
 
+use crate::misc::*;
+use crate::resource::*;
 use crate::rust::borrow::ToOwned;
 use crate::rust::fmt;
+use crate::rust::format;
 use crate::rust::str::FromStr;
 use crate::rust::string::String;
 use crate::rust::string::ToString;
@@ -32,6 +35,42 @@ impl NonFungibleId {
     pub fn from_u64(u: u64) -> Self {
         Self(u.to_be_bytes().to_vec())
     }
+
+    /// Creates a non-fungible ID from a `u128` UUID.
+    pub fn from_uuid(u: u128) -> Self {
+        Self(u.to_be_bytes().to_vec())
+    }
+
+    /// Creates a non-fungible ID from a string.
+    pub fn from_string(s: &str) -> Self {
+        Self(s.as_bytes().to_vec())
+    }
+
+    /// Returns a human-readable representation of this ID, interpreted as the given type.
+    ///
+    /// Falls back to hex if this ID is not of the given type.
+    pub fn to_typed_string(&self, id_type: NonFungibleIdType) -> String {
+        if !id_type.is_valid(self) {
+            return self.to_string();
+        }
+        match id_type {
+            NonFungibleIdType::U32 => u32::from_be_bytes(copy_u8_array(&self.0)).to_string(),
+            NonFungibleIdType::U64 => u64::from_be_bytes(copy_u8_array(&self.0)).to_string(),
+            NonFungibleIdType::Uuid => {
+                let hex = hex::encode(&self.0);
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[0..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..32]
+                )
+            }
+            NonFungibleIdType::String => String::from_utf8(self.0.clone()).unwrap(),
+            NonFungibleIdType::Bytes => self.to_string(),
+        }
+    }
 }
 
 //========
@@ -120,4 +159,46 @@ mod tests {
             NonFungibleId::from_u64(5)
         );
     }
+
+    #[test]
+    fn test_non_fungible_id_typed_string_rep() {
+        assert_eq!(
+            NonFungibleId::from_u32(5).to_typed_string(NonFungibleIdType::U32),
+            "5"
+        );
+        assert_eq!(
+            NonFungibleId::from_u64(5).to_typed_string(NonFungibleIdType::U64),
+            "5"
+        );
+        assert_eq!(
+            NonFungibleId::from_uuid(0x1234567890abcdef1234567890abcdef)
+                .to_typed_string(NonFungibleIdType::Uuid),
+            "12345678-90ab-cdef-1234-567890abcdef"
+        );
+        assert_eq!(
+            NonFungibleId::from_string("hello").to_typed_string(NonFungibleIdType::String),
+            "hello"
+        );
+        assert_eq!(
+            NonFungibleId::from_u32(5).to_typed_string(NonFungibleIdType::U64),
+            "00000005"
+        );
+    }
+
+    #[test]
+    fn test_non_fungible_id_type_validation() {
+        assert!(NonFungibleIdType::U32.is_valid(&NonFungibleId::from_u32(1)));
+        assert!(!NonFungibleIdType::U32.is_valid(&NonFungibleId::from_u64(1)));
+        assert!(NonFungibleIdType::Uuid.is_valid(&NonFungibleId::from_uuid(1)));
+        assert!(!NonFungibleIdType::String.is_valid(&NonFungibleId::from_bytes(vec![0xff])));
+        assert!(NonFungibleIdType::Bytes.is_valid(&NonFungibleId::from_bytes(vec![0xff])));
+        assert_eq!(
+            NonFungibleIdType::U32.sequential_id(7),
+            Some(NonFungibleId::from_u32(7))
+        );
+        assert!(NonFungibleIdType::U32.is_sequential());
+        assert_eq!(NonFungibleIdType::U32.sequential_id(u64::MAX), None);
+        assert!(!NonFungibleIdType::String.is_sequential());
+        assert_eq!(NonFungibleIdType::String.sequential_id(7), None);
+    }
 }
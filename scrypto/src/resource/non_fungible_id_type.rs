@@ -0,0 +1,52 @@
+use sbor::*;
+
+use crate::resource::*;
+use crate::rust::string::String;
+
+/// Represents the type of the ids of a non-fungible resource.
+#[derive(Debug, Clone, Copy, TypeId, Encode, Decode, Describe, Eq, PartialEq)]
+pub enum NonFungibleIdType {
+    /// A big-endian `u32` number; supports auto-increment
+    U32,
+
+    /// A big-endian `u64` number; supports auto-increment
+    U64,
+
+    /// A big-endian `u128` UUID, as generated by `NonFungibleId::random`
+    Uuid,
+
+    /// A UTF-8 string
+    String,
+
+    /// Arbitrary bytes
+    Bytes,
+}
+
+impl NonFungibleIdType {
+    /// Returns whether the given id is of this type.
+    pub fn is_valid(&self, id: &NonFungibleId) -> bool {
+        let bytes = id.to_vec();
+        match self {
+            Self::U32 => bytes.len() == 4,
+            Self::U64 => bytes.len() == 8,
+            Self::Uuid => bytes.len() == 16,
+            Self::String => String::from_utf8(bytes).is_ok(),
+            Self::Bytes => true,
+        }
+    }
+
+    /// Returns whether this type supports auto-increment.
+    pub fn is_sequential(&self) -> bool {
+        matches!(self, Self::U32 | Self::U64)
+    }
+
+    /// Returns the id of the given sequence number, if this type supports auto-increment and
+    /// the number fits in it.
+    pub fn sequential_id(&self, sequence: u64) -> Option<NonFungibleId> {
+        match self {
+            Self::U32 => u32::try_from(sequence).ok().map(NonFungibleId::from_u32),
+            Self::U64 => Some(NonFungibleId::from_u64(sequence)),
+            Self::Uuid | Self::String | Self::Bytes => None,
+        }
+    }
+}
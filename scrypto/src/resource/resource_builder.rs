@@ -15,11 +15,13 @@ pub struct ResourceBuilder;
 
 pub struct FungibleResourceBuilder {
     divisibility: u8,
+    max_supply: Option<Decimal>,
     metadata: HashMap<String, String>,
     authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
 }
 
 pub struct NonFungibleResourceBuilder {
+    id_type: Option<NonFungibleIdType>,
     metadata: HashMap<String, String>,
     authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
 }
@@ -40,6 +42,7 @@ impl FungibleResourceBuilder {
     pub fn new() -> Self {
         Self {
             divisibility: DIVISIBILITY_MAXIMUM,
+            max_supply: None,
             metadata: HashMap::new(),
             authorization: HashMap::new(),
         }
@@ -54,6 +57,14 @@ impl FungibleResourceBuilder {
         self
     }
 
+    /// Caps the total supply.
+    ///
+    /// Minting, including the initial supply, fails once the total supply would exceed the cap.
+    pub fn max_supply<T: Into<Decimal>>(&mut self, max_supply: T) -> &mut Self {
+        self.max_supply = Some(max_supply.into());
+        self
+    }
+
     /// Adds a resource metadata.
     ///
     /// If a previous attribute with the same name has been set, it will be overwritten.
@@ -103,6 +114,16 @@ impl FungibleResourceBuilder {
         self
     }
 
+    pub fn recallable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Recall, (method_auth, mutability));
+        self
+    }
+
+    pub fn freezable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Freeze, (method_auth, mutability));
+        self
+    }
+
     /// Creates resource with the given initial supply.
     ///
     /// # Example
@@ -126,13 +147,17 @@ impl FungibleResourceBuilder {
             authorization.insert(Withdraw, (rule!(allow_all), LOCKED));
         }
 
-        resource_system().new_resource(
+        resource_system().new_resource_with_options(
             ResourceType::Fungible {
                 divisibility: self.divisibility,
             },
             self.metadata.clone(),
             authorization,
             mint_params,
+            ResourceOptions {
+                max_supply: self.max_supply,
+                non_fungible_id_type: None,
+            },
         )
     }
 }
@@ -140,11 +165,20 @@ impl FungibleResourceBuilder {
 impl NonFungibleResourceBuilder {
     pub fn new() -> Self {
         Self {
+            id_type: None,
             metadata: HashMap::new(),
             authorization: HashMap::new(),
         }
     }
 
+    /// Sets the type of the ids; minted ids of any other type are rejected.
+    ///
+    /// Defaults to `NonFungibleIdType::Bytes`, which accepts any id.
+    pub fn id_type(&mut self, id_type: NonFungibleIdType) -> &mut Self {
+        self.id_type = Some(id_type);
+        self
+    }
+
     /// Adds a resource metadata.
     ///
     /// If a previous attribute with the same name has been set, it will be overwritten.
@@ -204,6 +238,16 @@ impl NonFungibleResourceBuilder {
         self
     }
 
+    pub fn recallable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Recall, (method_auth, mutability));
+        self
+    }
+
+    pub fn freezable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Freeze, (method_auth, mutability));
+        self
+    }
+
     /// Creates resource with the given initial supply.
     ///
     /// # Example
@@ -236,11 +280,15 @@ impl NonFungibleResourceBuilder {
             authorization.insert(Withdraw, (rule!(allow_all), LOCKED));
         }
 
-        resource_system().new_resource(
+        resource_system().new_resource_with_options(
             ResourceType::NonFungible,
             self.metadata.clone(),
             authorization,
             mint_params,
+            ResourceOptions {
+                max_supply: None,
+                non_fungible_id_type: self.id_type,
+            },
         )
     }
 }
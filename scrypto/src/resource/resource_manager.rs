@@ -2,13 +2,14 @@ use sbor::*;
 
 use crate::args;
 use crate::buffer::scrypto_decode;
+use crate::component::ComponentAddress;
 use crate::core::SNodeRef;
-use crate::engine::{api::*, call_engine};
+use crate::engine::{api::*, call_engine, types::VaultId};
 use crate::math::*;
 use crate::misc::*;
 use crate::resource::*;
 use crate::rust::borrow::ToOwned;
-use crate::rust::collections::HashMap;
+use crate::rust::collections::{BTreeSet, HashMap};
 use crate::rust::fmt;
 use crate::rust::str::FromStr;
 use crate::rust::string::String;
@@ -24,6 +25,8 @@ pub enum ResourceMethod {
     Deposit,
     UpdateMetadata,
     UpdateNonFungibleData,
+    Recall,
+    Freeze,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TypeId, Encode, Decode, Describe)]
@@ -33,7 +36,7 @@ pub enum Mutability {
 }
 
 /// Represents a resource address.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ResourceAddress(pub [u8; 26]);
 
 impl ResourceAddress {}
@@ -90,6 +93,20 @@ impl ResourceManager {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Mints a non-fungible with the next sequential id.
+    ///
+    /// # Panics
+    /// Panics if the id type of this resource is neither `U32` nor `U64`.
+    pub fn mint_next_non_fungible<T: NonFungibleData>(&self, data: T) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "mint_next_non_fungible".to_string(),
+            args: args![data.immutable_data(), data.mutable_data()],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Burns a bucket of resources.
     pub fn burn(&self, bucket: Bucket) {
         let input = InvokeSNodeInput {
@@ -211,6 +228,46 @@ impl ResourceManager {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    pub fn set_recallable(&self, recall_auth: AccessRule) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Recall, "update", recall_auth],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    pub fn lock_recallable(&self) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Recall, "lock"],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    pub fn set_freezable(&self, freeze_auth: AccessRule) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Freeze, "update", freeze_auth],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    pub fn lock_freezable(&self) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Freeze, "lock"],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Returns the metadata associated with this resource.
     pub fn metadata(&self) -> HashMap<String, String> {
         let input = InvokeSNodeInput {
@@ -233,6 +290,31 @@ impl ResourceManager {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Returns the cap on the total supply of this resource, if any.
+    pub fn max_supply(&self) -> Option<Decimal> {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "get_max_supply".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Returns the type of the ids of this resource.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible resource.
+    pub fn non_fungible_id_type(&self) -> NonFungibleIdType {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "get_non_fungible_id_type".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Returns the data of a non-fungible unit, both the immutable and mutable parts.
     ///
     /// # Panics
@@ -282,6 +364,65 @@ impl ResourceManager {
         };
         let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
     }
+
+    /// Takes some amount of this resource out of a vault held by any component.
+    ///
+    /// # Panics
+    /// Panics if the vault is not found or does not have enough balance.
+    pub fn recall<A: Into<Decimal>>(
+        &self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        amount: A,
+    ) -> Bucket {
+        let amount: Decimal = amount.into();
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentVaultRef(self.0, component_address, vault_id),
+            function: "recall_from_vault".to_string(),
+            args: args![amount],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Takes some non-fungibles of this resource out of a vault held by any component.
+    ///
+    /// # Panics
+    /// Panics if the vault is not found or does not contain all the non-fungibles.
+    pub fn recall_non_fungibles(
+        &self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        non_fungible_ids: &BTreeSet<NonFungibleId>,
+    ) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentVaultRef(self.0, component_address, vault_id),
+            function: "recall_non_fungibles_from_vault".to_string(),
+            args: args![non_fungible_ids.clone()],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Blocks withdraws from and deposits into a vault held by any component.
+    pub fn freeze(&self, component_address: ComponentAddress, vault_id: VaultId) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentVaultRef(self.0, component_address, vault_id),
+            function: "freeze_vault".to_string(),
+            args: args![],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Allows withdraws from and deposits into a frozen vault again.
+    pub fn unfreeze(&self, component_address: ComponentAddress, vault_id: VaultId) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentVaultRef(self.0, component_address, vault_id),
+            function: "unfreeze_vault".to_string(),
+            args: args![],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
 }
 
 //========
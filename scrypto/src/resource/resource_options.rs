@@ -0,0 +1,14 @@
+use sbor::*;
+
+use crate::math::*;
+use crate::resource::*;
+
+/// Represents the optional settings of a new resource.
+#[derive(Debug, Clone, Default, TypeId, Encode, Decode, Describe)]
+pub struct ResourceOptions {
+    /// The maximum total supply, for fungible resources
+    pub max_supply: Option<Decimal>,
+
+    /// The type of the ids, for non-fungible resources
+    pub non_fungible_id_type: Option<NonFungibleIdType>,
+}
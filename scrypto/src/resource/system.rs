@@ -44,11 +44,32 @@ impl ResourceSystem {
         metadata: HashMap<String, String>,
         authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
         mint_params: Option<MintParams>,
+    ) -> (ResourceAddress, Option<Bucket>) {
+        self.new_resource_with_options(
+            resource_type,
+            metadata,
+            authorization,
+            mint_params,
+            ResourceOptions::default(),
+        )
+    }
+
+    /// Creates a new resource with the given parameters and optional settings, such as a supply
+    /// cap or an id type.
+    ///
+    /// A bucket is returned iif an initial supply is provided.
+    pub fn new_resource_with_options(
+        &mut self,
+        resource_type: ResourceType,
+        metadata: HashMap<String, String>,
+        authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
+        mint_params: Option<MintParams>,
+        options: ResourceOptions,
     ) -> (ResourceAddress, Option<Bucket>) {
         let input = InvokeSNodeInput {
             snode_ref: SNodeRef::ResourceStatic,
-            function: "create".to_string(),
-            args: args![resource_type, metadata, authorization, mint_params],
+            function: "create_with_options".to_string(),
+            args: args![resource_type, metadata, authorization, mint_params, options],
         };
         let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
         scrypto_decode(&output.rtn).unwrap()
@@ -33,6 +33,24 @@ impl Vault {
         Self(output.vault_id)
     }
 
+    /// Creates an empty vault with its own access rules, keyed by vault function name, such as
+    /// `take_from_vault` or `put_into_vault`.
+    ///
+    /// The rules apply on top of those of the resource, to any call to the listed functions,
+    /// including the ones made by the component holding the vault. Other functions, as well as
+    /// recalls and freezes, are left to the resource, so the default rule is not used. The rules
+    /// can not refer to schema paths.
+    pub fn with_access_rules(resource_address: ResourceAddress, access_rules: AccessRules) -> Self {
+        let input = CreateEmptyVaultWithAccessRulesInput {
+            resource_address,
+            access_rules,
+        };
+        let output: CreateEmptyVaultOutput =
+            call_engine(CREATE_EMPTY_VAULT_WITH_ACCESS_RULES, input);
+
+        Self(output.vault_id)
+    }
+
     /// Creates an empty vault and fills it with an initial bucket of resource.
     pub fn with_bucket(bucket: Bucket) -> Self {
         let mut vault = Vault::new(bucket.resource_address());
@@ -64,6 +82,24 @@ impl Vault {
         bucket
     }
 
+    /// Locks some amount of XRD from this vault to pay the transaction fee.
+    ///
+    /// Any unused fee is refunded at the end of the transaction. The locked fee is charged
+    /// even if the transaction fails.
+    ///
+    /// # Panics
+    /// Panics if this is not an XRD vault or the vault does not have enough balance.
+    pub fn lock_fee<A: Into<Decimal>>(&mut self, amount: A) {
+        let amount: Decimal = amount.into();
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "lock_fee".to_string(),
+            args: args![amount],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Takes all resource stored in this vault.
     pub fn take_all(&mut self) -> Bucket {
         self.take(self.amount())
@@ -143,6 +179,17 @@ impl Vault {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Returns whether withdraws from and deposits into this vault are blocked.
+    pub fn is_frozen(&self) -> bool {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "is_vault_frozen".to_string(),
+            args: vec![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Returns the resource address.
     pub fn resource_address(&self) -> ResourceAddress {
         let input = InvokeSNodeInput {
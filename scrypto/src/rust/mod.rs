@@ -3,6 +3,8 @@ extern crate alloc;
 #[cfg(feature = "alloc")]
 pub use alloc::borrow;
 #[cfg(feature = "alloc")]
+pub use alloc::boxed;
+#[cfg(feature = "alloc")]
 pub use alloc::fmt;
 #[cfg(feature = "alloc")]
 pub use alloc::format;
@@ -17,6 +19,8 @@ pub use alloc::vec;
 #[cfg(feature = "alloc")]
 pub use core::cell;
 #[cfg(feature = "alloc")]
+pub use core::cmp;
+#[cfg(feature = "alloc")]
 pub use core::convert;
 #[cfg(feature = "alloc")]
 pub use core::marker;
@@ -30,8 +34,12 @@ pub use core::ptr;
 #[cfg(not(feature = "alloc"))]
 pub use std::borrow;
 #[cfg(not(feature = "alloc"))]
+pub use std::boxed;
+#[cfg(not(feature = "alloc"))]
 pub use std::cell;
 #[cfg(not(feature = "alloc"))]
+pub use std::cmp;
+#[cfg(not(feature = "alloc"))]
 pub use std::convert;
 #[cfg(not(feature = "alloc"))]
 pub use std::fmt;
@@ -153,6 +153,8 @@ pub enum ScryptoCustomValueCheckError {
     InvalidHash(ParseHashError),
     InvalidEcdsaPublicKey(ParseEcdsaPublicKeyError),
     InvalidEcdsaSignature(ParseEcdsaSignatureError),
+    InvalidEd25519PublicKey(ParseEd25519PublicKeyError),
+    InvalidEd25519Signature(ParseEd25519SignatureError),
     InvalidBucket(ParseBucketError),
     InvalidProof(ParseProofError),
     InvalidLazyMap(ParseLazyMapError),
@@ -204,6 +206,14 @@ impl CustomValueVisitor for ScryptoCustomValueChecker {
                 EcdsaSignature::try_from(data)
                     .map_err(ScryptoCustomValueCheckError::InvalidEcdsaSignature)?;
             }
+            ScryptoType::Ed25519PublicKey => {
+                Ed25519PublicKey::try_from(data)
+                    .map_err(ScryptoCustomValueCheckError::InvalidEd25519PublicKey)?;
+            }
+            ScryptoType::Ed25519Signature => {
+                Ed25519Signature::try_from(data)
+                    .map_err(ScryptoCustomValueCheckError::InvalidEd25519Signature)?;
+            }
             ScryptoType::Decimal => {
                 Decimal::try_from(data).map_err(ScryptoCustomValueCheckError::InvalidDecimal)?;
             }
@@ -458,6 +468,18 @@ impl ScryptoValueFormatter {
                     EcdsaSignature::try_from(data).unwrap()
                 )
             }
+            ScryptoType::Ed25519PublicKey => {
+                format!(
+                    "Ed25519PublicKey(\"{}\")",
+                    Ed25519PublicKey::try_from(data).unwrap()
+                )
+            }
+            ScryptoType::Ed25519Signature => {
+                format!(
+                    "Ed25519Signature(\"{}\")",
+                    Ed25519Signature::try_from(data).unwrap()
+                )
+            }
             ScryptoType::Bucket => {
                 let bucket = Bucket::try_from(data).unwrap();
                 if let Some(name) = bucket_ids.get(&bucket.0) {
@@ -475,6 +497,7 @@ impl ScryptoValueFormatter {
                 }
             }
             ScryptoType::Vault => format!("Vault(\"{}\")", Vault::try_from(data).unwrap()),
+            // In hex, which the manifest parses back into the same id whatever its type
             ScryptoType::NonFungibleId => format!(
                 "NonFungibleId(\"{}\")",
                 NonFungibleId::try_from(data).unwrap()
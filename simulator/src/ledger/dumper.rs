@@ -2,19 +2,26 @@
 use colored::*;
 use radix_engine::ledger::*;
 use radix_engine::model::*;
+use scrypto::buffer::*;
 use scrypto::engine::types::*;
+use scrypto::resource::NonFungibleIdType;
 use scrypto::rust::collections::HashSet;
 use scrypto::values::*;
 use std::collections::VecDeque;
+use transaction_manifest::decompile;
 
 use crate::utils::*;
 
+const NON_FUNGIBLES_PAGE_SIZE: usize = 100;
+
 /// Represents an error when displaying an entity.
 #[derive(Debug, Clone)]
 pub enum DisplayError {
     PackageNotFound,
     ComponentNotFound,
     ResourceManagerNotFound,
+    ResourceNotNonFungible,
+    TransactionNotFound,
 }
 
 /// Dump a package into console.
@@ -187,7 +194,10 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
                         "{}  {} NonFungible {{ id: {}, immutable_data: {}, mutable_data: {} }}",
                         if last { " " } else { "│" },
                         list_item_prefix(inner_last),
-                        id,
+                        resource_manager
+                            .non_fungible_id_type()
+                            .map(|id_type| id.to_typed_string(id_type))
+                            .unwrap_or(id.to_string()),
                         immutable_data,
                         mutable_data
                     );
@@ -236,8 +246,174 @@ pub fn dump_resource_manager<T: SubstateStore, O: std::io::Write>(
                 "Total Supply".green().bold(),
                 r.total_supply()
             );
+            if let Some(id_type) = r.non_fungible_id_type() {
+                writeln!(output, "{}: {:?}", "ID Type".green().bold(), id_type);
+            }
+            if let Some(max_supply) = r.max_supply() {
+                writeln!(output, "{}: {}", "Max Supply".green().bold(), max_supply);
+            }
             Ok(())
         }
         None => Err(DisplayError::ResourceManagerNotFound),
     }
 }
+
+/// Dump all non-fungibles of a resource into console.
+pub fn dump_non_fungibles<T: SubstateStore + QueryableSubstateStore, O: std::io::Write>(
+    resource_address: ResourceAddress,
+    substate_store: &T,
+    output: &mut O,
+) -> Result<(), DisplayError> {
+    let resource_manager: ResourceManager = substate_store
+        .get_decoded_substate(&resource_address)
+        .map(|(resource, _)| resource)
+        .ok_or(DisplayError::ResourceManagerNotFound)?;
+    let id_type = resource_manager
+        .non_fungible_id_type()
+        .ok_or(DisplayError::ResourceNotNonFungible)?;
+
+    writeln!(output, "{}:", "Non-fungibles".green().bold());
+
+    // Print each entry once the next one is fetched, so that the last one is known
+    let mut previous: Option<NonFungibleEntry> = None;
+    loop {
+        let page = substate_store.get_non_fungibles(
+            resource_address,
+            previous.as_ref().map(|e| &e.id),
+            NON_FUNGIBLES_PAGE_SIZE,
+        );
+        let exhausted = page.len() < NON_FUNGIBLES_PAGE_SIZE;
+        for e in page {
+            if let Some(previous) = previous.replace(e) {
+                dump_non_fungible(&previous, id_type, false, output);
+            }
+        }
+        if exhausted {
+            break;
+        }
+    }
+    if let Some(last) = previous {
+        dump_non_fungible(&last, id_type, true, output);
+    }
+    Ok(())
+}
+
+fn dump_non_fungible<O: std::io::Write>(
+    e: &NonFungibleEntry,
+    id_type: NonFungibleIdType,
+    last: bool,
+    output: &mut O,
+) {
+    writeln!(
+        output,
+        "{} NonFungible {{ id: {}, immutable_data: {}, mutable_data: {} }}",
+        list_item_prefix(last),
+        e.id.to_typed_string(id_type),
+        e.immutable_data,
+        e.mutable_data
+    );
+}
+
+/// Dump a committed transaction into console.
+pub fn dump_transaction<T: TransactionStore, O: std::io::Write>(
+    hash: Hash,
+    transaction_store: &T,
+    output: &mut O,
+) -> Result<(), DisplayError> {
+    let committed = transaction_store
+        .get_transaction_by_hash(&hash)
+        .ok_or(DisplayError::TransactionNotFound)?;
+    let signed: SignedTransaction = scrypto_decode(&committed.transaction).unwrap();
+    let receipt = &committed.receipt;
+
+    writeln!(output, "{}: {}", "Transaction Hash".green().bold(), hash);
+    writeln!(output, "{}: {}", "Index".green().bold(), committed.index);
+    writeln!(
+        output,
+        "{}: {}",
+        "State Version".green().bold(),
+        committed.state_version
+    );
+    writeln!(
+        output,
+        "{}: {}",
+        "Status".green().bold(),
+        match &receipt.result {
+            Ok(()) => "SUCCESS".blue(),
+            Err(e) => e.red(),
+        }
+    );
+
+    writeln!(
+        output,
+        "{}: {}",
+        "Signers".green().bold(),
+        signed.signatures.len()
+    );
+    for (last, (public_key, _)) in signed.signatures.iter().identify_last() {
+        writeln!(output, "{} {:?}", list_item_prefix(last), public_key);
+    }
+
+    writeln!(output, "{}:", "Manifest".green().bold());
+    match decompile(&signed.transaction) {
+        Ok(manifest) => write!(output, "{}", manifest),
+        Err(e) => writeln!(output, "{:?}", e),
+    };
+
+    writeln!(output, "{}: {}", "Logs".green().bold(), receipt.logs.len());
+    for (last, (level, message)) in receipt.logs.iter().identify_last() {
+        writeln!(
+            output,
+            "{} [{:5}] {}",
+            list_item_prefix(last),
+            format!("{:?}", level).to_uppercase(),
+            message
+        );
+    }
+
+    writeln!(
+        output,
+        "{}: {}",
+        "Events".green().bold(),
+        receipt.events.len()
+    );
+    for (last, event) in receipt.events.iter().identify_last() {
+        writeln!(
+            output,
+            "{} {} {}",
+            list_item_prefix(last),
+            event.name.cyan(),
+            ScryptoValue::from_slice(&event.data)
+                .map(|value| value.to_string())
+                .unwrap_or_default()
+        );
+    }
+
+    let new_entities: Vec<String> = receipt
+        .new_package_addresses
+        .iter()
+        .map(|address| format!("Package: {}", address))
+        .chain(
+            receipt
+                .new_component_addresses
+                .iter()
+                .map(|address| format!("Component: {}", address)),
+        )
+        .chain(
+            receipt
+                .new_resource_addresses
+                .iter()
+                .map(|address| format!("Resource: {}", address)),
+        )
+        .collect();
+    writeln!(
+        output,
+        "{}: {}",
+        "New Entities".green().bold(),
+        new_entities.len()
+    );
+    for (last, entity) in new_entities.iter().identify_last() {
+        writeln!(output, "{} {}", list_item_prefix(last), entity);
+    }
+    Ok(())
+}
@@ -1,25 +1,35 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 use radix_engine::ledger::*;
-use rocksdb::{DBWithThreadMode, Direction, IteratorMode, SingleThreaded, DB};
+use radix_engine::model::LOCAL_NETWORK_ID;
+use rand::Rng;
+use rocksdb::{DBWithThreadMode, Direction, IteratorMode, SingleThreaded, WriteBatch, DB};
 use sbor::{Decode, Encode};
 use scrypto::buffer::*;
 use scrypto::engine::types::*;
 
 pub struct RadixEngineDB {
     db: DBWithThreadMode<SingleThreaded>,
+    /// The writes of the commit in progress, if any
+    batch: Option<WriteBatch>,
 }
 
 impl RadixEngineDB {
     pub fn new(root: PathBuf) -> Self {
         let db = DB::open_default(root.as_path()).unwrap();
-        Self { db }
+        Self { db, batch: None }
     }
 
     pub fn with_bootstrap(root: PathBuf) -> Self {
         let mut ledger = Self::new(root);
         ledger.bootstrap();
+        // Each ledger gets its own network, so that transactions can't be replayed on another
+        let network_id_key = scrypto_encode(&"network_id");
+        if ledger.read(&network_id_key).is_none() {
+            let network_id: u32 = rand::thread_rng().gen_range(1..=u32::MAX);
+            ledger.write(&network_id_key, &scrypto_encode(&network_id));
+        }
         ledger
     }
 
@@ -57,13 +67,164 @@ impl RadixEngineDB {
         items
     }
 
+    fn list_committed_intents(&self) -> Vec<Hash> {
+        let mut prefix = scrypto_encode(&("intent", Hash([0; Hash::LENGTH])));
+        prefix.truncate(prefix.len() - Hash::LENGTH);
+        self.db
+            .iterator(IteratorMode::From(&prefix, Direction::Forward))
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, _)| {
+                let (_, intent_hash): (String, Hash) = scrypto_decode(&key).unwrap();
+                intent_hash
+            })
+            .collect()
+    }
+
+    /// Exports the substates, committed intents, epoch, nonce and state version of this ledger.
+    pub fn export_snapshot(&self) -> LedgerSnapshot {
+        let mut substates = Vec::new();
+        let mut child_substates = Vec::new();
+        for package_address in self.list_packages() {
+            self.export_substate(&package_address, &mut substates, &mut child_substates);
+        }
+        for component_address in self.list_components() {
+            self.export_substate(&component_address, &mut substates, &mut child_substates);
+        }
+        for resource_address in self.list_resource_managers() {
+            self.export_substate(&resource_address, &mut substates, &mut child_substates);
+        }
+
+        LedgerSnapshot {
+            substates,
+            child_substates,
+            committed_intents: self.list_committed_intents(),
+            epoch: self.get_epoch(),
+            nonce: self.get_nonce(),
+            state_version: self.get_state_version(),
+        }
+    }
+
+    fn export_substate<A: Encode>(
+        &self,
+        address: &A,
+        substates: &mut Vec<(Vec<u8>, Substate)>,
+        child_substates: &mut Vec<(Vec<u8>, Substate)>,
+    ) {
+        let id = scrypto_encode(address);
+        substates.push((id.clone(), self.get_substate(address).unwrap()));
+        for (key, substate) in self.get_child_substates(address, &[], None, usize::MAX) {
+            let mut child_id = id.clone();
+            child_id.extend(key);
+            child_substates.push((child_id, substate));
+        }
+    }
+
+    /// Replaces the content of this ledger with a snapshot, which becomes the start of history.
+    pub fn import_snapshot(&mut self, snapshot: LedgerSnapshot) {
+        let keys: Vec<Box<[u8]>> = self
+            .db
+            .iterator(IteratorMode::Start)
+            .map(|(key, _)| key)
+            .collect();
+        for key in keys {
+            self.delete(&key);
+        }
+
+        for (id, substate) in snapshot
+            .substates
+            .iter()
+            .chain(snapshot.child_substates.iter())
+        {
+            self.write_substate_at(id, Some(substate), snapshot.state_version);
+        }
+        let updates: Vec<(Vec<u8>, Option<Vec<u8>>)> = snapshot
+            .substates
+            .iter()
+            .chain(snapshot.child_substates.iter())
+            .map(|(id, substate)| (id.clone(), Some(substate.value.clone())))
+            .collect();
+        self.update_state_tree(snapshot.state_version, &updates);
+        for intent_hash in snapshot.committed_intents {
+            self.put_committed_intent(intent_hash);
+        }
+        self.set_epoch(snapshot.epoch);
+        self.write(&scrypto_encode(&"nonce"), &scrypto_encode(&snapshot.nonce));
+        self.write(
+            &scrypto_encode(&"state_version"),
+            &scrypto_encode(&snapshot.state_version),
+        );
+        self.write(
+            &scrypto_encode(&"base_state_version"),
+            &scrypto_encode(&snapshot.state_version),
+        );
+    }
+
+    /// Writes a substate, keeping its value in the history of the next state version.
+    fn write_substate(&mut self, id: &[u8], substate: Option<&Substate>) {
+        self.write_substate_at(id, substate, self.get_state_version() + 1)
+    }
+
+    fn write_substate_at(&mut self, id: &[u8], substate: Option<&Substate>, version: u64) {
+        self.atomically(|db| {
+            let mut history_id = scrypto_encode(&("substate_history", id.to_vec()));
+            history_id.extend(version.to_be_bytes());
+            db.write(&history_id, &scrypto_encode(&substate.cloned()));
+            let mut change_id = scrypto_encode(&("state_version_changes", version));
+            change_id.extend(id.to_vec());
+            db.write(&change_id, &[]);
+
+            match substate {
+                Some(substate) => db.write(id, &scrypto_encode(substate)),
+                None => db.delete(id),
+            }
+        })
+    }
+
+    fn read_substate_at(&self, id: &[u8], version: u64) -> Option<Substate> {
+        let prefix = scrypto_encode(&("substate_history", id.to_vec()));
+        let mut history_id = prefix.clone();
+        history_id.extend(version.to_be_bytes());
+
+        let mut iter = self
+            .db
+            .iterator(IteratorMode::From(&history_id, Direction::Reverse));
+        match iter.next() {
+            Some((key, value)) if key.starts_with(&prefix) => {
+                let substate: Option<Substate> = scrypto_decode(&value).unwrap();
+                substate
+            }
+            _ => None,
+        }
+    }
+
+    /// Applies the writes made by `f` all at once, as part of the commit in progress if any.
+    fn atomically<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        if self.batch.is_some() {
+            f(self);
+        } else {
+            self.begin_commit();
+            f(self);
+            self.end_commit();
+        }
+    }
+
     fn read(&self, key: &[u8]) -> Option<Vec<u8>> {
         // TODO: Use get_pinned
         self.db.get(key).unwrap()
     }
 
-    fn write(&self, key: &[u8], value: &[u8]) {
-        self.db.put(key, value).unwrap();
+    fn write(&mut self, key: &[u8], value: &[u8]) {
+        match &mut self.batch {
+            Some(batch) => batch.put(key, value),
+            None => self.db.put(key, value).unwrap(),
+        }
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        match &mut self.batch {
+            Some(batch) => batch.delete(key),
+            None => self.db.delete(key).unwrap(),
+        }
     }
 }
 
@@ -94,6 +255,44 @@ impl QueryableSubstateStore for RadixEngineDB {
     }
 }
 
+impl TransactionStore for RadixEngineDB {
+    fn get_transaction_count(&self) -> u64 {
+        let id = scrypto_encode(&"transaction_count");
+        self.read(&id)
+            .map(|v| scrypto_decode(&v).unwrap())
+            .unwrap_or(0)
+    }
+
+    fn get_transaction_by_index(&self, index: u64) -> Option<CommittedTransaction> {
+        let id = scrypto_encode(&("transaction", index));
+        self.read(&id).map(|v| scrypto_decode(&v).unwrap())
+    }
+
+    fn get_transaction_by_hash(&self, hash: &Hash) -> Option<CommittedTransaction> {
+        let id = scrypto_encode(&("transaction_index", *hash));
+        self.read(&id)
+            .and_then(|v| self.get_transaction_by_index(scrypto_decode(&v).unwrap()))
+    }
+
+    fn put_transaction(&mut self, transaction: CommittedTransaction) {
+        let index = transaction.index;
+        self.atomically(|db| {
+            db.write(
+                &scrypto_encode(&("transaction_index", transaction.hash)),
+                &scrypto_encode(&index),
+            );
+            db.write(
+                &scrypto_encode(&("transaction", index)),
+                &scrypto_encode(&transaction),
+            );
+            db.write(
+                &scrypto_encode(&"transaction_count"),
+                &scrypto_encode(&(index + 1)),
+            );
+        })
+    }
+}
+
 impl SubstateStore for RadixEngineDB {
     fn get_substate<T: Encode>(&self, address: &T) -> Option<Substate> {
         self.read(&scrypto_encode(address))
@@ -101,7 +300,7 @@ impl SubstateStore for RadixEngineDB {
     }
 
     fn put_substate<T: Encode>(&mut self, address: &T, substate: Substate) {
-        self.write(&scrypto_encode(address), &scrypto_encode(&substate));
+        self.write_substate(&scrypto_encode(address), Some(&substate));
     }
 
     fn get_child_substate<T: Encode>(&self, address: &T, key: &[u8]) -> Option<Substate> {
@@ -113,7 +312,206 @@ impl SubstateStore for RadixEngineDB {
     fn put_child_substate<T: Encode>(&mut self, address: &T, key: &[u8], substate: Substate) {
         let mut id = scrypto_encode(address);
         id.extend(key.to_vec());
-        self.write(&id, &scrypto_encode(&substate));
+        self.write_substate(&id, Some(&substate));
+    }
+
+    fn delete_child_substate<T: Encode>(&mut self, address: &T, key: &[u8]) {
+        let mut id = scrypto_encode(address);
+        id.extend(key.to_vec());
+        self.write_substate(&id, None);
+    }
+
+    fn get_child_substates<T: Encode>(
+        &self,
+        address: &T,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, Substate)> {
+        let mut id = scrypto_encode(address);
+        let address_len = id.len();
+        id.extend(prefix.to_vec());
+        let mut start = id.clone();
+        if let Some(after) = after {
+            start.extend(after.to_vec());
+        }
+
+        let iter = self
+            .db
+            .iterator(IteratorMode::From(&start, Direction::Forward));
+        let mut items = Vec::new();
+        for (key, value) in iter {
+            if items.len() >= limit || !key.starts_with(&id) {
+                break;
+            }
+            if after.is_some() && key.as_ref() == start.as_slice() {
+                continue;
+            }
+            // The parent itself is stored next to its children
+            if key.len() == address_len {
+                continue;
+            }
+
+            let local_key = key.split_at(id.len()).1.to_vec();
+            items.push((local_key, scrypto_decode(&value).unwrap()));
+        }
+        items
+    }
+
+    fn get_network_id(&self) -> u32 {
+        let id = scrypto_encode(&"network_id");
+        self.read(&id)
+            .map(|v| scrypto_decode(&v).unwrap())
+            .unwrap_or(LOCAL_NETWORK_ID)
+    }
+
+    fn is_intent_committed(&self, intent_hash: &Hash) -> bool {
+        let id = scrypto_encode(&("intent", *intent_hash));
+        self.read(&id).is_some()
+    }
+
+    fn put_committed_intent(&mut self, intent_hash: Hash) {
+        let id = scrypto_encode(&("intent", intent_hash));
+        self.write(&id, &[])
+    }
+
+    fn begin_commit(&mut self) {
+        self.batch = Some(WriteBatch::default());
+    }
+
+    fn end_commit(&mut self) {
+        if let Some(batch) = self.batch.take() {
+            self.db.write(batch).unwrap();
+        }
+    }
+
+    fn get_state_version(&self) -> u64 {
+        let id = scrypto_encode(&"state_version");
+        self.read(&id)
+            .map(|v| scrypto_decode(&v).unwrap())
+            .unwrap_or(0)
+    }
+
+    fn increase_state_version(&mut self) {
+        let id = scrypto_encode(&"state_version");
+        let value = scrypto_encode(&(self.get_state_version() + 1));
+        self.write(&id, &value)
+    }
+
+    fn get_substate_at<T: Encode>(&self, address: &T, version: u64) -> Option<Substate> {
+        self.read_substate_at(&scrypto_encode(address), version)
+    }
+
+    fn get_child_substate_at<T: Encode>(
+        &self,
+        address: &T,
+        key: &[u8],
+        version: u64,
+    ) -> Option<Substate> {
+        let mut id = scrypto_encode(address);
+        id.extend(key.to_vec());
+        self.read_substate_at(&id, version)
+    }
+
+    fn get_base_state_version(&self) -> u64 {
+        let id = scrypto_encode(&"base_state_version");
+        self.read(&id)
+            .map(|v| scrypto_decode(&v).unwrap())
+            .unwrap_or(0)
+    }
+
+    fn rollback_to(&mut self, version: u64) {
+        assert!(
+            version >= self.get_base_state_version(),
+            "Can't roll back to before the base state version"
+        );
+        let current_version = self.get_state_version();
+        if version >= current_version {
+            return;
+        }
+
+        self.atomically(|db| {
+            let mut reverted = HashSet::new();
+            for v in version + 1..=current_version {
+                let prefix = scrypto_encode(&("state_version_changes", v));
+                let changes: Vec<Vec<u8>> = db
+                    .db
+                    .iterator(IteratorMode::From(&prefix, Direction::Forward))
+                    .take_while(|(key, _)| key.starts_with(&prefix))
+                    .map(|(key, _)| key.to_vec())
+                    .collect();
+                for change_id in changes {
+                    let id = change_id[prefix.len()..].to_vec();
+                    let mut history_id = scrypto_encode(&("substate_history", id.clone()));
+                    history_id.extend(v.to_be_bytes());
+                    db.delete(&history_id);
+                    db.delete(&change_id);
+                    reverted.insert(id);
+                }
+            }
+            for v in version + 1..=current_version {
+                let prefix = scrypto_encode(&("state_tree_changes", v));
+                let changes: Vec<Vec<u8>> = db
+                    .db
+                    .iterator(IteratorMode::From(&prefix, Direction::Forward))
+                    .take_while(|(key, _)| key.starts_with(&prefix))
+                    .map(|(key, _)| key.to_vec())
+                    .collect();
+                for change_id in changes {
+                    let key: StateTreeNodeKey = scrypto_decode(&change_id[prefix.len()..]).unwrap();
+                    let mut node_id = scrypto_encode(&("state_tree", key));
+                    node_id.extend(v.to_be_bytes());
+                    db.delete(&node_id);
+                    db.delete(&change_id);
+                }
+            }
+            for id in reverted {
+                match db.read_substate_at(&id, version) {
+                    Some(substate) => db.write(&id, &scrypto_encode(&substate)),
+                    None => db.delete(&id),
+                }
+            }
+
+            let mut count = db.get_transaction_count();
+            while count > 0 {
+                let transaction = db.get_transaction_by_index(count - 1).unwrap();
+                if transaction.state_version <= version {
+                    break;
+                }
+                db.delete(&scrypto_encode(&("intent", transaction.hash)));
+                db.delete(&scrypto_encode(&("transaction_index", transaction.hash)));
+                db.delete(&scrypto_encode(&("transaction", transaction.index)));
+                count -= 1;
+            }
+            db.write(
+                &scrypto_encode(&"transaction_count"),
+                &scrypto_encode(&count),
+            );
+            db.write(&scrypto_encode(&"state_version"), &scrypto_encode(&version));
+        })
+    }
+
+    fn get_state_tree_node(&self, key: &StateTreeNodeKey, version: u64) -> Option<Hash> {
+        let prefix = scrypto_encode(&("state_tree", *key));
+        let mut node_id = prefix.clone();
+        node_id.extend(version.to_be_bytes());
+
+        let mut iter = self
+            .db
+            .iterator(IteratorMode::From(&node_id, Direction::Reverse));
+        match iter.next() {
+            Some((key, value)) if key.starts_with(&prefix) => Some(scrypto_decode(&value).unwrap()),
+            _ => None,
+        }
+    }
+
+    fn put_state_tree_node(&mut self, key: StateTreeNodeKey, version: u64, node: Hash) {
+        let mut node_id = scrypto_encode(&("state_tree", key));
+        node_id.extend(version.to_be_bytes());
+        self.write(&node_id, &scrypto_encode(&node));
+        let mut change_id = scrypto_encode(&("state_tree_changes", version));
+        change_id.extend(scrypto_encode(&key));
+        self.write(&change_id, &[]);
     }
 
     fn get_epoch(&self) -> u64 {
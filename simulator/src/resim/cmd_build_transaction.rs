@@ -0,0 +1,44 @@
+use clap::Parser;
+use colored::*;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Compiles a transaction manifest into a transaction to be signed by `sign-transaction`
+#[derive(Parser, Debug)]
+pub struct BuildTransaction {
+    /// The path to a transaction manifest file
+    path: PathBuf,
+
+    /// The path to write the transaction to
+    output: PathBuf,
+
+    /// The public key of the notary, who must also sign the transaction
+    #[clap(long)]
+    notary: Option<String>,
+}
+
+impl BuildTransaction {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let executor = TransactionExecutor::new(&mut ledger, false);
+        let manifest = std::fs::read_to_string(&self.path).map_err(Error::IOError)?;
+        let pre_processed_manifest = Run::pre_process_manifest(&manifest);
+        let mut transaction =
+            transaction_manifest::compile(&pre_processed_manifest).map_err(Error::CompileError)?;
+        set_transaction_header(&executor, &mut transaction);
+        transaction.add_nonce(executor.get_nonce([]));
+        if let Some(notary) = &self.notary {
+            transaction.header.notary_public_key = Some(parse_public_key(notary)?);
+        }
+
+        write_transaction(&self.output, &transaction.into_signed())?;
+        writeln!(
+            out,
+            "Transaction written to {}, with no signature yet.",
+            self.output.display().to_string().green()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -22,13 +22,25 @@ pub struct CallMethod {
     #[clap(short, long)]
     manifest: Option<PathBuf>,
 
-    /// The private keys used for signing, separated by comma
+    /// The private keys used for signing, separated by comma, with Ed25519 ones prefixed by `ed25519:`
     #[clap(short, long)]
     signing_keys: Option<String>,
 
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
+
+    /// Preview the transaction without committing it
+    #[clap(long)]
+    preview: bool,
+
+    /// When previewing, accept the signers without verifying their signatures
+    #[clap(long, requires = "preview")]
+    skip_signature_check: bool,
+
+    /// When previewing, ignore all method and resource access rules
+    #[clap(long, requires = "preview")]
+    skip_auth_check: bool,
 }
 
 impl CallMethod {
@@ -50,12 +62,25 @@ impl CallMethod {
             .map_err(Error::TransactionConstructionError)?
             .call_method_with_all_resources(default_account, "deposit_batch")
             .build_with_no_nonce();
-        process_transaction(
-            &mut executor,
-            transaction,
-            &self.signing_keys,
-            &self.manifest,
-            out,
-        )
+        if self.preview {
+            preview_transaction(
+                &mut executor,
+                transaction,
+                &self.signing_keys,
+                PreviewFlags {
+                    skip_signature_check: self.skip_signature_check,
+                    skip_auth_check: self.skip_auth_check,
+                },
+                out,
+            )
+        } else {
+            process_transaction(
+                &mut executor,
+                transaction,
+                &self.signing_keys,
+                &self.manifest,
+                out,
+            )
+        }
     }
 }
@@ -7,20 +7,34 @@ use crate::resim::*;
 
 /// Generate a key pair
 #[derive(Parser, Debug)]
-pub struct GenerateKeyPair {}
+pub struct GenerateKeyPair {
+    /// Generate an Ed25519 key pair, instead of ECDSA
+    #[clap(long)]
+    ed25519: bool,
+}
 
 impl GenerateKeyPair {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
         let secret = rand::thread_rng().gen::<[u8; 32]>();
-        let private_key = EcdsaPrivateKey::from_bytes(&secret).unwrap();
-        let public_key = private_key.public_key();
-        writeln!(out, "Public key: {}", public_key.to_string().green()).map_err(Error::IOError)?;
-        writeln!(
-            out,
-            "Private key: {}",
-            hex::encode(private_key.to_bytes()).green()
-        )
-        .map_err(Error::IOError)?;
+        let (public_key, private_key) = if self.ed25519 {
+            let private_key = Ed25519PrivateKey::from_bytes(&secret).unwrap();
+            (
+                private_key.public_key().to_string(),
+                format!(
+                    "{}{}",
+                    ED25519_PRIVATE_KEY_PREFIX,
+                    hex::encode(private_key.to_bytes())
+                ),
+            )
+        } else {
+            let private_key = EcdsaPrivateKey::from_bytes(&secret).unwrap();
+            (
+                private_key.public_key().to_string(),
+                hex::encode(private_key.to_bytes()),
+            )
+        };
+        writeln!(out, "Public key: {}", public_key.green()).map_err(Error::IOError)?;
+        writeln!(out, "Private key: {}", private_key.green()).map_err(Error::IOError)?;
         Ok(())
     }
 }
@@ -0,0 +1,35 @@
+use clap::Parser;
+use colored::*;
+
+use crate::ledger::*;
+use crate::resim::*;
+use crate::utils::*;
+
+/// List the committed transactions, in commit order
+#[derive(Parser, Debug)]
+pub struct ListTransactions {}
+
+impl ListTransactions {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+
+        let count = ledger.get_transaction_count();
+        writeln!(out, "{}: {}", "Transactions".green().bold(), count).map_err(Error::IOError)?;
+        for index in 0..count {
+            let transaction = ledger.get_transaction_by_index(index).unwrap();
+            writeln!(
+                out,
+                "{} #{} {} {}",
+                list_item_prefix(index == count - 1),
+                index,
+                transaction.hash,
+                match transaction.receipt.result {
+                    Ok(()) => "SUCCESS".blue(),
+                    Err(e) => e.red(),
+                }
+            )
+            .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
@@ -20,7 +20,7 @@ pub struct Mint {
     #[clap(short, long)]
     manifest: Option<PathBuf>,
 
-    /// The private keys used for signing, separated by comma
+    /// The private keys used for signing, separated by comma, with Ed25519 ones prefixed by `ed25519:`
     #[clap(short, long)]
     signing_keys: Option<String>,
 
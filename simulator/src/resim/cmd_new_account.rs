@@ -8,6 +8,14 @@ use crate::resim::*;
 /// Create an account
 #[derive(Parser, Debug)]
 pub struct NewAccount {
+    /// The ECDSA or Ed25519 public keys of a multi-signature account, separated by comma
+    #[clap(long)]
+    public_keys: Option<String>,
+
+    /// The number of signatures required by a multi-signature account, all by default
+    #[clap(long)]
+    threshold: Option<u8>,
+
     /// Output a transaction manifest without execution
     #[clap(short, long)]
     manifest: Option<PathBuf>,
@@ -22,6 +30,10 @@ impl NewAccount {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
         let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
 
+        if let Some(public_keys) = &self.public_keys {
+            return self.new_multisig_account(&mut executor, public_keys, out);
+        }
+
         if let Some(path) = &self.manifest {
             let secret = rand::thread_rng().gen::<[u8; 32]>();
             let private_key = EcdsaPrivateKey::from_bytes(&secret).unwrap();
@@ -79,4 +91,62 @@ impl NewAccount {
 
         Ok(())
     }
+
+    fn new_multisig_account<L: SubstateStore, O: std::io::Write>(
+        &self,
+        executor: &mut TransactionExecutor<L>,
+        public_keys: &str,
+        out: &mut O,
+    ) -> Result<(), Error> {
+        let public_keys = public_keys
+            .split(',')
+            .map(str::trim)
+            .map(parse_public_key)
+            .collect::<Result<Vec<PublicKey>, Error>>()?;
+        let threshold = match self.threshold {
+            Some(threshold) => threshold,
+            None => u8::try_from(public_keys.len())
+                .map_err(|_| Error::TooManyPublicKeys(public_keys.len()))?,
+        };
+        if threshold == 0 || threshold as usize > public_keys.len() {
+            return Err(Error::InvalidThreshold(threshold));
+        }
+
+        let mut builder = TransactionBuilder::new();
+        builder
+            .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+            .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+                builder.new_multisig_account_with_resource(threshold, &public_keys, bucket_id)
+            });
+
+        if let Some(path) = &self.manifest {
+            let transaction = builder.build_with_no_nonce();
+            process_transaction(executor, transaction, &None, &Some(path.clone()), out)?;
+            writeln!(out, "A manifest has been produced for the multi-signature account. To complete account creation, you will need to run the manifest!").map_err(Error::IOError)?;
+        } else {
+            let mut transaction = builder.build(executor.get_nonce([]));
+            set_transaction_header(executor, &mut transaction);
+            let receipt = executor
+                .validate_and_execute(&transaction.sign([]))
+                .map_err(Error::TransactionValidationError)?;
+            receipt.result.map_err(Error::TransactionExecutionError)?;
+            writeln!(out, "A new multi-signature account has been created!")
+                .map_err(Error::IOError)?;
+            writeln!(
+                out,
+                "Account component address: {}",
+                receipt.new_component_addresses[0].to_string().green()
+            )
+            .map_err(Error::IOError)?;
+            writeln!(
+                out,
+                "Signatures required: {} of {}",
+                threshold,
+                public_keys.len()
+            )
+            .map_err(Error::IOError)?;
+        }
+
+        Ok(())
+    }
 }
@@ -35,7 +35,7 @@ pub struct NewBadgeFixed {
     #[clap(short, long)]
     manifest: Option<PathBuf>,
 
-    /// The private keys used for signing, separated by comma
+    /// The private keys used for signing, separated by comma, with Ed25519 ones prefixed by `ed25519:`
     #[clap(short, long)]
     signing_keys: Option<String>,
 
@@ -0,0 +1,26 @@
+use clap::Parser;
+use radix_engine::ledger::SubstateStore;
+
+use crate::resim::*;
+
+/// Roll back the ledger state to an earlier state version
+#[derive(Parser, Debug)]
+pub struct Rollback {
+    /// The state version to roll back to
+    version: u64,
+}
+
+impl Rollback {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let current_version = ledger.get_state_version();
+        if self.version > current_version || self.version < ledger.get_base_state_version() {
+            return Err(Error::InvalidStateVersion(self.version));
+        }
+        ledger.rollback_to(self.version);
+
+        writeln!(out, "Ledger rolled back to state version {}!", self.version)
+            .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
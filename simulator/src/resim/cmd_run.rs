@@ -11,13 +11,25 @@ pub struct Run {
     /// The path to a transaction manifest file
     path: PathBuf,
 
-    /// The private keys used for signing, separated by comma
+    /// The private keys used for signing, separated by comma, with Ed25519 ones prefixed by `ed25519:`
     #[clap(short, long)]
     signing_keys: Option<String>,
 
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
+
+    /// Preview the transaction without committing it
+    #[clap(long)]
+    preview: bool,
+
+    /// When previewing, accept the signers without verifying their signatures
+    #[clap(long, requires = "preview")]
+    skip_signature_check: bool,
+
+    /// When previewing, ignore all method and resource access rules
+    #[clap(long, requires = "preview")]
+    skip_auth_check: bool,
 }
 
 impl Run {
@@ -36,7 +48,20 @@ impl Run {
         let pre_processed_manifest = Self::pre_process_manifest(&manifest);
         let transaction =
             transaction_manifest::compile(&pre_processed_manifest).map_err(Error::CompileError)?;
-        process_transaction(&mut executor, transaction, &self.signing_keys, &None, out)
+        if self.preview {
+            preview_transaction(
+                &mut executor,
+                transaction,
+                &self.signing_keys,
+                PreviewFlags {
+                    skip_signature_check: self.skip_signature_check,
+                    skip_auth_check: self.skip_auth_check,
+                },
+                out,
+            )
+        } else {
+            process_transaction(&mut executor, transaction, &self.signing_keys, &None, out)
+        }
     }
 }
 
@@ -10,6 +10,10 @@ use crate::resim::*;
 pub struct Show {
     /// The address of a package, component or resource manager
     address: String,
+
+    /// List the non-fungibles of the resource, with their data
+    #[clap(long)]
+    non_fungibles: bool,
 }
 
 impl Show {
@@ -21,7 +25,13 @@ impl Show {
         } else if let Ok(component_address) = ComponentAddress::from_str(&self.address) {
             dump_component(component_address, &ledger, out).map_err(Error::LedgerDumpError)
         } else if let Ok(resource_address) = ResourceAddress::from_str(&self.address) {
-            dump_resource_manager(resource_address, &ledger, out).map_err(Error::LedgerDumpError)
+            dump_resource_manager(resource_address, &ledger, out)
+                .map_err(Error::LedgerDumpError)?;
+            if self.non_fungibles {
+                dump_non_fungibles(resource_address, &ledger, out)
+                    .map_err(Error::LedgerDumpError)?;
+            }
+            Ok(())
         } else {
             Err(Error::InvalidId(self.address.clone()))
         }
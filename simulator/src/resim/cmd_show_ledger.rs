@@ -33,6 +33,20 @@ impl ShowLedger {
 
         writeln!(out, "{}: {}", "Nonce".green().bold(), ledger.get_nonce())
             .map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "{}: {}",
+            "State Version".green().bold(),
+            ledger.get_state_version()
+        )
+        .map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "{}: {}",
+            "State Root".green().bold(),
+            ledger.get_state_root(ledger.get_state_version())
+        )
+        .map_err(Error::IOError)?;
         Ok(())
     }
 }
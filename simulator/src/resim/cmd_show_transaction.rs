@@ -0,0 +1,22 @@
+use clap::Parser;
+use scrypto::crypto::Hash;
+use std::str::FromStr;
+
+use crate::ledger::*;
+use crate::resim::*;
+
+/// Show a committed transaction and its receipt
+#[derive(Parser, Debug)]
+pub struct ShowTransaction {
+    /// The transaction hash
+    hash: String,
+}
+
+impl ShowTransaction {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+
+        let hash = Hash::from_str(&self.hash).map_err(|_| Error::InvalidId(self.hash.clone()))?;
+        dump_transaction(hash, &ledger, out).map_err(Error::LedgerDumpError)
+    }
+}
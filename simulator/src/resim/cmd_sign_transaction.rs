@@ -0,0 +1,36 @@
+use clap::Parser;
+use colored::*;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Adds signatures to a transaction created by `build-transaction`
+#[derive(Parser, Debug)]
+pub struct SignTransaction {
+    /// The path to the transaction file, which is updated in place
+    path: PathBuf,
+
+    /// The private keys used for signing, separated by comma, with Ed25519 ones prefixed by `ed25519:`
+    #[clap(short, long)]
+    signing_keys: Option<String>,
+}
+
+impl SignTransaction {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let transaction = read_transaction(&self.path)?;
+        let sks = parse_signing_keys(&self.signing_keys)?;
+        let signed = sign_with(transaction, &sks);
+        write_transaction(&self.path, &signed)?;
+
+        for sk in &sks {
+            writeln!(
+                out,
+                "Signed by: {}",
+                format_public_key(&sk.public_key()).green()
+            )
+            .map_err(Error::IOError)?;
+        }
+        writeln!(out, "Total signatures: {}", signed.signatures.len()).map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use clap::{Parser, Subcommand};
+use radix_engine::ledger::LedgerSnapshot;
+use scrypto::buffer::*;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Save the ledger state to a file, or replace it with the one in a file
+#[derive(Parser, Debug)]
+pub struct Snapshot {
+    #[clap(subcommand)]
+    action: SnapshotAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotAction {
+    /// Save the ledger state to a file
+    Save {
+        /// The path to the snapshot file
+        path: PathBuf,
+    },
+    /// Replace the ledger state with the one in a file
+    Load {
+        /// The path to the snapshot file
+        path: PathBuf,
+    },
+}
+
+impl Snapshot {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        match &self.action {
+            SnapshotAction::Save { path } => {
+                let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+                let snapshot = ledger.export_snapshot();
+                fs::write(path, scrypto_encode(&snapshot)).map_err(Error::IOError)?;
+                writeln!(out, "Snapshot saved to {}!", path.display()).map_err(Error::IOError)?;
+            }
+            SnapshotAction::Load { path } => {
+                let bytes = fs::read(path).map_err(Error::IOError)?;
+                let snapshot: LedgerSnapshot = scrypto_decode(&bytes).map_err(Error::DataError)?;
+                let mut ledger = RadixEngineDB::new(get_data_dir()?);
+                ledger.import_snapshot(snapshot);
+                writeln!(out, "Snapshot loaded from {}!", path.display())
+                    .map_err(Error::IOError)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Submits a transaction signed by `sign-transaction`
+#[derive(Parser, Debug)]
+pub struct SubmitTransaction {
+    /// The path to the transaction file
+    path: PathBuf,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl SubmitTransaction {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let transaction = read_transaction(&self.path)?;
+        let receipt = executor
+            .validate_and_execute(&transaction)
+            .map_err(Error::TransactionValidationError)?;
+        writeln!(out, "{:?}", receipt).map_err(Error::IOError)?;
+        receipt.result.map_err(Error::TransactionExecutionError)
+    }
+}
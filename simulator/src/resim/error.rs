@@ -43,4 +43,12 @@ pub enum Error {
     InvalidId(String),
 
     InvalidPrivateKey,
+
+    InvalidPublicKey(String),
+
+    InvalidThreshold(u8),
+
+    TooManyPublicKeys(usize),
+
+    InvalidStateVersion(u64),
 }
@@ -1,7 +1,9 @@
+mod cmd_build_transaction;
 mod cmd_call_function;
 mod cmd_call_method;
 mod cmd_export_abi;
 mod cmd_generate_key_pair;
+mod cmd_list_transactions;
 mod cmd_mint;
 mod cmd_new_account;
 mod cmd_new_badge_fixed;
@@ -10,20 +12,27 @@ mod cmd_new_token_fixed;
 mod cmd_new_token_mutable;
 mod cmd_publish;
 mod cmd_reset;
+mod cmd_rollback;
 mod cmd_run;
 mod cmd_set_current_epoch;
 mod cmd_set_default_account;
 mod cmd_show;
 mod cmd_show_configs;
 mod cmd_show_ledger;
+mod cmd_show_transaction;
+mod cmd_sign_transaction;
+mod cmd_snapshot;
+mod cmd_submit_transaction;
 mod cmd_transfer;
 mod config;
 mod error;
 
+pub use cmd_build_transaction::*;
 pub use cmd_call_function::*;
 pub use cmd_call_method::*;
 pub use cmd_export_abi::*;
 pub use cmd_generate_key_pair::*;
+pub use cmd_list_transactions::*;
 pub use cmd_mint::*;
 pub use cmd_new_account::*;
 pub use cmd_new_badge_fixed::*;
@@ -32,12 +41,17 @@ pub use cmd_new_token_fixed::*;
 pub use cmd_new_token_mutable::*;
 pub use cmd_publish::*;
 pub use cmd_reset::*;
+pub use cmd_rollback::*;
 pub use cmd_run::*;
 pub use cmd_set_current_epoch::*;
 pub use cmd_set_default_account::*;
 pub use cmd_show::*;
 pub use cmd_show_configs::*;
 pub use cmd_show_ledger::*;
+pub use cmd_show_transaction::*;
+pub use cmd_sign_transaction::*;
+pub use cmd_snapshot::*;
+pub use cmd_submit_transaction::*;
 pub use cmd_transfer::*;
 pub use config::*;
 pub use error::*;
@@ -45,15 +59,21 @@ pub use error::*;
 pub const DEFAULT_SCRYPTO_DIR_UNDER_HOME: &'static str = ".scrypto";
 pub const ENV_DATA_DIR: &'static str = "DATA_DIR";
 pub const ENV_DISABLE_MANIFEST_OUTPUT: &'static str = "DISABLE_MANIFEST_OUTPUT";
+/// The number of epochs, from the current one, in which transactions built by resim can be committed
+pub const TRANSACTION_VALIDITY_EPOCHS: u64 = 100;
+/// The prefix of Ed25519 private keys, which tells them apart from ECDSA ones of the same length
+pub const ED25519_PRIVATE_KEY_PREFIX: &str = "ed25519:";
 
 use clap::{Parser, Subcommand};
 use radix_engine::ledger::*;
 use radix_engine::model::*;
 use radix_engine::transaction::*;
+use scrypto::buffer::scrypto_decode;
 use scrypto::crypto::*;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
+use std::str::FromStr;
 use transaction_manifest::decompile;
 
 use crate::ledger::*;
@@ -74,10 +94,12 @@ impl ResimCli {
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    BuildTransaction(BuildTransaction),
     CallFunction(CallFunction),
     CallMethod(CallMethod),
     ExportAbi(ExportAbi),
     GenerateKeyPair(GenerateKeyPair),
+    ListTransactions(ListTransactions),
     Mint(Mint),
     NewAccount(NewAccount),
     NewBadgeFixed(NewBadgeFixed),
@@ -86,12 +108,17 @@ pub enum Command {
     NewTokenMutable(NewTokenMutable),
     Publish(Publish),
     Reset(Reset),
+    Rollback(Rollback),
     Run(Run),
     SetCurrentEpoch(SetCurrentEpoch),
     SetDefaultAccount(SetDefaultAccount),
     ShowConfigs(ShowConfigs),
     ShowLedger(ShowLedger),
+    ShowTransaction(ShowTransaction),
     Show(Show),
+    SignTransaction(SignTransaction),
+    Snapshot(Snapshot),
+    SubmitTransaction(SubmitTransaction),
     Transfer(Transfer),
 }
 
@@ -101,10 +128,12 @@ pub fn run() -> Result<(), Error> {
     let mut out = std::io::stdout();
 
     match cli.command {
+        Command::BuildTransaction(cmd) => cmd.run(&mut out),
         Command::CallFunction(cmd) => cmd.run(&mut out),
         Command::CallMethod(cmd) => cmd.run(&mut out),
         Command::ExportAbi(cmd) => cmd.run(&mut out),
         Command::GenerateKeyPair(cmd) => cmd.run(&mut out),
+        Command::ListTransactions(cmd) => cmd.run(&mut out),
         Command::Mint(cmd) => cmd.run(&mut out),
         Command::NewAccount(cmd) => cmd.run(&mut out),
         Command::NewBadgeFixed(cmd) => cmd.run(&mut out),
@@ -113,19 +142,24 @@ pub fn run() -> Result<(), Error> {
         Command::NewTokenMutable(cmd) => cmd.run(&mut out),
         Command::Publish(cmd) => cmd.run(&mut out),
         Command::Reset(cmd) => cmd.run(&mut out),
+        Command::Rollback(cmd) => cmd.run(&mut out),
         Command::Run(cmd) => cmd.run(&mut out),
         Command::SetCurrentEpoch(cmd) => cmd.run(&mut out),
         Command::SetDefaultAccount(cmd) => cmd.run(&mut out),
         Command::ShowConfigs(cmd) => cmd.run(&mut out),
         Command::ShowLedger(cmd) => cmd.run(&mut out),
+        Command::ShowTransaction(cmd) => cmd.run(&mut out),
         Command::Show(cmd) => cmd.run(&mut out),
+        Command::SignTransaction(cmd) => cmd.run(&mut out),
+        Command::Snapshot(cmd) => cmd.run(&mut out),
+        Command::SubmitTransaction(cmd) => cmd.run(&mut out),
         Command::Transfer(cmd) => cmd.run(&mut out),
     }
 }
 
 pub fn process_transaction<L: SubstateStore, O: std::io::Write>(
     executor: &mut TransactionExecutor<L>,
-    mut transaction: Transaction,
+    transaction: Transaction,
     signing_keys: &Option<String>,
     manifest_path: &Option<PathBuf>,
     out: &mut O,
@@ -140,14 +174,7 @@ pub fn process_transaction<L: SubstateStore, O: std::io::Write>(
             }
         }
         None => {
-            let sks = parse_signing_keys(signing_keys)?;
-            let pks = sks
-                .iter()
-                .map(|e| e.public_key())
-                .collect::<Vec<EcdsaPublicKey>>();
-            let nonce = executor.get_nonce(&pks);
-            transaction.add_nonce(nonce);
-            let signed = transaction.sign(sks.iter().collect::<Vec<&EcdsaPrivateKey>>());
+            let signed = sign_transaction(executor, transaction, signing_keys)?;
             let receipt = executor
                 .validate_and_execute(&signed)
                 .map_err(Error::TransactionValidationError)?;
@@ -157,21 +184,126 @@ pub fn process_transaction<L: SubstateStore, O: std::io::Write>(
     }
 }
 
-pub fn parse_signing_keys(signing_keys: &Option<String>) -> Result<Vec<EcdsaPrivateKey>, Error> {
+pub fn preview_transaction<L: SubstateStore, O: std::io::Write>(
+    executor: &mut TransactionExecutor<L>,
+    transaction: Transaction,
+    signing_keys: &Option<String>,
+    flags: PreviewFlags,
+    out: &mut O,
+) -> Result<(), Error> {
+    let signed = sign_transaction(executor, transaction, signing_keys)?;
+    let validated = signed
+        .validate_preview(&flags)
+        .map_err(Error::TransactionValidationError)?;
+    let receipt = executor.preview(&validated, flags);
+    writeln!(out, "{:?}", receipt).map_err(Error::IOError)?;
+    receipt.result.map_err(Error::TransactionExecutionError)
+}
+
+fn sign_transaction<L: SubstateStore>(
+    executor: &TransactionExecutor<L>,
+    mut transaction: Transaction,
+    signing_keys: &Option<String>,
+) -> Result<SignedTransaction, Error> {
+    let sks = parse_signing_keys(signing_keys)?;
+    let pks = sks
+        .iter()
+        .filter_map(|sk| match sk {
+            SigningKey::Ecdsa(sk) => Some(sk.public_key()),
+            SigningKey::Ed25519(_) => None,
+        })
+        .collect::<Vec<EcdsaPublicKey>>();
+    let nonce = executor.get_nonce(&pks);
+    set_transaction_header(executor, &mut transaction);
+    transaction.add_nonce(nonce);
+    Ok(sign_with(transaction.into_signed(), &sks))
+}
+
+/// Scopes a transaction to the network of the simulator ledger, from its current epoch.
+pub fn set_transaction_header<L: SubstateStore>(
+    executor: &TransactionExecutor<L>,
+    transaction: &mut Transaction,
+) {
+    let ledger = executor.substate_store();
+    let current_epoch = ledger.get_epoch();
+    transaction.header.network_id = ledger.get_network_id();
+    transaction.header.start_epoch_inclusive = current_epoch;
+    transaction.header.end_epoch_exclusive = current_epoch + TRANSACTION_VALIDITY_EPOCHS;
+}
+
+/// Reads a transaction, with the signatures collected so far, from a file.
+pub fn read_transaction(path: &PathBuf) -> Result<SignedTransaction, Error> {
+    let bytes = fs::read(path).map_err(Error::IOError)?;
+    scrypto_decode(&bytes).map_err(Error::DataError)
+}
+
+/// Writes a transaction, with the signatures collected so far, to a file.
+pub fn write_transaction(path: &PathBuf, transaction: &SignedTransaction) -> Result<(), Error> {
+    fs::write(path, transaction.to_vec()).map_err(Error::IOError)
+}
+
+/// A private key of any supported signature scheme.
+pub enum SigningKey {
+    Ecdsa(EcdsaPrivateKey),
+    Ed25519(Ed25519PrivateKey),
+}
+
+impl SigningKey {
+    pub fn public_key(&self) -> PublicKey {
+        match self {
+            SigningKey::Ecdsa(sk) => sk.public_key().into(),
+            SigningKey::Ed25519(sk) => sk.public_key().into(),
+        }
+    }
+}
+
+/// Adds the signatures of the given private keys to a transaction.
+pub fn sign_with(transaction: SignedTransaction, sks: &[SigningKey]) -> SignedTransaction {
+    sks.iter().fold(transaction, |transaction, sk| match sk {
+        SigningKey::Ecdsa(sk) => transaction.sign([sk]),
+        SigningKey::Ed25519(sk) => transaction.sign_ed25519([sk]),
+    })
+}
+
+/// Parses private keys, separated by comma, with Ed25519 ones prefixed by `ed25519:`.
+pub fn parse_signing_keys(signing_keys: &Option<String>) -> Result<Vec<SigningKey>, Error> {
     let private_keys = if let Some(keys) = signing_keys {
         keys.split(",")
             .map(str::trim)
-            .map(|key| {
-                hex::decode(key)
+            .map(|key| match key.strip_prefix(ED25519_PRIVATE_KEY_PREFIX) {
+                Some(key) => hex::decode(key)
+                    .map_err(|_| Error::InvalidPrivateKey)
+                    .and_then(|bytes| {
+                        Ed25519PrivateKey::from_bytes(&bytes).map_err(|_| Error::InvalidPrivateKey)
+                    })
+                    .map(SigningKey::Ed25519),
+                None => hex::decode(key)
                     .map_err(|_| Error::InvalidPrivateKey)
                     .and_then(|bytes| {
                         EcdsaPrivateKey::from_bytes(&bytes).map_err(|_| Error::InvalidPrivateKey)
                     })
+                    .map(SigningKey::Ecdsa),
             })
-            .collect::<Result<Vec<EcdsaPrivateKey>, Error>>()?
+            .collect::<Result<Vec<SigningKey>, Error>>()?
     } else {
-        vec![get_default_private_key()?]
+        vec![SigningKey::Ecdsa(get_default_private_key()?)]
     };
 
     Ok(private_keys)
 }
+
+/// Parses a public key of any supported signature scheme, told apart by its length.
+pub fn parse_public_key(key: &str) -> Result<PublicKey, Error> {
+    EcdsaPublicKey::from_str(key)
+        .map(PublicKey::from)
+        .or_else(|_| Ed25519PublicKey::from_str(key).map(PublicKey::from))
+        .map_err(|_| Error::InvalidPublicKey(key.to_owned()))
+}
+
+/// Formats a public key of any supported signature scheme as hex.
+pub fn format_public_key(public_key: &PublicKey) -> String {
+    match public_key {
+        PublicKey::Ecdsa(pk) => pk.to_string(),
+        PublicKey::Ed25519(pk) => pk.to_string(),
+    }
+}
@@ -27,6 +27,8 @@ $resim transfer 111 $token_address $account2
 package=`$resim publish ../examples/hello-world | awk '/Package:/ {print $NF}'`
 component=`$resim call-function $package Hello instantiate_hello | awk '/Component:/ {print $NF}'`
 $resim call-method $component free_token
+$resim call-method $component free_token --preview
+$resim call-method $component free_token --preview --skip-signature-check --skip-auth-check
 
 # Test - export abi
 $resim export-abi $package Hello
@@ -38,11 +40,17 @@ $resim show $account
 $resim show $account2
 $resim show $token_address
 
+# Test - list non-fungibles
+nft_package=`$resim publish ./tests/nft | awk '/Package:/ {print $NF}'`
+nft_address=`$resim call-function $nft_package Foo nfts | awk '/Resource:/ {print $NF}'`
+$resim show $nft_address --non-fungibles
+
 # Test - output manifest
 $resim new-badge-fixed 1 --name 'MintBadge' --manifest ./target/temp.rtm
 cat ./target/temp.rtm
 $resim publish ../examples/hello-world --manifest ./target/temp2.rtm
 $resim run ./target/temp2.rtm
+$resim run ./target/temp2.rtm --preview
 $resim new-account --manifest ./target/temp3.rtm
 $resim run ./target/temp3.rtm
 
@@ -50,6 +58,36 @@ $resim run ./target/temp3.rtm
 $resim generate-key-pair
 $resim run ./target/temp2.rtm --signing-keys 4fc0db017bf9b80743b7151fee3f04bad817f2d8d7e34ae96c022fe7451b0ea3,329a27258d7e9496c42a110571e6ba0d47f2bda8bd610e9777d16853ab145b0c
 
+# Test - multi-signature account, signed in separate steps
+pk1=`$resim generate-key-pair | tee ./target/key1.txt | awk '/Public key:/ {print $NF}'`
+sk1=`awk '/Private key:/ {print $NF}' ./target/key1.txt`
+pk2=`$resim generate-key-pair --ed25519 | tee ./target/key2.txt | awk '/Public key:/ {print $NF}'`
+sk2=`awk '/Private key:/ {print $NF}' ./target/key2.txt`
+pk3=`$resim generate-key-pair | awk '/Public key:/ {print $NF}'`
+multisig_account=`$resim new-account --public-keys $pk1,$pk2,$pk3 --threshold 2 | awk '/Account component address:/ {print $NF}'`
+echo "CALL_METHOD ComponentAddress(\"$multisig_account\") \"withdraw\" ResourceAddress(\"030000000000000000000000000000000000000000000000000004\");" > ./target/multisig.rtm
+echo "CALL_METHOD_WITH_ALL_RESOURCES ComponentAddress(\"$account\") \"deposit_batch\";" >> ./target/multisig.rtm
+$resim build-transaction ./target/multisig.rtm ./target/multisig.tx
+$resim sign-transaction ./target/multisig.tx --signing-keys $sk1
+$resim sign-transaction ./target/multisig.tx --signing-keys $sk2
+$resim submit-transaction ./target/multisig.tx
+
+# Test - transaction history
+$resim list-transactions
+last_transaction=`$resim list-transactions | tail -n 1 | awk '{print $3}'`
+$resim show-transaction $last_transaction
+
+# Test - rollback
+state_version=`$resim show-ledger | awk '/State Version:/ {print $NF}'`
+$resim new-badge-fixed 1 --name 'RollbackBadge'
+$resim rollback $state_version
+
+# Test - snapshot
+$resim snapshot save ./target/ledger.snapshot
+$resim new-badge-fixed 1 --name 'SnapshotBadge'
+$resim snapshot load ./target/ledger.snapshot
+$resim show $account
+
 # Test - nft
 package=`$resim publish ./tests/nft | awk '/Package:/ {print $NF}'`
 $resim call-function $package Foo nfts
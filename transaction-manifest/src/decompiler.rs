@@ -53,10 +53,7 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                 buckets.insert(bucket_id, name.clone());
                 buf.push_str(&format!(
                     "TAKE_FROM_WORKTOP_BY_IDS TreeSet<NonFungibleId>({}) ResourceAddress(\"{}\") Bucket(\"{}\");\n",
-                    ids.iter()
-                    .map(|k| format!("NonFungibleId(\"{}\")", k))
-                    .collect::<Vec<String>>()
-                    .join(", "),
+                    format_non_fungible_ids(&ids),
                     resource_address, name
                 ));
             }
@@ -93,10 +90,7 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
             } => {
                 buf.push_str(&format!(
                     "ASSERT_WORKTOP_CONTAINS_BY_IDS TreeSet<NonFungibleId>({}) ResourceAddress(\"{}\");\n",
-                    ids.iter()
-                        .map(|k| format!("NonFungibleId(\"{}\")", k))
-                        .collect::<Vec<String>>()
-                        .join(", "),
+                    format_non_fungible_ids(&ids),
                     resource_address
                 ));
             }
@@ -159,10 +153,8 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                 let name = format!("proof{}", proofs.len() + 1);
                 proofs.insert(proof_id, name.clone());
                 buf.push_str(&format!(
-                    "CREATE_PROOF_FROM_AUTH_ZONE_BY_IDS TreeSet<NonFungibleId>({}) ResourceAddress(\"{}\") Proof(\"{}\");\n",ids.iter()
-                    .map(|k| format!("NonFungibleId(\"{}\")", k))
-                    .collect::<Vec<String>>()
-                    .join(", "),
+                    "CREATE_PROOF_FROM_AUTH_ZONE_BY_IDS TreeSet<NonFungibleId>({}) ResourceAddress(\"{}\") Proof(\"{}\");\n",
+                    format_non_fungible_ids(&ids),
                     resource_address, name
                 ));
             }
@@ -267,15 +259,21 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                     hex::encode(&code)
                 ));
             }
-            Instruction::Nonce { .. } => {
-                // TODO: add support for this
-            }
         }
     }
 
     Ok(buf)
 }
 
+/// Formats non-fungible ids in hex, which the generator parses back into the same ids whatever
+/// their type, as the type is only known to the resource manager.
+fn format_non_fungible_ids(ids: &BTreeSet<NonFungibleId>) -> String {
+    ids.iter()
+        .map(|id| format!("NonFungibleId(\"{}\")", id))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,4 +288,19 @@ mod tests {
 
         assert_eq!(compile(manifest).unwrap(), tx);
     }
+
+    #[test]
+    fn test_decompile_typed_non_fungible_ids() {
+        let tx = compile(
+            r#"
+CALL_METHOD ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "withdraw_non_fungibles" TreeSet<NonFungibleId>(NonFungibleId(5u32), NonFungibleId(5u64), NonFungibleId(5u128), NonFungibleId(String("five")));
+TAKE_FROM_WORKTOP_BY_IDS TreeSet<NonFungibleId>(NonFungibleId(5u32), NonFungibleId(String("five"))) ResourceAddress("030000000000000000000000000000000000000000000000000004") Bucket("nfts");
+"#,
+        )
+        .unwrap();
+
+        let manifest = &decompile(&tx).unwrap();
+
+        assert_eq!(compile(manifest).unwrap(), tx);
+    }
 }
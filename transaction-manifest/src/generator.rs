@@ -109,7 +109,10 @@ pub fn generate_transaction(tx: &ast::Transaction) -> Result<Transaction, Genera
         )?);
     }
 
-    Ok(Transaction { instructions })
+    Ok(Transaction {
+        header: TransactionHeader::default(),
+        instructions,
+    })
 }
 
 pub fn generate_instruction(
@@ -521,7 +524,18 @@ fn generate_non_fungible_id(value: &ast::Value) -> Result<NonFungibleId, Generat
         ast::Value::NonFungibleId(inner) => match &**inner {
             ast::Value::String(s) => NonFungibleId::from_str(s)
                 .map_err(|_| GeneratorError::InvalidNonFungibleId(s.into())),
-            v @ _ => invalid_type!(v, ast::Type::String),
+            ast::Value::Bytes(bytes) => Ok(NonFungibleId::from_bytes(bytes.clone())),
+            ast::Value::U32(n) => Ok(NonFungibleId::from_u32(*n)),
+            ast::Value::U64(n) => Ok(NonFungibleId::from_u64(*n)),
+            ast::Value::U128(n) => Ok(NonFungibleId::from_uuid(*n)),
+            v @ _ => invalid_type!(
+                v,
+                ast::Type::String,
+                ast::Type::Bytes,
+                ast::Type::U32,
+                ast::Type::U64,
+                ast::Type::U128
+            ),
         },
         v @ _ => invalid_type!(v, ast::Type::NonFungibleId),
     }
@@ -856,6 +870,44 @@ mod tests {
                 ]
             }
         );
+        generate_value_ok!(
+            r#"Struct(NonFungibleId("0a"), NonFungibleId(5u32), NonFungibleId(5u64), NonFungibleId(5u128))"#,
+            Value::Struct {
+                fields: vec![
+                    Value::Custom {
+                        type_id: ScryptoType::NonFungibleId.id(),
+                        bytes: NonFungibleId::from_bytes(vec![10]).to_vec()
+                    },
+                    Value::Custom {
+                        type_id: ScryptoType::NonFungibleId.id(),
+                        bytes: NonFungibleId::from_u32(5).to_vec()
+                    },
+                    Value::Custom {
+                        type_id: ScryptoType::NonFungibleId.id(),
+                        bytes: NonFungibleId::from_u64(5).to_vec()
+                    },
+                    Value::Custom {
+                        type_id: ScryptoType::NonFungibleId.id(),
+                        bytes: NonFungibleId::from_uuid(5).to_vec()
+                    },
+                ]
+            }
+        );
+        generate_value_ok!(
+            r#"Struct(NonFungibleId(String("0a")), NonFungibleId(Bytes("0a")))"#,
+            Value::Struct {
+                fields: vec![
+                    Value::Custom {
+                        type_id: ScryptoType::NonFungibleId.id(),
+                        bytes: NonFungibleId::from_string("0a").to_vec()
+                    },
+                    Value::Custom {
+                        type_id: ScryptoType::NonFungibleId.id(),
+                        bytes: NonFungibleId::from_bytes(vec![10]).to_vec()
+                    },
+                ]
+            }
+        );
         generate_value_ok!(r#"Struct()"#, Value::Struct { fields: vec![] });
         generate_value_ok!(
             r#"Enum("Variant", "abc")"#,
@@ -1049,6 +1101,7 @@ mod tests {
         assert_eq!(
             crate::compile(tx).unwrap(),
             Transaction {
+                header: TransactionHeader::default(),
                 instructions: vec![
                     Instruction::CallMethod {
                         component_address: ComponentAddress::from_str(
@@ -348,7 +348,9 @@ impl Parser {
             TokenKind::Hash => Ok(Value::Hash(self.parse_values_one()?.into())),
             TokenKind::Bucket => Ok(Value::Bucket(self.parse_values_one()?.into())),
             TokenKind::Proof => Ok(Value::Proof(self.parse_values_one()?.into())),
-            TokenKind::NonFungibleId => Ok(Value::NonFungibleId(self.parse_values_one()?.into())),
+            TokenKind::NonFungibleId => Ok(Value::NonFungibleId(
+                self.parse_non_fungible_id_value()?.into(),
+            )),
             TokenKind::NonFungibleAddress => {
                 Ok(Value::NonFungibleAddress(self.parse_values_one()?.into()))
             }
@@ -356,6 +358,27 @@ impl Parser {
         }
     }
 
+    /// Parse the value of a `NonFungibleId`, where `String("...")` stands for the UTF-8 bytes
+    /// of a string.
+    fn parse_non_fungible_id_value(&mut self) -> Result<Value, ParserError> {
+        advance_match!(self, TokenKind::OpenParenthesis);
+        let value = if self.peek()?.kind == TokenKind::String {
+            advance_match!(self, TokenKind::String);
+            advance_match!(self, TokenKind::OpenParenthesis);
+            let token = self.advance()?;
+            let value = match token.kind {
+                TokenKind::StringLiteral(s) => Value::Bytes(s.into_bytes()),
+                _ => return Err(ParserError::UnexpectedToken(token)),
+            };
+            advance_match!(self, TokenKind::CloseParenthesis);
+            value
+        } else {
+            self.parse_value()?
+        };
+        advance_match!(self, TokenKind::CloseParenthesis);
+        Ok(value)
+    }
+
     /// Parse a comma-separated value list, enclosed by a pair of marks.
     fn parse_values_any(
         &mut self,